@@ -68,3 +68,5 @@ id!(SystemId, u64);
 id!(EntrantId, u64);
 id!(UserId, u64);
 id!(EventId, u64);
+id!(WebhookId, u64);
+id!(DeliveryId, u64);
@@ -1,4 +1,7 @@
-use self::{auth::AuthClient, systems::SystemsClient, tournaments::TournamentsClient};
+use self::{
+    auth::AuthClient, systems::SystemsClient, tournaments::log::SystemLogClient,
+    tournaments::TournamentsClient, users::UsersClient,
+};
 
 pub mod auth;
 pub mod id;
@@ -27,4 +30,12 @@ impl<'a> Client<'a> {
     pub fn tournaments(&self) -> TournamentsClient {
         TournamentsClient::new(self.inner)
     }
+
+    pub fn log(&self) -> SystemLogClient {
+        SystemLogClient::new(self.inner)
+    }
+
+    pub fn users(&self) -> UsersClient {
+        UsersClient::new(self.inner)
+    }
 }
@@ -1,10 +1,14 @@
 pub mod brackets;
 pub mod entrants;
 pub mod log;
+pub mod matches;
+pub mod ratings;
 pub mod roles;
+pub mod webhooks;
 
 use self::{
-    brackets::BracketsClient, entrants::EntrantsClient, log::EventLogClient, roles::RolesClient,
+    brackets::BracketsClient, entrants::EntrantsClient, log::EventLogClient,
+    matches::MatchesClient, ratings::RatingsClient, roles::RolesClient, webhooks::WebhooksClient,
 };
 
 use super::id::TournamentId;
@@ -278,4 +282,16 @@ impl<'a> TournamentsClient<'a> {
     pub fn log(&self, tournament_id: TournamentId) -> EventLogClient {
         EventLogClient::new(self.client, tournament_id)
     }
+
+    pub fn ratings(&self, tournament_id: TournamentId) -> RatingsClient {
+        RatingsClient::new(self.client, tournament_id)
+    }
+
+    pub fn webhooks(&self, tournament_id: TournamentId) -> WebhooksClient {
+        WebhooksClient::new(self.client, tournament_id)
+    }
+
+    pub fn matches(&self, tournament_id: TournamentId) -> MatchesClient {
+        MatchesClient::new(self.client, tournament_id)
+    }
 }
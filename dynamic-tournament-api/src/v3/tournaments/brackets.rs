@@ -1,9 +1,18 @@
 pub mod matches;
+pub mod schedule;
+pub mod veto;
 
+use chrono::{DateTime, Utc};
 use dynamic_tournament_core::options::TournamentOptionValues;
+use dynamic_tournament_core::rounds::Rounds;
+use dynamic_tournament_core::scenarios::Status;
+use dynamic_tournament_core::{EntrantScore, Matches};
 use serde::{Deserialize, Serialize};
 
-use crate::v3::id::{BracketId, EntrantId, SystemId, TournamentId};
+use self::matches::MetadataClient;
+use self::schedule::ScheduleClient;
+use self::veto::VetoClient;
+use crate::v3::id::{BracketId, EntrantId, EventId, SystemId, TournamentId};
 use crate::websocket::WebSocketBuilder;
 use crate::{Client, Result};
 
@@ -24,6 +33,162 @@ pub struct Bracket {
     /// An ordered list of the entrants playing in the bracket. Note that the order may be
     /// important and defines the initial placements if seeding is disabled.
     pub entrants: Vec<EntrantId>,
+    #[serde(default)]
+    pub seeding: Seeding,
+}
+
+impl Bracket {
+    /// Updates an existing `Bracket` by applying a [`PartialBracket`] patch on it.
+    pub fn update(&mut self, patch: PartialBracket) {
+        if let Some(name) = patch.name {
+            self.name = name;
+        }
+
+        if let Some(options) = patch.options {
+            self.options = options;
+        }
+
+        // An explicit order of the entrants replaces the previous seeding.
+        if let Some(entrants) = patch.entrants {
+            self.entrants = entrants;
+            self.seeding = Seeding::Ordered;
+        }
+    }
+}
+
+/// A [`Bracket`] with all optional fields. This is primarly useful for `PATCH` requests.
+///
+/// The `options` and `entrants` can only be changed while the bracket has no results.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartialBracket {
+    pub name: Option<String>,
+    pub options: Option<TournamentOptionValues>,
+    pub entrants: Option<Vec<EntrantId>>,
+}
+
+/// The method used to determine the initial placements of the entrants in a [`Bracket`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Seeding {
+    /// The entrants are placed in the order of [`Bracket::entrants`].
+    #[default]
+    Ordered,
+    /// The entrants are placed in a random order. The entrants are sorted by their id and then
+    /// shuffled using [`shuffle`] with the `seed`.
+    ///
+    /// If no `seed` is given when creating the bracket, the server generates one. The `seed` of
+    /// an existing bracket is always set.
+    ///
+    /// [`shuffle`]: dynamic_tournament_core::seeding::shuffle
+    Shuffle {
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+}
+
+impl Seeding {
+    /// Applies the `Seeding` to `entrants`. Does nothing for [`Seeding::Ordered`] or if no seed
+    /// is set.
+    pub fn apply(&self, entrants: &mut [EntrantId]) {
+        if let Self::Shuffle { seed: Some(seed) } = self {
+            entrants.sort_unstable();
+            dynamic_tournament_core::seeding::shuffle(entrants, *seed);
+        }
+    }
+}
+
+/// The predicted outcome of a bracket, based on the ratings of all entrants.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Prediction {
+    /// The number of simulated runs the prediction is based on.
+    pub iterations: u64,
+    /// The prediction for every entrant in the same order as [`Bracket::entrants`].
+    pub entrants: Vec<EntrantPrediction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntrantPrediction {
+    pub entrant: EntrantId,
+    /// The probability of finishing at every position, starting with the first position.
+    pub placements: Vec<f64>,
+}
+
+impl EntrantPrediction {
+    /// Returns the probability of winning the bracket.
+    #[inline]
+    pub fn win(&self) -> f64 {
+        self.placements.first().copied().unwrap_or(0.0)
+    }
+}
+
+/// The progress of a bracket and the estimated times of all remaining matches.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Progress {
+    /// The number of concluded matches.
+    pub completed: usize,
+    /// The number of matches that are played in total, excluding byes.
+    pub total: usize,
+    /// The match duration in seconds the estimates are based on.
+    pub match_duration: u64,
+    /// The estimated end of the last match. `None` if all matches are concluded.
+    pub end: Option<DateTime<Utc>>,
+    /// The indices of all remaining matches on the critical path in the order they are played.
+    pub critical_path: Vec<usize>,
+    /// The estimate for every match in the bracket. A `None` value indicates that the match is
+    /// concluded or never played (e.g. a bye).
+    pub matches: Vec<Option<MatchEstimate>>,
+}
+
+impl Progress {
+    /// Returns the percentage of concluded matches in the range `0.0..=100.0`.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.completed as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// The estimated start and end time of a match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchEstimate {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// The clinch and elimination scenarios of all entrants in a bracket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Scenarios {
+    /// The number of positions that advance.
+    pub top: usize,
+    /// The number of possible outcomes of the remaining matches.
+    pub outcomes: u64,
+    /// The scenarios for every entrant in the same order as [`Bracket::entrants`].
+    pub entrants: Vec<EntrantScenarios>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntrantScenarios {
+    pub entrant: EntrantId,
+    pub status: Status,
+    /// The number of outcomes in which the entrant can advance.
+    pub advancing: u64,
+    /// The results required for the entrant to advance. Only set if the entrant is
+    /// [`Status::Alive`].
+    pub requirements: Vec<Requirement>,
+}
+
+/// A match result required for an entrant to advance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Requirement {
+    /// The index of the match.
+    pub index: usize,
+    /// The match code of the match, e.g. `R3-M2`.
+    pub code: String,
+    /// The entrant that needs to win the match.
+    pub winner: EntrantId,
 }
 
 #[derive(Clone, Debug)]
@@ -68,6 +233,26 @@ impl<'a> BracketsClient<'a> {
         self.client.send(req).await?.json().await
     }
 
+    /// Updates the bracket with the given `id` using the given bracket. Returns the updated
+    /// [`Bracket`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn patch(&self, id: BracketId, bracket: &PartialBracket) -> Result<Bracket> {
+        let uri = format!("/v3/tournaments/{}/brackets/{}", self.tournament_id, id);
+
+        let req = self
+            .client
+            .request()
+            .patch()
+            .uri(&uri)
+            .body(bracket)
+            .build();
+
+        self.client.send(req).await?.json().await
+    }
+
     pub fn matches(&self, id: BracketId) -> WebSocketBuilder {
         let uri = format!(
             "{}/v3/tournaments/{}/brackets/{}/matches",
@@ -78,4 +263,138 @@ impl<'a> BracketsClient<'a> {
 
         WebSocketBuilder::new(uri)
     }
+
+    /// Returns the predicted outcome of the bracket with the given `id`.
+    pub async fn prediction(&self, id: BracketId) -> Result<Prediction> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/prediction",
+            self.tournament_id, id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    /// Returns the progress and estimated match times of the bracket with the given `id`.
+    pub async fn progress(&self, id: BracketId) -> Result<Progress> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/progress",
+            self.tournament_id, id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    /// Returns the rounds of the bracket with the given `id`.
+    pub async fn rounds(&self, id: BracketId) -> Result<Rounds> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/rounds",
+            self.tournament_id, id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    /// Returns the clinch and elimination scenarios of the bracket with the given `id` for
+    /// entrants advancing from the first `top` positions.
+    pub async fn scenarios(&self, id: BracketId, top: usize) -> Result<Scenarios> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/scenarios/{}",
+            self.tournament_id, id, top
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    /// Returns the matches of the bracket with the given `id` after replaying the event log up to
+    /// the event with the id `event_id`.
+    pub async fn replay(
+        &self,
+        id: BracketId,
+        event_id: EventId,
+    ) -> Result<Matches<EntrantScore<u64>>> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/replay/{}",
+            self.tournament_id, id, event_id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    /// Rolls the bracket with the given `id` back to the state after the event with the id
+    /// `event_id`. Returns the new matches of the bracket.
+    pub async fn rollback(
+        &self,
+        id: BracketId,
+        event_id: EventId,
+    ) -> Result<Matches<EntrantScore<u64>>> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/replay/{}",
+            self.tournament_id, id, event_id
+        );
+
+        let req = self.client.request().post().uri(&uri).build();
+
+        let resp = self.client.send(req).await?;
+
+        resp.json().await
+    }
+
+    pub fn metadata(&self, id: BracketId) -> MetadataClient<'_> {
+        MetadataClient::new(self.client, self.tournament_id, id)
+    }
+
+    pub fn schedule(&self, id: BracketId) -> ScheduleClient<'_> {
+        ScheduleClient::new(self.client, self.tournament_id, id)
+    }
+
+    pub fn veto(&self, id: BracketId) -> VetoClient<'_> {
+        VetoClient::new(self.client, self.tournament_id, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Seeding;
+    use crate::v3::id::EntrantId;
+
+    #[test]
+    fn test_seeding_apply() {
+        let entrants: Vec<EntrantId> = (0..8).map(EntrantId).collect();
+
+        let mut ordered = entrants.clone();
+        Seeding::Ordered.apply(&mut ordered);
+        assert_eq!(ordered, entrants);
+
+        // The draw does not depend on the input order.
+        let mut a = entrants.clone();
+        let mut b: Vec<EntrantId> = entrants.iter().rev().copied().collect();
+        Seeding::Shuffle { seed: Some(42) }.apply(&mut a);
+        Seeding::Shuffle { seed: Some(42) }.apply(&mut b);
+        assert_eq!(a, b);
+        assert_ne!(a, entrants);
+
+        let mut unseeded = entrants.clone();
+        Seeding::Shuffle { seed: None }.apply(&mut unseeded);
+        assert_eq!(unseeded, entrants);
+    }
 }
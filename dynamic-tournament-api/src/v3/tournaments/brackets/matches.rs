@@ -2,9 +2,13 @@ use std::io::{self, Read, Write};
 use std::mem::MaybeUninit;
 use std::string::FromUtf8Error;
 
+use chrono::{DateTime, TimeZone, Utc};
 use dynamic_tournament_core::{EntrantScore, EntrantSpot, Match, Matches, Node};
 use serde::{Deserialize, Serialize};
 
+use crate::v3::id::{BracketId, TournamentId};
+use crate::{Client, Result as ApiResult};
+
 /// An error which can occur while encoding or decoding a type.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -29,15 +33,24 @@ pub enum Request {
     /// Synchronize the state of the bracket.
     SyncState,
     /// Update the match the given `index` using the data in the
-    /// `nodes`.
+    /// `nodes`. The update is only applied if the match is still at the given `revision`.
     UpdateMatch {
         index: u64,
+        revision: u64,
         nodes: [EntrantScore<u64>; 2],
     },
     /// Resets the match at the given `index`.
     ResetMatch {
         index: u64,
     },
+    /// Plays the next step in the veto of the match at the given `index` using the map at index
+    /// `map` in the pool. `key` is the veto key of the entrant whose turn it is. Authorized
+    /// clients may play the step of the entrant whose turn it is using an empty `key`.
+    Veto {
+        index: u64,
+        map: u64,
+        key: String,
+    },
 }
 
 impl Request {
@@ -57,8 +70,9 @@ impl Encode for Request {
             Self::Reserved => 0,
             Self::Authorize(_) => 1,
             Self::SyncState => 2,
-            Self::UpdateMatch { index: _, nodes: _ } => 3,
+            Self::UpdateMatch { .. } => 3,
             Self::ResetMatch { index: _ } => 4,
+            Self::Veto { .. } => 5,
         };
         let mut bytes_written = n.encode(&mut writer)?;
 
@@ -68,13 +82,23 @@ impl Encode for Request {
                 bytes_written += token.encode(writer)?;
             }
             Self::SyncState => (),
-            Self::UpdateMatch { index, nodes } => {
+            Self::UpdateMatch {
+                index,
+                revision,
+                nodes,
+            } => {
                 bytes_written += index.encode(&mut writer)?;
+                bytes_written += revision.encode(&mut writer)?;
                 bytes_written += nodes.encode(writer)?;
             }
             Self::ResetMatch { index } => {
                 bytes_written += index.encode(writer)?;
             }
+            Self::Veto { index, map, key } => {
+                bytes_written += index.encode(&mut writer)?;
+                bytes_written += map.encode(&mut writer)?;
+                bytes_written += key.encode(writer)?;
+            }
         }
 
         Ok(bytes_written)
@@ -96,15 +120,27 @@ impl Decode for Request {
             2 => Ok(Self::SyncState),
             3 => {
                 let index = u64::decode(&mut reader)?;
+                let revision = u64::decode(&mut reader)?;
                 let nodes = Decode::decode(reader)?;
 
-                Ok(Self::UpdateMatch { index, nodes })
+                Ok(Self::UpdateMatch {
+                    index,
+                    revision,
+                    nodes,
+                })
             }
             4 => {
                 let index = u64::decode(&mut reader)?;
 
                 Ok(Self::ResetMatch { index })
             }
+            5 => {
+                let index = u64::decode(&mut reader)?;
+                let map = u64::decode(&mut reader)?;
+                let key = String::decode(reader)?;
+
+                Ok(Self::Veto { index, map, key })
+            }
             _ => Err(Error::InvalidVariant),
         }
     }
@@ -114,14 +150,26 @@ impl Decode for Request {
 pub enum Response {
     Reserved,
     Error(ErrorResponse),
-    SyncState(Matches<EntrantScore<u64>>),
+    /// The complete state of the bracket and the revisions of all matches.
+    SyncState(Matches<EntrantScore<u64>>, Vec<u64>),
     UpdateMatch {
         index: u64,
+        revision: u64,
         nodes: [EntrantScore<u64>; 2],
     },
     ResetMatch {
         index: u64,
+        revision: u64,
+    },
+    /// The veto of the match at the given `index` changed.
+    Veto {
+        index: u64,
     },
+    /// The [`MatchMetadata`] of a match changed.
+    UpdateMetadata(MatchMetadata),
+    /// The bracket was edited and its matches were recreated. Clients should fetch the bracket
+    /// and synchronize the state again.
+    Rebuild,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -132,6 +180,11 @@ pub enum ErrorResponse {
     /// The server event queue lagged and is out sync. The client may want
     /// to synchronize again.
     Lagged,
+    /// The veto step could not be played.
+    InvalidVeto,
+    /// The match was changed since the revision of the update. Contains the current state of the
+    /// match.
+    Conflict(MatchState),
     ProtoInvalidVariant,
     ProtoInvalidSequence,
     ProtoInvalidString,
@@ -139,7 +192,7 @@ pub enum ErrorResponse {
 }
 
 impl Encode for ErrorResponse {
-    fn encode<W>(&self, writer: W) -> Result<usize, Error>
+    fn encode<W>(&self, mut writer: W) -> Result<usize, Error>
     where
         W: Write,
     {
@@ -148,26 +201,35 @@ impl Encode for ErrorResponse {
             Self::Proto => 1,
             Self::Unauthorized => 2,
             Self::Lagged => 3,
+            Self::InvalidVeto => 4,
+            Self::Conflict(_) => 5,
             Self::ProtoInvalidVariant => 128,
             Self::ProtoInvalidSequence => 129,
             Self::ProtoInvalidString => 130,
             Self::ProtoIntOverflow => 131,
         };
+        let mut bytes_written = b.encode(&mut writer)?;
 
-        b.encode(writer)
+        if let Self::Conflict(state) = self {
+            bytes_written += state.encode(writer)?;
+        }
+
+        Ok(bytes_written)
     }
 }
 
 impl Decode for ErrorResponse {
-    fn decode<R>(reader: R) -> Result<Self, Error>
+    fn decode<R>(mut reader: R) -> Result<Self, Error>
     where
         R: Read,
     {
-        match u8::decode(reader)? {
+        match u8::decode(&mut reader)? {
             0 => Ok(Self::Internal),
             1 => Ok(Self::Proto),
             2 => Ok(Self::Unauthorized),
             3 => Ok(Self::Lagged),
+            4 => Ok(Self::InvalidVeto),
+            5 => Ok(Self::Conflict(MatchState::decode(reader)?)),
             128 => Ok(Self::ProtoInvalidVariant),
             129 => Ok(Self::ProtoInvalidSequence),
             130 => Ok(Self::ProtoInvalidString),
@@ -193,9 +255,12 @@ impl Encode for Response {
         let cmd: u8 = match self {
             Self::Reserved => 0,
             Self::Error(_) => 1,
-            Self::SyncState(_) => 2,
-            Self::UpdateMatch { index: _, nodes: _ } => 3,
-            Self::ResetMatch { index: _ } => 4,
+            Self::SyncState(_, _) => 2,
+            Self::UpdateMatch { .. } => 3,
+            Self::ResetMatch { .. } => 4,
+            Self::Veto { index: _ } => 5,
+            Self::UpdateMetadata(_) => 6,
+            Self::Rebuild => 7,
         };
         let mut bytes_written = cmd.encode(&mut writer)?;
 
@@ -204,17 +269,31 @@ impl Encode for Response {
             Self::Error(err) => {
                 bytes_written += err.encode(writer)?;
             }
-            Self::SyncState(state) => {
+            Self::SyncState(state, revisions) => {
                 let slice: &[_] = state.as_ref();
-                bytes_written += slice.encode(writer)?;
+                bytes_written += slice.encode(&mut writer)?;
+                bytes_written += revisions.as_slice().encode(writer)?;
             }
-            Self::UpdateMatch { index, nodes } => {
+            Self::UpdateMatch {
+                index,
+                revision,
+                nodes,
+            } => {
                 bytes_written += index.encode(&mut writer)?;
+                bytes_written += revision.encode(&mut writer)?;
                 bytes_written += nodes.encode(writer)?;
             }
-            Self::ResetMatch { index } => {
+            Self::ResetMatch { index, revision } => {
+                bytes_written += index.encode(&mut writer)?;
+                bytes_written += revision.encode(writer)?;
+            }
+            Self::Veto { index } => {
                 bytes_written += index.encode(writer)?;
             }
+            Self::UpdateMetadata(metadata) => {
+                bytes_written += metadata.encode(writer)?;
+            }
+            Self::Rebuild => (),
         }
 
         Ok(bytes_written)
@@ -234,26 +313,257 @@ impl Decode for Response {
                 Ok(Self::Error(err))
             }
             2 => {
-                let matches: Vec<Match<Node<EntrantScore<u64>>>> = Decode::decode(reader)?;
+                let matches: Vec<Match<Node<EntrantScore<u64>>>> = Decode::decode(&mut reader)?;
+                let revisions = Decode::decode(reader)?;
 
-                Ok(Self::SyncState(Matches::from(matches)))
+                Ok(Self::SyncState(Matches::from(matches), revisions))
             }
             3 => {
                 let index = u64::decode(&mut reader)?;
+                let revision = u64::decode(&mut reader)?;
                 let nodes = Decode::decode(reader)?;
 
-                Ok(Self::UpdateMatch { index, nodes })
+                Ok(Self::UpdateMatch {
+                    index,
+                    revision,
+                    nodes,
+                })
             }
             4 => {
+                let index = u64::decode(&mut reader)?;
+                let revision = u64::decode(reader)?;
+
+                Ok(Self::ResetMatch { index, revision })
+            }
+            5 => {
                 let index = u64::decode(reader)?;
 
-                Ok(Self::ResetMatch { index })
+                Ok(Self::Veto { index })
             }
+            6 => {
+                let metadata = MatchMetadata::decode(reader)?;
+
+                Ok(Self::UpdateMetadata(metadata))
+            }
+            7 => Ok(Self::Rebuild),
             _ => Err(Error::InvalidVariant),
         }
     }
 }
 
+/// The result of a single match together with its revision.
+///
+/// The revision of a match is increased on every update or reset of the match.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchState {
+    /// The index of the match.
+    #[serde(default)]
+    pub index: u64,
+    #[serde(default)]
+    pub revision: u64,
+    /// The nodes of both entrants. Spots without an entrant have a default node.
+    pub nodes: [EntrantScore<u64>; 2],
+}
+
+impl Encode for MatchState {
+    fn encode<W>(&self, mut writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        let mut bytes_written = self.index.encode(&mut writer)?;
+        bytes_written += self.revision.encode(&mut writer)?;
+        bytes_written += self.nodes.encode(writer)?;
+
+        Ok(bytes_written)
+    }
+}
+
+impl Decode for MatchState {
+    fn decode<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let index = u64::decode(&mut reader)?;
+        let revision = u64::decode(&mut reader)?;
+        let nodes = Decode::decode(reader)?;
+
+        Ok(Self {
+            index,
+            revision,
+            nodes,
+        })
+    }
+}
+
+/// Additional information about a single match that is not part of the bracket state.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchMetadata {
+    /// The index of the match.
+    #[serde(default)]
+    pub index: usize,
+    /// The planned start of the match.
+    #[serde(default)]
+    pub scheduled: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub status: MatchStatus,
+    /// A link to the stream of the match.
+    #[serde(default)]
+    pub stream: Option<String>,
+    #[serde(default)]
+    pub referee: Option<String>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// The status of a match as set by the organizers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchStatus {
+    #[default]
+    Scheduled,
+    Live,
+    Finished,
+    /// The match was not played and one entrant advanced without playing.
+    Walkover,
+}
+
+impl Encode for MatchMetadata {
+    fn encode<W>(&self, mut writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        let mut bytes_written = self.index.encode(&mut writer)?;
+        bytes_written += self.scheduled.encode(&mut writer)?;
+        bytes_written += self.status.encode(&mut writer)?;
+        bytes_written += self.stream.encode(&mut writer)?;
+        bytes_written += self.referee.encode(&mut writer)?;
+        bytes_written += self.notes.encode(writer)?;
+
+        Ok(bytes_written)
+    }
+}
+
+impl Decode for MatchMetadata {
+    fn decode<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let index = usize::decode(&mut reader)?;
+        let scheduled = Decode::decode(&mut reader)?;
+        let status = MatchStatus::decode(&mut reader)?;
+        let stream = Decode::decode(&mut reader)?;
+        let referee = Decode::decode(&mut reader)?;
+        let notes = String::decode(reader)?;
+
+        Ok(Self {
+            index,
+            scheduled,
+            status,
+            stream,
+            referee,
+            notes,
+        })
+    }
+}
+
+impl Encode for MatchStatus {
+    fn encode<W>(&self, writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        let b: u8 = match self {
+            Self::Scheduled => 0,
+            Self::Live => 1,
+            Self::Finished => 2,
+            Self::Walkover => 3,
+        };
+
+        b.encode(writer)
+    }
+}
+
+impl Decode for MatchStatus {
+    fn decode<R>(reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        match u8::decode(reader)? {
+            0 => Ok(Self::Scheduled),
+            1 => Ok(Self::Live),
+            2 => Ok(Self::Finished),
+            3 => Ok(Self::Walkover),
+            _ => Err(Error::InvalidVariant),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MetadataClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+}
+
+impl<'a> MetadataClient<'a> {
+    pub(crate) fn new(
+        client: &'a Client,
+        tournament_id: TournamentId,
+        bracket_id: BracketId,
+    ) -> Self {
+        Self {
+            client,
+            tournament_id,
+            bracket_id,
+        }
+    }
+
+    /// Returns the [`MatchMetadata`] of all matches in the bracket that have metadata.
+    pub async fn list(&self) -> ApiResult<Vec<MatchMetadata>> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/matches/metadata",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Returns the [`MatchMetadata`] of the match with the given `index`.
+    pub async fn get(&self, index: usize) -> ApiResult<MatchMetadata> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Replaces the [`MatchMetadata`] of the match with the given `index`.
+    pub async fn update(&self, index: usize, metadata: &MatchMetadata) -> ApiResult<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().put().uri(&uri).body(metadata).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Removes the [`MatchMetadata`] of the match with the given `index`.
+    pub async fn delete(&self, index: usize) -> ApiResult<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+}
+
 const CONTINUE_BIT: u8 = 1 << 7;
 
 /// A type that can be encoded into a byte buffer.
@@ -453,6 +763,71 @@ impl Encode for str {
     }
 }
 
+impl Encode for String {
+    fn encode<W>(&self, writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        self.as_str().encode(writer)
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    fn encode<W>(&self, mut writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        match self {
+            Some(value) => {
+                let bytes_written = true.encode(&mut writer)?;
+                Ok(bytes_written + value.encode(writer)?)
+            }
+            None => false.encode(writer),
+        }
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode,
+{
+    fn decode<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        match bool::decode(&mut reader)? {
+            true => Ok(Some(T::decode(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+/// Dates are encoded as seconds since the unix epoch.
+impl Encode for DateTime<Utc> {
+    fn encode<W>(&self, writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+    {
+        self.timestamp().encode(writer)
+    }
+}
+
+impl Decode for DateTime<Utc> {
+    fn decode<R>(reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let timestamp = i64::decode(reader)?;
+
+        Utc.timestamp_opt(timestamp, 0)
+            .single()
+            .ok_or(Error::IntOverflow)
+    }
+}
+
 impl<T> Decode for Vec<T>
 where
     T: Decode,
@@ -689,7 +1064,12 @@ mod tests {
     use std::io::{Cursor, Read};
     use std::sync::atomic::{AtomicUsize, Ordering};
 
-    use super::{Decode, Encode, EntrantScore, EntrantSpot, Error, Match, Node};
+    use chrono::{TimeZone, Utc};
+
+    use super::{
+        Decode, Encode, EntrantScore, EntrantSpot, Error, ErrorResponse, Match, MatchMetadata,
+        MatchState, MatchStatus, Node, Request, Response,
+    };
 
     #[test]
     fn test_encode_bool() {
@@ -848,4 +1228,87 @@ mod tests {
             .unwrap();
         assert_eq!(buf, [2, 0, 0]);
     }
+
+    #[test]
+    fn test_request_veto() {
+        let req = Request::Veto {
+            index: 3,
+            map: 1,
+            key: String::from("ab"),
+        };
+
+        let buf = req.to_bytes();
+        assert_eq!(buf, [5, 3, 1, 2, b'a', b'b']);
+        assert_eq!(Request::decode(Cursor::new(buf)).unwrap(), req);
+    }
+
+    #[test]
+    fn test_response_update_metadata() {
+        let resp = Response::UpdateMetadata(MatchMetadata {
+            index: 2,
+            scheduled: Some(Utc.timestamp_opt(1, 0).unwrap()),
+            status: MatchStatus::Live,
+            stream: None,
+            referee: Some(String::from("a")),
+            notes: String::new(),
+        });
+
+        let buf = resp.to_bytes();
+        assert_eq!(buf, [6, 2, 1, 2, 1, 0, 1, 1, b'a', 0]);
+        assert_eq!(Response::decode(Cursor::new(buf)).unwrap(), resp);
+    }
+
+    #[test]
+    fn test_response_rebuild() {
+        let buf = Response::Rebuild.to_bytes();
+        assert_eq!(buf, [7]);
+        assert_eq!(
+            Response::decode(Cursor::new(buf)).unwrap(),
+            Response::Rebuild
+        );
+    }
+
+    #[test]
+    fn test_request_update_match() {
+        let req = Request::UpdateMatch {
+            index: 2,
+            revision: 5,
+            nodes: [
+                EntrantScore {
+                    score: 1,
+                    winner: false,
+                },
+                EntrantScore {
+                    score: 2,
+                    winner: true,
+                },
+            ],
+        };
+
+        let buf = req.to_bytes();
+        assert_eq!(buf, [3, 2, 5, 2, 1, 0, 2, 1]);
+        assert_eq!(Request::decode(Cursor::new(buf)).unwrap(), req);
+    }
+
+    #[test]
+    fn test_response_error_conflict() {
+        let resp = Response::Error(ErrorResponse::Conflict(MatchState {
+            index: 1,
+            revision: 3,
+            nodes: [
+                EntrantScore {
+                    score: 0,
+                    winner: false,
+                },
+                EntrantScore {
+                    score: 4,
+                    winner: true,
+                },
+            ],
+        }));
+
+        let buf = resp.to_bytes();
+        assert_eq!(buf, [1, 5, 1, 3, 2, 0, 0, 4, 1]);
+        assert_eq!(Response::decode(Cursor::new(buf)).unwrap(), resp);
+    }
 }
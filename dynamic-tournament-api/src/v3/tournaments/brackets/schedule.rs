@@ -0,0 +1,144 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::v3::id::{BracketId, TournamentId};
+use crate::{Client, Result};
+
+/// The configuration used to plan the [`Schedule`] of a bracket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// No match will be planned before this time.
+    pub start: DateTime<Utc>,
+    /// The names of all stations (e.g. setups or servers) matches can be played on. Matches
+    /// refer to a station using the index in this list.
+    pub stations: Vec<String>,
+    /// The expected duration of a match in seconds.
+    pub match_duration: u64,
+    /// The minimum rest time of an entrant between two matches in seconds.
+    #[serde(default)]
+    pub rest: u64,
+}
+
+/// The schedule of a bracket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Schedule {
+    pub config: ScheduleConfig,
+    /// The planned slot for every match in the bracket. A `None` value indicates that the match
+    /// is not played (e.g. a bye) or was concluded before it was scheduled.
+    #[serde(default)]
+    pub matches: Vec<Option<ScheduledMatch>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledMatch {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// The index of the station in [`ScheduleConfig::stations`].
+    pub station: usize,
+    #[serde(default)]
+    pub status: ScheduleStatus,
+}
+
+/// The status of a [`ScheduledMatch`]. Only [`Planned`] matches are moved when the schedule is
+/// re-planned.
+///
+/// [`Planned`]: Self::Planned
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleStatus {
+    /// The slot was assigned by the scheduler.
+    #[default]
+    Planned,
+    /// The slot was set manually.
+    Override,
+    /// The match was played. `end` contains the time the result came in.
+    Finished,
+}
+
+/// A manual override for the slot of a single match.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduleOverride {
+    pub start: DateTime<Utc>,
+    /// The index of the station in [`ScheduleConfig::stations`].
+    pub station: usize,
+    /// The end of the slot. Defaults to `start` plus [`ScheduleConfig::match_duration`].
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ScheduleClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+}
+
+impl<'a> ScheduleClient<'a> {
+    pub(crate) fn new(
+        client: &'a Client,
+        tournament_id: TournamentId,
+        bracket_id: BracketId,
+    ) -> Self {
+        Self {
+            client,
+            tournament_id,
+            bracket_id,
+        }
+    }
+
+    pub async fn get(&self) -> Result<Schedule> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/schedule",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Creates or updates the [`ScheduleConfig`] and returns the re-planned [`Schedule`].
+    pub async fn update(&self, config: &ScheduleConfig) -> Result<Schedule> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/schedule",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().put().uri(&uri).body(config).build();
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn delete(&self) -> Result<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/schedule",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Overrides the slot of the match with the given `index` and returns the re-planned
+    /// [`Schedule`].
+    pub async fn set_override(&self, index: usize, slot: &ScheduleOverride) -> Result<Schedule> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/schedule/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().put().uri(&uri).body(slot).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Removes the override of the match with the given `index` and returns the re-planned
+    /// [`Schedule`].
+    pub async fn delete_override(&self, index: usize) -> Result<Schedule> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/schedule/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+}
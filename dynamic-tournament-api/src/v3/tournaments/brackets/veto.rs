@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v3::id::{BracketId, TournamentId};
+use crate::{Client, Result};
+
+pub use dynamic_tournament_core::veto::{Veto, VetoConfig, VetoEntry, VetoError, VetoStep};
+
+/// The [`Veto`] of a single match.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatchVeto {
+    /// The index of the match.
+    pub index: usize,
+    pub veto: Veto,
+    /// The keys the entrants use to play their steps over the websocket. The key at position `i`
+    /// belongs to the entrant at position `i` in the match. Only returned when the veto is
+    /// started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<[String; 2]>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct VetoClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+}
+
+impl<'a> VetoClient<'a> {
+    pub(crate) fn new(
+        client: &'a Client,
+        tournament_id: TournamentId,
+        bracket_id: BracketId,
+    ) -> Self {
+        Self {
+            client,
+            tournament_id,
+            bracket_id,
+        }
+    }
+
+    /// Returns the [`VetoConfig`] of the bracket.
+    pub async fn get_config(&self) -> Result<VetoConfig> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Creates or updates the [`VetoConfig`] of the bracket. Already started vetoes are not
+    /// changed.
+    pub async fn update_config(&self, config: &VetoConfig) -> Result<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().put().uri(&uri).body(config).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    pub async fn delete_config(&self) -> Result<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Returns the [`MatchVeto`]s of all matches in the bracket.
+    pub async fn list(&self) -> Result<Vec<MatchVeto>> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto/matches",
+            self.tournament_id, self.bracket_id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Returns the [`MatchVeto`] of the match with the given `index`.
+    pub async fn get(&self, index: usize) -> Result<MatchVeto> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Starts the veto of the match with the given `index` using the [`VetoConfig`] of the
+    /// bracket. The returned [`MatchVeto`] contains the keys for both entrants.
+    pub async fn start(&self, index: usize) -> Result<MatchVeto> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().post().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Removes the veto of the match with the given `index`.
+    pub async fn delete(&self, index: usize) -> Result<()> {
+        let uri = format!(
+            "/v3/tournaments/{}/brackets/{}/veto/matches/{}",
+            self.tournament_id, self.bracket_id, index
+        );
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+}
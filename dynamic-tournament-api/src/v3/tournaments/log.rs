@@ -2,7 +2,16 @@ use chrono::{DateTime, Utc};
 use dynamic_tournament_core::EntrantScore;
 use serde::{Deserialize, Serialize};
 
-use crate::v3::id::{BracketId, EventId, TournamentId};
+use super::brackets::matches::MatchMetadata;
+use super::brackets::schedule::Schedule;
+use super::brackets::veto::{MatchVeto, VetoConfig};
+use super::brackets::Bracket;
+use super::entrants::Entrant;
+use super::ratings::RatingSystem;
+use super::roles::Role;
+use super::webhooks::Webhook;
+use super::Tournament;
+use crate::v3::id::{BracketId, EntrantId, EventId, RoleId, TournamentId, UserId, WebhookId};
 use crate::{Client, Result};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -27,6 +36,128 @@ pub enum LogEventBody {
         bracket_id: BracketId,
         index: u64,
     },
+    /// The entrants of a bracket were shuffled using the `seed`.
+    ShuffleBracket {
+        bracket_id: BracketId,
+        seed: u64,
+    },
+    /// The veto of a match finished with the given `maps` picked.
+    Veto {
+        bracket_id: BracketId,
+        index: u64,
+        maps: Vec<String>,
+    },
+    /// The bracket was rolled back to the state after the event with the given `event_id`. An
+    /// `event_id` of `0` rolls back to the initial state of the bracket.
+    Rollback {
+        bracket_id: BracketId,
+        event_id: EventId,
+    },
+    /// A new tournament was created. This event is part of the system log.
+    CreateTournament {
+        id: TournamentId,
+        tournament: Tournament,
+    },
+    UpdateTournament {
+        before: Tournament,
+        after: Tournament,
+    },
+    /// The tournament was deleted. This event is part of the system log.
+    DeleteTournament {
+        id: TournamentId,
+        tournament: Tournament,
+    },
+    CreateEntrant {
+        id: EntrantId,
+        entrant: Entrant,
+    },
+    UpdateEntrant {
+        id: EntrantId,
+        before: Entrant,
+        after: Entrant,
+    },
+    DeleteEntrant {
+        id: EntrantId,
+        entrant: Entrant,
+    },
+    CreateRole {
+        id: RoleId,
+        role: Role,
+    },
+    DeleteRole {
+        id: RoleId,
+        role: Role,
+    },
+    CreateBracket {
+        id: BracketId,
+        bracket: Bracket,
+    },
+    UpdateBracket {
+        id: BracketId,
+        before: Bracket,
+        after: Bracket,
+    },
+    DeleteBracket {
+        id: BracketId,
+        bracket: Bracket,
+    },
+    /// The schedule of a bracket was created, updated or deleted.
+    UpdateSchedule {
+        bracket_id: BracketId,
+        before: Option<Schedule>,
+        after: Option<Schedule>,
+    },
+    /// The veto config of a bracket was created, updated or deleted.
+    UpdateVetoConfig {
+        bracket_id: BracketId,
+        before: Option<VetoConfig>,
+        after: Option<VetoConfig>,
+    },
+    /// The veto of a match was started or deleted.
+    UpdateMatchVeto {
+        bracket_id: BracketId,
+        index: u64,
+        before: Option<MatchVeto>,
+        after: Option<MatchVeto>,
+    },
+    /// The metadata of a match was created, updated or deleted.
+    UpdateMatchMetadata {
+        bracket_id: BracketId,
+        index: u64,
+        before: Option<MatchMetadata>,
+        after: Option<MatchMetadata>,
+    },
+    /// The rating system of the tournament was set or removed.
+    UpdateRatingSystem {
+        before: Option<RatingSystem>,
+        after: Option<RatingSystem>,
+    },
+    /// A webhook was registered. The secret of the webhook is never written to the log.
+    CreateWebhook {
+        id: WebhookId,
+        webhook: Webhook,
+    },
+    /// A webhook was deleted. The secret of the webhook is never written to the log.
+    DeleteWebhook {
+        id: WebhookId,
+        webhook: Webhook,
+    },
+    /// A new user was created. This event is part of the system log.
+    CreateUser {
+        id: UserId,
+        username: String,
+    },
+    /// The username or password of a user was changed. This event is part of the system log.
+    UpdateUser {
+        id: UserId,
+        before: String,
+        after: String,
+    },
+    /// A user was deleted. This event is part of the system log.
+    DeleteUser {
+        id: UserId,
+        username: String,
+    },
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -51,3 +182,22 @@ impl<'a> EventLogClient<'a> {
         resp.json().await
     }
 }
+
+/// A client for the system log. The system log contains all events that don't belong to a
+/// single tournament.
+#[derive(Copy, Clone, Debug)]
+pub struct SystemLogClient<'a> {
+    client: &'a Client,
+}
+
+impl<'a> SystemLogClient<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn list(&self) -> Result<Vec<LogEvent>> {
+        let req = self.client.request().get().uri("/v3/log").build();
+        let resp = self.client.send(req).await?;
+        resp.json().await
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v3::id::{BracketId, EntrantId, TournamentId};
+use crate::v3::tournaments::brackets::matches::MatchMetadata;
+use crate::{Client, Result};
+
+pub use dynamic_tournament_core::upcoming::BlockedMatch;
+
+/// The upcoming matches of a single bracket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpcomingMatches {
+    pub bracket_id: BracketId,
+    /// All matches with both entrants known that are not concluded, ordered by their index.
+    pub playable: Vec<PlayableMatch>,
+    /// All matches that are still waiting on the result of other matches.
+    pub blocked: Vec<BlockedMatch>,
+    /// The next match of every entrant that has an open match.
+    pub entrants: Vec<EntrantNextMatch>,
+}
+
+/// A match that can be played.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayableMatch {
+    /// The index of the match.
+    pub index: usize,
+    /// The match code of the match, e.g. `UB-R2-M3`.
+    #[serde(default)]
+    pub code: String,
+    pub entrants: [EntrantId; 2],
+    /// The [`MatchMetadata`] of the match, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MatchMetadata>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct EntrantNextMatch {
+    pub entrant: EntrantId,
+    /// The index of the match. The match may still be blocked.
+    pub index: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatchesClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+}
+
+impl<'a> MatchesClient<'a> {
+    pub(crate) fn new(client: &'a Client, tournament_id: TournamentId) -> Self {
+        Self {
+            client,
+            tournament_id,
+        }
+    }
+
+    /// Returns the [`UpcomingMatches`] of all brackets in the tournament.
+    pub async fn next(&self) -> Result<Vec<UpcomingMatches>> {
+        let uri = format!("/v3/tournaments/{}/matches/next", self.tournament_id);
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::v3::id::{BracketId, EntrantId, TournamentId};
+use crate::{Client, Result};
+
+/// The system used to update the ratings of players from concluded matches.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "system", rename_all = "snake_case")]
+pub enum RatingSystem {
+    Elo {
+        /// The maximum rating change of a single match.
+        #[serde(default = "default_elo_k")]
+        k: f64,
+    },
+    Glicko2 {
+        /// The system constant which constrains the change in volatility over time.
+        #[serde(default = "default_glicko2_tau")]
+        tau: f64,
+    },
+}
+
+fn default_elo_k() -> f64 {
+    32.0
+}
+
+fn default_glicko2_tau() -> f64 {
+    0.5
+}
+
+/// A single rating change of a player caused by a concluded match.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RatingChange {
+    pub date: DateTime<Utc>,
+    pub entrant: EntrantId,
+    /// The index of the player in the team. Always `0` for player entrants.
+    pub player: usize,
+    /// The bracket of the match that caused the change.
+    pub bracket: BracketId,
+    /// The index of the match that caused the change.
+    pub index: usize,
+    /// The new rating.
+    pub rating: f64,
+    /// The difference between the new and the previous rating.
+    #[serde(default)]
+    pub delta: f64,
+    /// The new rating deviation. Only changes when using [`RatingSystem::Glicko2`].
+    pub deviation: f64,
+    /// The new rating volatility. Only changes when using [`RatingSystem::Glicko2`].
+    pub volatility: f64,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct RatingsClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+}
+
+impl<'a> RatingsClient<'a> {
+    pub(crate) fn new(client: &'a Client, tournament_id: TournamentId) -> Self {
+        Self {
+            client,
+            tournament_id,
+        }
+    }
+
+    /// Returns the [`RatingSystem`] of the tournament.
+    pub async fn get(&self) -> Result<RatingSystem> {
+        let uri = format!("/v3/tournaments/{}/ratings", self.tournament_id);
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    /// Sets the [`RatingSystem`] of the tournament. Only matches concluded after this call will
+    /// update the ratings.
+    pub async fn update(&self, system: &RatingSystem) -> Result<()> {
+        let uri = format!("/v3/tournaments/{}/ratings", self.tournament_id);
+
+        let req = self.client.request().put().uri(&uri).body(system).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Disables rating updates for the tournament. The existing history is kept.
+    pub async fn delete(&self) -> Result<()> {
+        let uri = format!("/v3/tournaments/{}/ratings", self.tournament_id);
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Returns all [`RatingChange`]s of the tournament, oldest first.
+    pub async fn history(&self) -> Result<Vec<RatingChange>> {
+        let uri = format!("/v3/tournaments/{}/ratings/history", self.tournament_id);
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+}
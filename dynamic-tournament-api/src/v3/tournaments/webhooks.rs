@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::log::LogEventBody;
+use crate::v3::id::{DeliveryId, TournamentId, WebhookId};
+use crate::{Client, Result};
+
+/// A webhook that receives events of a tournament.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Webhook {
+    #[cfg_attr(feature = "server", serde(skip_deserializing))]
+    pub id: WebhookId,
+    /// The `http` or `https` url the events are posted to.
+    pub url: String,
+    /// The secret used to sign all payloads sent to the webhook. The secret is never returned by
+    /// the server and is empty in all received webhooks.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub secret: String,
+    /// The events that are sent to the webhook.
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    UpdateMatch,
+    ResetMatch,
+    CreateBracket,
+    CreateEntrant,
+    UpdateEntrant,
+    DeleteEntrant,
+}
+
+impl WebhookEvent {
+    /// Returns the `WebhookEvent` caused by the [`LogEventBody`]. Returns `None` if the event is
+    /// not sent to webhooks.
+    pub fn from_body(body: &LogEventBody) -> Option<Self> {
+        match body {
+            LogEventBody::UpdateMatch { .. } => Some(Self::UpdateMatch),
+            LogEventBody::ResetMatch { .. } => Some(Self::ResetMatch),
+            LogEventBody::CreateBracket { .. } => Some(Self::CreateBracket),
+            LogEventBody::CreateEntrant { .. } => Some(Self::CreateEntrant),
+            LogEventBody::UpdateEntrant { .. } => Some(Self::UpdateEntrant),
+            LogEventBody::DeleteEntrant { .. } => Some(Self::DeleteEntrant),
+            _ => None,
+        }
+    }
+}
+
+/// The payload posted to a webhook.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub tournament_id: TournamentId,
+    pub date: DateTime<Utc>,
+    /// The user that invoked the event.
+    pub author: u64,
+    #[serde(flatten)]
+    pub body: LogEventBody,
+}
+
+/// A single attempt to deliver an event to a webhook.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Delivery {
+    pub id: DeliveryId,
+    pub webhook_id: WebhookId,
+    pub date: DateTime<Utc>,
+    pub event: WebhookEvent,
+    /// The number of the attempt, starting at `1`.
+    pub attempt: u32,
+    /// The status code returned by the webhook. `None` if no response was received.
+    pub status: Option<u16>,
+    /// The reason why no response was received.
+    pub error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WebhooksClient<'a> {
+    client: &'a Client,
+    tournament_id: TournamentId,
+}
+
+impl<'a> WebhooksClient<'a> {
+    pub(crate) fn new(client: &'a Client, tournament_id: TournamentId) -> Self {
+        Self {
+            client,
+            tournament_id,
+        }
+    }
+
+    pub async fn list(&self) -> Result<Vec<Webhook>> {
+        let uri = format!("/v3/tournaments/{}/webhooks", self.tournament_id);
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn get(&self, id: WebhookId) -> Result<Webhook> {
+        let uri = format!("/v3/tournaments/{}/webhooks/{}", self.tournament_id, id);
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn create(&self, webhook: &Webhook) -> Result<Webhook> {
+        let uri = format!("/v3/tournaments/{}/webhooks", self.tournament_id);
+
+        let req = self.client.request().post().uri(&uri).body(webhook).build();
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn delete(&self, id: WebhookId) -> Result<()> {
+        let uri = format!("/v3/tournaments/{}/webhooks/{}", self.tournament_id, id);
+
+        let req = self.client.request().delete().uri(&uri).build();
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Returns all [`Delivery`] attempts of the webhook, oldest first.
+    pub async fn deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>> {
+        let uri = format!(
+            "/v3/tournaments/{}/webhooks/{}/deliveries",
+            self.tournament_id, id
+        );
+
+        let req = self.client.request().get().uri(&uri).build();
+        self.client.send(req).await?.json().await
+    }
+}
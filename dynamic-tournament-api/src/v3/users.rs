@@ -1,11 +1,109 @@
 use serde::{Deserialize, Serialize};
 
 use super::id::UserId;
+use crate::{Client, Result};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct User {
     #[serde(default)]
     pub id: UserId,
     pub username: String,
+    /// The password of the user. This field is never returned by the server.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub password: String,
 }
+
+/// A [`User`] with all fields being optional. Only fields that are `Some` are updated.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartialUser {
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// The request body to change the password of the authenticated user.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChangePassword {
+    pub password: String,
+    pub new_password: String,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct UsersClient<'a> {
+    client: &'a Client,
+}
+
+impl<'a> UsersClient<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn list(&self) -> Result<Vec<User>> {
+        let req = self.client.request().get().uri("/v3/users").build();
+
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn get(&self, id: UserId) -> Result<User> {
+        let uri = format!("/v3/users/{}", id);
+
+        let req = self.client.request().get().uri(&uri).build();
+
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn create(&self, user: &User) -> Result<()> {
+        let req = self
+            .client
+            .request()
+            .post()
+            .uri("/v3/users")
+            .body(user)
+            .build();
+
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Updates the user with the given `id`. Returns the updated [`User`].
+    ///
+    /// Note that all tokens of the user become invalid.
+    pub async fn patch(&self, id: UserId, user: &PartialUser) -> Result<User> {
+        let uri = format!("/v3/users/{}", id);
+
+        let req = self.client.request().patch().uri(&uri).body(user).build();
+
+        self.client.send(req).await?.json().await
+    }
+
+    pub async fn delete(&self, id: UserId) -> Result<()> {
+        let uri = format!("/v3/users/{}", id);
+
+        let req = self.client.request().delete().uri(&uri).build();
+
+        self.client.send(req).await?;
+        Ok(())
+    }
+
+    /// Changes the password of the authenticated user from `password` to `new_password`.
+    ///
+    /// Note that all tokens of the user, including the current one, become invalid. A new login
+    /// is required afterwards.
+    pub async fn change_password(&self, password: &str, new_password: &str) -> Result<()> {
+        let body = ChangePassword {
+            password: password.to_owned(),
+            new_password: new_password.to_owned(),
+        };
+
+        let req = self
+            .client
+            .request()
+            .post()
+            .uri("/v3/users/me/password")
+            .body(&body)
+            .build();
+
+        self.client.send(req).await?;
+        Ok(())
+    }
+}
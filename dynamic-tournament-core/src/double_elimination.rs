@@ -1,4 +1,7 @@
+use crate::options::{TournamentOptionValues, TournamentOptions};
 use crate::render::{Column, Element, Position, RenderState, Row};
+use crate::rounds::{Round, Rounds};
+use crate::sides::{self, SideRule};
 use crate::{
     EntrantData, EntrantSpot, Entrants, Error, Match, MatchResult, Matches, NextMatches, Node,
     Result, System,
@@ -15,6 +18,7 @@ where
     entrants: Entrants<T>,
     matches: Matches<D>,
     lower_bracket_index: usize,
+    options: DoubleEliminationOptions,
 }
 
 impl<T, D> DoubleElimination<T, D>
@@ -26,6 +30,19 @@ where
     where
         I: Iterator<Item = T>,
     {
+        Self::new_with_options(entrants, Self::options())
+    }
+
+    /// Creates a new `DoubleElimination` tournament with the given `entrants` and using the
+    /// given `options`.
+    pub fn new_with_options<I, O>(entrants: I, options: O) -> Self
+    where
+        I: Iterator<Item = T>,
+        O: Into<TournamentOptionValues>,
+    {
+        let options = DoubleEliminationOptions::new(options.into());
+        log::debug!("Using options: {:?}", options);
+
         let entrants: Entrants<T> = entrants.collect();
 
         log::debug!(
@@ -38,6 +55,7 @@ where
                 entrants: Entrants::new(),
                 matches: Matches::new(),
                 lower_bracket_index: 0,
+                options,
             };
         }
 
@@ -130,16 +148,31 @@ where
             entrants,
             matches,
             lower_bracket_index,
+            options,
         }
     }
 
+    /// Returns the [`TournamentOptions`] accepted by this system.
+    pub fn options() -> TournamentOptions {
+        let builder = TournamentOptions::builder().option(
+            "crossover",
+            "Cross over the losers dropping into the lower bracket to avoid early rematches",
+            false,
+        );
+
+        sides::elimination_options(builder).build()
+    }
+
     /// Resumes the bracket from existing matches.
     ///
     /// # Errors
     ///
     /// Returns an [`enum@Error`] if `matches` has an invalid number of matches for `entrants` or an
     /// [`Node`] in `matches` pointed to a value that is out-of-bounds.
-    pub fn resume(entrants: Entrants<T>, matches: Matches<D>) -> Result<Self> {
+    pub fn resume<O>(entrants: Entrants<T>, matches: Matches<D>, options: O) -> Result<Self>
+    where
+        O: Into<TournamentOptionValues>,
+    {
         let expected = Self::calculate_matches(entrants.len());
         let found = matches.len();
 
@@ -161,7 +194,7 @@ where
         }
 
         // SAFETY: `matches` has a valid length for `entrants` and all indexes are within bounds.
-        unsafe { Ok(Self::resume_unchecked(entrants, matches)) }
+        unsafe { Ok(Self::resume_unchecked(entrants, matches, options)) }
     }
 
     /// Resumes the bracket from existing matches without validating the length of `matches`.
@@ -172,7 +205,16 @@ where
     /// `entrants` will create an [`DoubleElimination`] object with false assumptions. Usage
     /// of that invalid object can cause all sorts behavoir including infinite loops, wrong
     /// returned data and potentially undefined behavoir.
-    pub unsafe fn resume_unchecked(entrants: Entrants<T>, matches: Matches<D>) -> Self {
+    pub unsafe fn resume_unchecked<O>(
+        entrants: Entrants<T>,
+        matches: Matches<D>,
+        options: O,
+    ) -> Self
+    where
+        O: Into<TournamentOptionValues>,
+    {
+        let options = DoubleEliminationOptions::new(options.into());
+
         log::debug!(
             "Resuming DoubleElimination bracket with {} entrants and {} matches",
             entrants.len(),
@@ -185,6 +227,7 @@ where
             entrants,
             matches,
             lower_bracket_index,
+            options,
         }
     }
 
@@ -483,9 +526,13 @@ where
 
                     // Find the (upper) round.
                     let mut num_matches = initial_matches;
+                    let mut round_matches = 0;
+                    let mut round = 0;
                     let mut final_index = 0;
                     while final_index <= index {
                         final_index += num_matches;
+                        round_matches = num_matches;
+                        round += 1;
 
                         num_matches /= 2;
                     }
@@ -495,7 +542,14 @@ where
 
                     // Apply the offset from the final index.
                     let offset = final_index - index;
-                    let loser_index = final_index * 2 - offset;
+                    let mut loser_index = final_index * 2 - offset;
+
+                    if self.options.crossover {
+                        let start = loser_index + offset + 1 - round_matches;
+                        let position = round_matches - offset - 1;
+
+                        loser_index = start + crossover(round, round_matches, position);
+                    }
 
                     dbg!(final_index);
                     dbg!(loser_index);
@@ -521,6 +575,61 @@ where
         }
     }
 
+    fn side_rule(&self) -> SideRule {
+        self.options.sides
+    }
+
+    fn rounds(&self) -> Rounds {
+        // Brackets with two entrants only consist of the grand final.
+        if self.matches.len() <= 1 {
+            return Rounds::new(vec![Round::new("Grand Final", "GF", 0..self.matches.len())]);
+        }
+
+        let mut rounds = Vec::new();
+
+        let mut start = 0;
+        let mut num_matches = self.lower_bracket_index.div_ceil(2);
+        while start < self.lower_bracket_index {
+            let round = rounds.len() + 1;
+            let end = (start + num_matches).min(self.lower_bracket_index);
+
+            rounds.push(Round::new(
+                format!("Upper Bracket Round {}", round),
+                format!("UB-R{}", round),
+                start..end,
+            ));
+
+            start = end;
+            num_matches = (num_matches / 2).max(1);
+        }
+
+        // Every round of the lower bracket is followed by a round with the same number of
+        // matches that takes the losers from the upper bracket.
+        let final_index = self.final_bracket_index();
+        let mut round = 1;
+        let mut num_matches = (self.lower_bracket_index + 1) / 4;
+        while start < final_index && num_matches > 0 {
+            for _ in 0..2 {
+                let end = (start + num_matches).min(final_index);
+
+                rounds.push(Round::new(
+                    format!("Lower Bracket Round {}", round),
+                    format!("LB-R{}", round),
+                    start..end,
+                ));
+
+                start = end;
+                round += 1;
+            }
+
+            num_matches /= 2;
+        }
+
+        rounds.push(Round::new("Grand Final", "GF", [final_index]));
+
+        Rounds::new(rounds)
+    }
+
     fn start_render(&self) -> RenderState<'_, Self> {
         let initial_matches = self.entrants.len().next_power_of_two() / 2;
 
@@ -538,6 +647,7 @@ where
                     matches.push(Element::new(crate::render::Match {
                         index: i,
                         predecessors: vec![],
+                        sides: None,
                         _marker: PhantomData,
                         label: None,
                         position: None,
@@ -571,6 +681,7 @@ where
                     matches.push(Element::new(crate::render::Match {
                         index: i,
                         predecessors: vec![],
+                        sides: None,
                         _marker: PhantomData,
                         position: None,
                         label: None,
@@ -619,6 +730,7 @@ where
                 position: None,
                 index: self.matches.len() - 1,
                 predecessors: vec![],
+                sides: None,
                 _marker: PhantomData,
             })]
             .into_iter(),
@@ -630,6 +742,39 @@ where
     }
 }
 
+/// Returns the position in the lower bracket round at which the loser of the match at `position`
+/// in the upper bracket `round` (starting at 1) drops in. The round has `num_matches` matches.
+///
+/// Losers of the first round are never crossed over as they only meet each other. Later rounds
+/// alternate between reversing the order and swapping both halves.
+fn crossover(round: usize, num_matches: usize, position: usize) -> usize {
+    match round {
+        0 | 1 => position,
+        round if round % 2 == 0 => num_matches - position - 1,
+        _ => (position + num_matches / 2) % num_matches,
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct DoubleEliminationOptions {
+    crossover: bool,
+    sides: SideRule,
+}
+
+impl DoubleEliminationOptions {
+    fn new(mut options: TournamentOptionValues) -> Self {
+        let mut this = Self::default();
+
+        if let Some(val) = options.take("crossover") {
+            this.crossover = val.unwrap_bool_or(false);
+        }
+
+        this.sides = sides::take_elimination_rule(&mut options);
+
+        this
+    }
+}
+
 impl<T, D> Borrow<Entrants<T>> for DoubleElimination<T, D>
 where
     D: EntrantData,
@@ -651,8 +796,9 @@ where
 #[cfg(test)]
 mod tests {
     use crate::{
-        entrants,
+        entrants, option_values,
         tests::{TColumn, TElement, TMatch, TRow, TestRenderer},
+        EntrantScore,
     };
 
     use super::*;
@@ -784,7 +930,8 @@ mod tests {
             Match::new([EntrantSpot::TBD, EntrantSpot::TBD]),
         ]);
 
-        DoubleElimination::<i32, u32>::resume(entrants, matches).unwrap();
+        DoubleElimination::<i32, u32>::resume(entrants, matches, TournamentOptionValues::default())
+            .unwrap();
 
         let entrants = Entrants::from(vec![0, 1, 2, 3, 4]);
         let matches = Matches::from(vec![
@@ -803,7 +950,12 @@ mod tests {
         ]);
 
         assert_eq!(
-            DoubleElimination::<i32, u32>::resume(entrants, matches).unwrap_err(),
+            DoubleElimination::<i32, u32>::resume(
+                entrants,
+                matches,
+                TournamentOptionValues::default()
+            )
+            .unwrap_err(),
             Error::InvalidNumberOfMatches {
                 expected: 14,
                 found: 6
@@ -827,7 +979,12 @@ mod tests {
         ]);
 
         assert_eq!(
-            DoubleElimination::<i32, u32>::resume(entrants, matches).unwrap_err(),
+            DoubleElimination::<i32, u32>::resume(
+                entrants,
+                matches,
+                TournamentOptionValues::default()
+            )
+            .unwrap_err(),
             Error::InvalidEntrant {
                 index: 4,
                 length: 4
@@ -2523,4 +2680,81 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn test_double_elimination_crossover() {
+        assert_eq!(crossover(1, 4, 1), 1);
+        assert_eq!(crossover(2, 4, 0), 3);
+        assert_eq!(crossover(2, 4, 3), 0);
+        assert_eq!(crossover(3, 4, 0), 2);
+        assert_eq!(crossover(3, 4, 3), 1);
+        assert_eq!(crossover(3, 1, 0), 0);
+
+        let options = option_values!("crossover" => true);
+        let tournament = DoubleElimination::<i32, u32>::new_with_options(0..8, options);
+
+        // The losers of the second upper round drop in reversed order.
+        assert_eq!(tournament.next_matches(4).loser_index(), Some(10));
+        assert_eq!(tournament.next_matches(5).loser_index(), Some(9));
+        assert_eq!(tournament.next_matches(0).loser_index(), Some(7));
+        assert_eq!(tournament.next_matches(6).loser_index(), Some(12));
+
+        let tournament = DoubleElimination::<i32, u32>::new(0..8);
+        assert_eq!(tournament.next_matches(4).loser_index(), Some(9));
+        assert_eq!(tournament.next_matches(5).loser_index(), Some(10));
+    }
+
+    #[test]
+    fn test_double_elimination_crossover_rematches() {
+        fn play(
+            tournament: &mut DoubleElimination<i32, EntrantScore<u32>>,
+            index: usize,
+            winner: usize,
+            played: &mut Vec<[usize; 2]>,
+        ) {
+            tournament.update_match(index, |m, res| {
+                played.push(m.entrants.map(|spot| spot.unwrap_ref().index));
+
+                if let EntrantSpot::Entrant(node) = &mut m.entrants[winner] {
+                    node.data.winner = true;
+                }
+
+                res.winner_default(&m.entrants[winner]);
+                res.loser_default(&m.entrants[1 - winner]);
+            });
+        }
+
+        let mut rematches = [0, 0];
+        for (crossover, rematches) in [false, true].into_iter().zip(&mut rematches) {
+            // Play all outcomes up to the second round of the lower bracket.
+            for outcome in 0..256 {
+                let options = option_values!("crossover" => crossover);
+                let mut tournament = DoubleElimination::new_with_options(0..8, options);
+
+                let mut played = Vec::new();
+                for (bit, index) in [0, 1, 2, 3, 4, 5, 7, 8].into_iter().enumerate() {
+                    let winner = (outcome >> bit) & 1;
+                    play(&mut tournament, index, winner, &mut played);
+                }
+
+                for index in [9, 10] {
+                    let mut entrants = tournament.matches()[index]
+                        .entrants
+                        .map(|spot| spot.unwrap_ref().index);
+
+                    if played.contains(&entrants) {
+                        *rematches += 1;
+                    }
+
+                    entrants.reverse();
+                    if played.contains(&entrants) {
+                        *rematches += 1;
+                    }
+                }
+            }
+        }
+
+        assert_ne!(rematches[0], 0);
+        assert_eq!(rematches[1], 0);
+    }
 }
@@ -24,22 +24,38 @@
 #![deny(unused_crate_dependencies)]
 
 pub mod options;
+pub mod prediction;
+pub mod progress;
+pub mod rating;
 pub mod render;
+pub mod rounds;
+pub mod scenarios;
+pub mod schedule;
+pub mod seeding;
+pub mod sides;
+pub mod snapshot;
 pub mod standings;
 
 mod double_elimination;
+mod pools;
 mod round_robin;
 mod single_elimination;
 mod swiss;
 pub mod tournament;
+pub mod upcoming;
 mod utils;
+pub mod veto;
 
 pub use double_elimination::DoubleElimination;
+pub use pools::Pools;
 use render::{RenderState, Renderer};
 pub use round_robin::RoundRobin;
+use rounds::Rounds;
+use sides::{SideRule, Sides};
 pub use single_elimination::SingleElimination;
 use standings::Standings;
 pub use swiss::Swiss;
+use upcoming::Upcoming;
 
 use thiserror::Error;
 
@@ -364,6 +380,11 @@ pub enum Error {
     )]
     /// The tournament defined an entrant that does not exist.
     InvalidEntrant { index: usize, length: usize },
+    /// The snapshot was written by a newer, unsupported version.
+    #[error(
+        "unsupported snapshot version {version}: only versions up to {supported} are supported"
+    )]
+    UnsupportedSnapshotVersion { version: u32, supported: u32 },
 }
 
 /// The result of a [`Match`].
@@ -994,6 +1015,19 @@ pub trait System: Sized + Borrow<Entrants<Self::Entrant>> {
     /// Returns the [`NextMatches`] of the match with the given `index`.
     fn next_matches(&self, index: usize) -> NextMatches;
 
+    /// Returns the indices of all matches that must be concluded before the match with the given
+    /// `index` can be played.
+    ///
+    /// The default implementation returns all matches whose [`NextMatches`] point to `index`.
+    fn predecessors(&self, index: usize) -> Vec<usize> {
+        (0..self.matches().len())
+            .filter(|i| {
+                let next = self.next_matches(*i);
+                next.winner_index() == Some(index) || next.loser_index() == Some(index)
+            })
+            .collect()
+    }
+
     /// Updates the match at `index` by applying `f` on it. The next match is updated using the
     /// returned [`MatchResult`]. If `index` is out-of-bounds, `f` is never called.
     fn update_match<F>(&mut self, index: usize, f: F)
@@ -1007,7 +1041,36 @@ pub trait System: Sized + Borrow<Entrants<Self::Entrant>> {
     where
         R: Renderer<Self, Self::Entrant, Self::NodeData>,
     {
-        renderer.render(self.start_render().root);
+        let sides = self.sides();
+        renderer.render(self.start_render().root.with_sides(&sides));
+    }
+
+    /// Returns the [`SideRule`] used to assign the sides of matches.
+    ///
+    /// The default implementation returns [`SideRule::HigherSeed`].
+    fn side_rule(&self) -> SideRule {
+        SideRule::HigherSeed
+    }
+
+    /// Returns the [`Sides`] of all matches using the [`SideRule`] returned by [`side_rule`].
+    ///
+    /// [`side_rule`]: Self::side_rule
+    fn sides(&self) -> Sides {
+        Sides::new(self, self.side_rule())
+    }
+
+    /// Returns all [`Rounds`] with their matches in the order they are played.
+    ///
+    /// The default implementation groups matches by their [`predecessors`].
+    ///
+    /// [`predecessors`]: Self::predecessors
+    fn rounds(&self) -> Rounds {
+        rounds::by_depth(self)
+    }
+
+    /// Returns the playable and blocked matches and the next match of every entrant.
+    fn upcoming(&self) -> Upcoming {
+        Upcoming::new(self)
     }
 
     fn standings(&self) -> Standings {
@@ -11,8 +11,10 @@
 #[cfg(feature = "serde")]
 mod serde_impl;
 
+use std::cmp::Ordering;
 use std::collections::hash_map::{Iter, Keys};
 use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
 use std::hint;
 
 use thiserror::Error;
@@ -32,6 +34,20 @@ pub enum Error {
         found: &'static str,
         expected: &'static str,
     },
+    #[error("invalid value for {key}: {found} is smaller than the minimum of {min}")]
+    ValueTooSmall {
+        key: String,
+        found: String,
+        min: String,
+    },
+    #[error("invalid value for {key}: {found} is larger than the maximum of {max}")]
+    ValueTooLarge {
+        key: String,
+        found: String,
+        max: String,
+    },
+    #[error("invalid value for {key}: {found} is not one of the allowed choices")]
+    InvalidChoice { key: String, found: String },
 }
 
 /// A list of optional values for a tournament. `TournamentOptions` includes the names and should
@@ -62,13 +78,8 @@ impl TournamentOptions {
         match self.0.get_mut(&key) {
             Some(val) => val.value = value.into(),
             None => {
-                self.0.insert(
-                    key,
-                    TournamentOption {
-                        name: String::new(),
-                        value: value.into(),
-                    },
-                );
+                self.0
+                    .insert(key, TournamentOption::new(String::new(), value.into()));
             }
         }
     }
@@ -106,7 +117,7 @@ impl From<TournamentOptions> for TournamentOptionValues {
 }
 
 /// A list of optional key-values for a tournament which only contains the values.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TournamentOptionValues(HashMap<String, OptionValue>);
 
@@ -137,14 +148,27 @@ impl TournamentOptionValues {
         self.0.iter()
     }
 
+    /// Merges `self` with the accepted `options`. Every value in `self` is validated against the
+    /// [`TournamentOption`] with the same key. Keys that are missing in `self` are filled with
+    /// the default value of the option.
+    ///
+    /// Integer values are converted into `f64` (and `u64` into `i64`) if the option expects
+    /// that type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `self` contains a key that does not exist in `options`, or a value
+    /// has the wrong type or violates the constraints of its option.
     // TODO: Can avoid some `to_owned` calls.
     pub fn merge(mut self, mut options: TournamentOptions) -> Result<Self, Error> {
-        for (key, value) in self.0.iter() {
+        for (key, value) in self.0.iter_mut() {
             let default_value = match options.0.remove(key) {
                 Some(value) => value,
                 None => return Err(Error::UnknownKey(key.to_owned())),
             };
 
+            *value = value.clone().coerce(&default_value.value);
+
             if default_value.value.value_type() != value.value_type() {
                 return Err(Error::InvalidValue {
                     key: key.to_owned(),
@@ -152,6 +176,8 @@ impl TournamentOptionValues {
                     expected: default_value.value.value_type(),
                 });
             }
+
+            default_value.validate(key, value)?;
         }
 
         // Fill the unassigned fields with defaults.
@@ -163,19 +189,132 @@ impl TournamentOptionValues {
     }
 }
 
+/// A single option accepted by a [`System`]. `value` contains the default value.
+///
+/// An option can optionally restrict the values it accepts. `min` and `max` limit numeric
+/// values, `choices` restricts the value to a fixed list. The constraints are enforced by
+/// [`TournamentOptionValues::merge`].
+///
+/// [`System`]: crate::System
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TournamentOption {
     pub name: String,
     pub value: OptionValue,
+    /// The smallest value accepted (inclusive).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub min: Option<OptionValue>,
+    /// The largest value accepted (inclusive).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max: Option<OptionValue>,
+    /// A list of all accepted values. All values are accepted if this is empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub choices: Vec<OptionValue>,
+}
+
+impl TournamentOption {
+    /// Creates a new `TournamentOption` without any constraints.
+    pub fn new<T, V>(name: T, value: V) -> Self
+    where
+        T: ToString,
+        V: Into<OptionValue>,
+    {
+        Self {
+            name: name.to_string(),
+            value: value.into(),
+            min: None,
+            max: None,
+            choices: Vec::new(),
+        }
+    }
+
+    /// Sets the smallest accepted value.
+    pub fn min<V>(mut self, min: V) -> Self
+    where
+        V: Into<OptionValue>,
+    {
+        self.min = Some(min.into());
+        self
+    }
+
+    /// Sets the largest accepted value.
+    pub fn max<V>(mut self, max: V) -> Self
+    where
+        V: Into<OptionValue>,
+    {
+        self.max = Some(max.into());
+        self
+    }
+
+    /// Sets the list of accepted values.
+    pub fn choices<I, V>(mut self, choices: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<OptionValue>,
+    {
+        self.choices = choices.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Checks that `value` satisfies all constraints of this option. `key` is only used in the
+    /// returned [`Error`].
+    ///
+    /// Note that this does not check the type of `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `value` is outside of `min`/`max` or not in `choices`.
+    pub fn validate(&self, key: &str, value: &OptionValue) -> Result<(), Error> {
+        if let Some(min) = &self.min {
+            if value.partial_cmp_numeric(min) == Some(Ordering::Less) {
+                return Err(Error::ValueTooSmall {
+                    key: key.to_owned(),
+                    found: value.to_string(),
+                    min: min.to_string(),
+                });
+            }
+        }
+
+        if let Some(max) = &self.max {
+            if value.partial_cmp_numeric(max) == Some(Ordering::Greater) {
+                return Err(Error::ValueTooLarge {
+                    key: key.to_owned(),
+                    found: value.to_string(),
+                    max: max.to_string(),
+                });
+            }
+        }
+
+        if !self.choices.is_empty() && !self.choices.contains(value) {
+            return Err(Error::InvalidChoice {
+                key: key.to_owned(),
+                found: value.to_string(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 /// The value of a [`TournamentOption`].
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// Two [`OptionValue::F64`] values are only equal if they have the same bit pattern. This keeps
+/// the equality reflexive (`NaN` equals itself), but means that `0.0` and `-0.0` are distinct.
+#[derive(Clone, Debug)]
 pub enum OptionValue {
     Bool(bool),
     I64(i64),
     U64(u64),
+    F64(f64),
     String(String),
 }
 
@@ -194,6 +333,7 @@ impl OptionValue {
             Self::Bool(_) => "bool",
             Self::I64(_) => "i64",
             Self::U64(_) => "u64",
+            Self::F64(_) => "f64",
             Self::String(_) => "string",
         }
     }
@@ -537,6 +677,121 @@ impl OptionValue {
         }
     }
 
+    /// Returns the contained [`F64`] value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `self` value is not [`F64`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::F64(0.5);
+    /// assert_eq!(val.unwrap_f64(), 0.5);
+    /// ```
+    ///
+    /// ```should_panic
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::Bool(false);
+    /// assert_eq!(val.unwrap_f64(), 0.5); // Panics
+    /// ```
+    ///
+    /// [`F64`]: Self::F64
+    #[inline]
+    pub fn unwrap_f64(self) -> f64 {
+        match self {
+            Self::F64(val) => val,
+            _ => panic!(
+                "called `OptionValue::unwrap_f64` on a `{}` value",
+                self.panic_string()
+            ),
+        }
+    }
+
+    /// Returns the contained [`F64`] value without checking whether `self` is [`F64`].
+    ///
+    /// # Safety
+    ///
+    /// Calling this method on a value other than [`F64`] is undefined behavoir.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::F64(0.5);
+    /// assert_eq!(unsafe { val.unwrap_f64_unchecked() }, 0.5);
+    /// ```
+    ///
+    /// ```no_run
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::Bool(false);
+    /// assert_eq!(unsafe { val.unwrap_f64_unchecked() }, 0.5); // Undefined behavoir
+    /// ```
+    ///
+    /// [`F64`]: Self::F64
+    #[inline]
+    pub unsafe fn unwrap_f64_unchecked(self) -> f64 {
+        match self {
+            Self::F64(val) => val,
+            // SAFETY: The caller must guarantee that this branch never executed.
+            _ => unsafe { hint::unreachable_unchecked() },
+        }
+    }
+
+    /// Returns the contained [`F64`] value or the provided default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::F64(0.5);
+    /// assert_eq!(val.unwrap_f64_or(0.0), 0.5);
+    /// ```
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::Bool(false);
+    /// assert_eq!(val.unwrap_f64_or(0.0), 0.0);
+    /// ```
+    ///
+    /// [`F64`]: Self::F64
+    #[inline]
+    pub fn unwrap_f64_or(self, default: f64) -> f64 {
+        match self {
+            Self::F64(val) => val,
+            _ => default,
+        }
+    }
+
+    /// Returns the contained [`F64`] value or computes it from the provided closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::F64(0.5);
+    /// assert_eq!(val.unwrap_f64_or_else(|| 0.0), 0.5);
+    /// ```
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::OptionValue;
+    /// let val = OptionValue::Bool(false);
+    /// assert_eq!(val.unwrap_f64_or_else(|| 0.0), 0.0);
+    /// ```
+    ///
+    /// [`F64`]: Self::F64
+    #[inline]
+    pub fn unwrap_f64_or_else<F>(self, f: F) -> f64
+    where
+        F: FnOnce() -> f64,
+    {
+        match self {
+            Self::F64(val) => val,
+            _ => f(),
+        }
+    }
+
     /// Returns the contained [`String`] value.
     ///
     /// # Panics
@@ -663,9 +918,62 @@ impl OptionValue {
             Self::Bool(_) => "Bool",
             Self::I64(_) => "I64",
             Self::U64(_) => "U64",
+            Self::F64(_) => "F64",
             Self::String(_) => "String",
         }
     }
+
+    /// Compares two numeric values of the same type. Returns `None` if either value is not
+    /// numeric or the types differ.
+    fn partial_cmp_numeric(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::I64(lhs), Self::I64(rhs)) => lhs.partial_cmp(rhs),
+            (Self::U64(lhs), Self::U64(rhs)) => lhs.partial_cmp(rhs),
+            (Self::F64(lhs), Self::F64(rhs)) => lhs.partial_cmp(rhs),
+            _ => None,
+        }
+    }
+
+    /// Converts `self` into the numeric type of `expected` if the conversion is lossless (or
+    /// into a float). All other values are returned unchanged.
+    fn coerce(self, expected: &Self) -> Self {
+        match (self, expected) {
+            (Self::I64(val), Self::F64(_)) => Self::F64(val as f64),
+            (Self::U64(val), Self::F64(_)) => Self::F64(val as f64),
+            (Self::U64(val), Self::I64(_)) => match i64::try_from(val) {
+                Ok(val) => Self::I64(val),
+                Err(_) => Self::U64(val),
+            },
+            (val, _) => val,
+        }
+    }
+}
+
+impl PartialEq for OptionValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bool(lhs), Self::Bool(rhs)) => lhs == rhs,
+            (Self::I64(lhs), Self::I64(rhs)) => lhs == rhs,
+            (Self::U64(lhs), Self::U64(rhs)) => lhs == rhs,
+            (Self::F64(lhs), Self::F64(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (Self::String(lhs), Self::String(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for OptionValue {}
+
+impl Display for OptionValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(val) => Display::fmt(val, f),
+            Self::I64(val) => Display::fmt(val, f),
+            Self::U64(val) => Display::fmt(val, f),
+            Self::F64(val) => Display::fmt(val, f),
+            Self::String(val) => Display::fmt(val, f),
+        }
+    }
 }
 
 impl From<bool> for OptionValue {
@@ -689,6 +997,13 @@ impl From<u64> for OptionValue {
     }
 }
 
+impl From<f64> for OptionValue {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::F64(value)
+    }
+}
+
 impl<'a> From<&'a str> for OptionValue {
     #[inline]
     fn from(value: &'a str) -> Self {
@@ -716,13 +1031,29 @@ impl Builder {
         T: ToString,
         V: Into<OptionValue>,
     {
-        self.options.insert(
-            key.to_string(),
-            TournamentOption {
-                name: name.to_string(),
-                value: value.into(),
-            },
-        );
+        self.options
+            .insert(key.to_string(), TournamentOption::new(name, value));
+        self
+    }
+
+    /// Inserts a new [`TournamentOption`] with constraints. If the `key` already exists, it is
+    /// overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dynamic_tournament_core::options::{TournamentOption, TournamentOptions};
+    /// let options = TournamentOptions::builder()
+    ///     .insert(
+    ///         "rounds",
+    ///         TournamentOption::new("Number of rounds", 3u64).min(1u64).max(10u64),
+    ///     )
+    ///     .build();
+    ///
+    /// assert_eq!(options.get("rounds").unwrap().max, Some(10u64.into()));
+    /// ```
+    pub fn insert(mut self, key: &'static str, option: TournamentOption) -> Self {
+        self.options.insert(key, option);
         self
     }
 
@@ -732,3 +1063,127 @@ impl Builder {
         self.options
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, OptionValue, TournamentOption, TournamentOptionValues, TournamentOptions};
+
+    fn options() -> TournamentOptions {
+        TournamentOptions::builder()
+            .option("third_place_match", "Third place match", false)
+            .insert(
+                "rounds",
+                TournamentOption::new("Rounds", 3u64).min(1u64).max(10u64),
+            )
+            .insert(
+                "pairing",
+                TournamentOption::new("Pairing", "monrad").choices(["monrad", "dutch"]),
+            )
+            .insert(
+                "weight",
+                TournamentOption::new("Weight", 1.0).min(0.0).max(1.0),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_merge_defaults() {
+        let values = TournamentOptionValues::new().merge(options()).unwrap();
+
+        assert_eq!(
+            values.get("third_place_match"),
+            Some(&OptionValue::Bool(false))
+        );
+        assert_eq!(values.get("rounds"), Some(&OptionValue::U64(3)));
+        assert_eq!(values.get("pairing"), Some(&OptionValue::string("monrad")));
+        assert_eq!(values.get("weight"), Some(&OptionValue::F64(1.0)));
+    }
+
+    #[test]
+    fn test_merge_type() {
+        let mut values = TournamentOptionValues::new();
+        values.set("rounds", "3");
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::InvalidValue {
+                key: "rounds".into(),
+                found: "string",
+                expected: "u64",
+            }
+        );
+
+        let mut values = TournamentOptionValues::new();
+        values.set("unknown", true);
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::UnknownKey("unknown".into())
+        );
+    }
+
+    #[test]
+    fn test_merge_min_max() {
+        let mut values = TournamentOptionValues::new();
+        values.set("rounds", 10u64);
+        let values = values.merge(options()).unwrap();
+        assert_eq!(values.get("rounds"), Some(&OptionValue::U64(10)));
+
+        let mut values = TournamentOptionValues::new();
+        values.set("rounds", 0u64);
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::ValueTooSmall {
+                key: "rounds".into(),
+                found: "0".into(),
+                min: "1".into(),
+            }
+        );
+
+        let mut values = TournamentOptionValues::new();
+        values.set("rounds", 11u64);
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::ValueTooLarge {
+                key: "rounds".into(),
+                found: "11".into(),
+                max: "10".into(),
+            }
+        );
+
+        let mut values = TournamentOptionValues::new();
+        values.set("weight", 1.5);
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::ValueTooLarge {
+                key: "weight".into(),
+                found: "1.5".into(),
+                max: "1".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_choices() {
+        let mut values = TournamentOptionValues::new();
+        values.set("pairing", "dutch");
+        let values = values.merge(options()).unwrap();
+        assert_eq!(values.get("pairing"), Some(&OptionValue::string("dutch")));
+
+        let mut values = TournamentOptionValues::new();
+        values.set("pairing", "swiss");
+        assert_eq!(
+            values.merge(options()).unwrap_err(),
+            Error::InvalidChoice {
+                key: "pairing".into(),
+                found: "swiss".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_coerce() {
+        let mut values = TournamentOptionValues::new();
+        values.set("weight", 0u64);
+        let values = values.merge(options()).unwrap();
+        assert_eq!(values.get("weight"), Some(&OptionValue::F64(0.0)));
+    }
+}
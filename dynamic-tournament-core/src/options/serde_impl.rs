@@ -16,6 +16,7 @@ impl Serialize for OptionValue {
             Self::Bool(v) => serializer.serialize_bool(*v),
             Self::I64(v) => serializer.serialize_i64(*v),
             Self::U64(v) => serializer.serialize_u64(*v),
+            Self::F64(v) => serializer.serialize_f64(*v),
             Self::String(v) => serializer.serialize_str(v),
         }
     }
@@ -38,7 +39,7 @@ impl<'de> Visitor<'de> for OptionValueVisitor {
 
     #[inline]
     fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
-        formatter.write_str("a bool, i64, u64 or string")
+        formatter.write_str("a bool, i64, u64, f64 or string")
     }
 
     #[inline]
@@ -65,6 +66,14 @@ impl<'de> Visitor<'de> for OptionValueVisitor {
         Ok(OptionValue::U64(v))
     }
 
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(OptionValue::F64(v))
+    }
+
     #[inline]
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
@@ -93,6 +102,7 @@ mod tests {
         assert_tokens(&OptionValue::Bool(true), &[Token::Bool(true)]);
         assert_tokens(&OptionValue::U64(123), &[Token::U64(123)]);
         assert_tokens(&OptionValue::I64(-456), &[Token::I64(-456)]);
+        assert_tokens(&OptionValue::F64(0.5), &[Token::F64(0.5)]);
         assert_tokens(&OptionValue::string("Hi"), &[Token::Str("Hi")]);
     }
 }
@@ -0,0 +1,826 @@
+use crate::options::{TournamentOption, TournamentOptionValues, TournamentOptions};
+use crate::render::{Column, Element, Label, Position, RenderState, Row};
+use crate::rounds::{Round, Rounds};
+use crate::standings::Standings;
+use crate::{EntrantData, EntrantSpot, Entrants, Error, Match, Matches, NextMatches, System};
+use crate::{MatchResult, Node, Result, SingleElimination};
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// Qualifier pools feeding a main bracket.
+///
+/// The entrants are split into single elimination pools. The winner of every pool fills a fixed
+/// slot in a single elimination main bracket. The number of pools equals the size of the main
+/// bracket, set by the `main_size` option. It is rounded down to a power of two and never
+/// exceeds the number of entrants.
+///
+/// Entrants are distributed over the pools in seed order: the entrant at index `i` plays in pool
+/// `i % pools`, spreading the top seeds over all pools. The winner of pool `p` takes the slot of
+/// the entrant at index `p` in the main bracket.
+///
+/// All matches are stored in a single list: the matches of every pool in order, followed by the
+/// matches of the main bracket.
+///
+/// # Examples
+///
+/// ```
+/// # use dynamic_tournament_core::options::TournamentOptionValues;
+/// # use dynamic_tournament_core::{EntrantScore, Pools, System};
+/// let mut options = TournamentOptionValues::new();
+/// options.set("main_size", 4u64);
+/// let tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..16, options);
+///
+/// assert_eq!(tournament.num_pools(), 4);
+/// // Four pools of 4 entrants with 3 matches each and a main bracket with 3 matches.
+/// assert_eq!(tournament.matches().len(), 15);
+/// assert_eq!(tournament.main_bracket_index(), 12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pools<T, D> {
+    entrants: Entrants<T>,
+    matches: Matches<D>,
+    pools: Vec<Pool>,
+}
+
+impl<T, D> Pools<T, D>
+where
+    D: EntrantData,
+{
+    /// Creates a new `Pools` tournament with the given `entrants`.
+    pub fn new<I>(entrants: I) -> Self
+    where
+        I: Iterator<Item = T>,
+    {
+        Self::new_with_options(entrants, Self::options())
+    }
+
+    /// Creates a new `Pools` tournament with the given `entrants` and using the given `options`.
+    pub fn new_with_options<I, O>(entrants: I, options: O) -> Self
+    where
+        I: Iterator<Item = T>,
+        O: Into<TournamentOptionValues>,
+    {
+        let options = PoolsOptions::new(options.into());
+        log::debug!("Using options: {:?}", options);
+
+        let entrants: Entrants<T> = entrants.collect();
+
+        log::debug!(
+            "Creating new Pools bracket with {} entrants",
+            entrants.len()
+        );
+
+        let pools = layout(entrants.len(), options.main_size);
+        let num_pools = pools.len();
+
+        let mut matches = Matches::with_capacity(calculate_matches(&pools));
+
+        for (index, pool) in pools.iter().enumerate() {
+            let mut pool_matches =
+                SingleElimination::<usize, D>::new(0..pool.entrants).into_matches();
+
+            // Map the entrants of the pool to the entrants of the tournament.
+            for match_ in pool_matches.iter_mut() {
+                for spot in match_.entrants.iter_mut() {
+                    if let EntrantSpot::Entrant(node) = spot {
+                        node.index = index + node.index * num_pools;
+                    }
+                }
+            }
+
+            matches.append(&mut pool_matches);
+        }
+
+        while matches.len() < matches.capacity() {
+            matches.push(Match::new([EntrantSpot::TBD, EntrantSpot::TBD]));
+        }
+
+        let mut this = Self {
+            entrants,
+            matches,
+            pools,
+        };
+
+        // A pool with a single entrant has nothing to play. The entrant directly takes its slot
+        // in the main bracket.
+        for (index, pool) in this.pools.clone().iter().enumerate() {
+            if pool.entrants == 1 {
+                let next_matches = this.next_matches(pool.start);
+                if let Some(spot) = next_matches.winner_mut(&mut this.matches) {
+                    *spot = EntrantSpot::Entrant(Node::new(index));
+                }
+            }
+        }
+
+        log::debug!(
+            "Created new Pools bracket with {} pools and {} matches",
+            num_pools,
+            this.matches.len()
+        );
+
+        this
+    }
+
+    /// Returns the [`TournamentOptions`] accepted by this system.
+    pub fn options() -> TournamentOptions {
+        TournamentOptions::builder()
+            .insert(
+                "main_size",
+                TournamentOption::new("The number of entrants in the main bracket", 8u64).min(1u64),
+            )
+            .build()
+    }
+
+    /// Resumes the bracket from existing matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if `matches` has an invalid number of matches for `entrants` or
+    /// a [`Node`] in `matches` points to a value that is out-of-bounds.
+    pub fn resume<O>(entrants: Entrants<T>, matches: Matches<D>, options: O) -> Result<Self>
+    where
+        O: Into<TournamentOptionValues>,
+    {
+        let options = PoolsOptions::new(options.into());
+
+        log::debug!(
+            "Trying to resume Pools bracket with {} entrants and {} matches",
+            entrants.len(),
+            matches.len()
+        );
+
+        let pools = layout(entrants.len(), options.main_size);
+
+        let expected = calculate_matches(&pools);
+        let found = matches.len();
+
+        if found != expected {
+            return Err(Error::InvalidNumberOfMatches { expected, found });
+        }
+
+        for m in matches.iter() {
+            for entrant in m.entrants.iter() {
+                if let EntrantSpot::Entrant(entrant) = entrant {
+                    if entrant.index >= entrants.len() {
+                        return Err(Error::InvalidEntrant {
+                            index: entrant.index,
+                            length: entrants.len(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            entrants,
+            matches,
+            pools,
+        })
+    }
+
+    /// Returns the number of pools. This is also the number of entrants in the main bracket.
+    #[inline]
+    pub fn num_pools(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Returns the index of the first match of the main bracket.
+    #[inline]
+    pub fn main_bracket_index(&self) -> usize {
+        self.pools
+            .last()
+            .map(|pool| pool.start + pool.len)
+            .unwrap_or(0)
+    }
+
+    /// Returns the pool of the match with the given `index`. Returns `None` if the match is
+    /// part of the main bracket or `index` is out-of-bounds.
+    pub fn pool_of(&self, index: usize) -> Option<usize> {
+        self.pools
+            .iter()
+            .position(|pool| (pool.start..pool.start + pool.len).contains(&index))
+    }
+}
+
+impl<T, D> System for Pools<T, D>
+where
+    D: EntrantData,
+{
+    type Entrant = T;
+    type NodeData = D;
+
+    #[inline]
+    fn entrants(&self) -> &Entrants<Self::Entrant> {
+        &self.entrants
+    }
+
+    #[inline]
+    unsafe fn entrants_mut(&mut self) -> &mut Entrants<Self::Entrant> {
+        &mut self.entrants
+    }
+
+    #[inline]
+    fn into_entrants(self) -> Entrants<Self::Entrant> {
+        self.entrants
+    }
+
+    #[inline]
+    fn matches(&self) -> &Matches<Self::NodeData> {
+        &self.matches
+    }
+
+    #[inline]
+    unsafe fn matches_mut(&mut self) -> &mut Matches<Self::NodeData> {
+        &mut self.matches
+    }
+
+    #[inline]
+    fn into_matches(self) -> Matches<Self::NodeData> {
+        self.matches
+    }
+
+    fn next_matches(&self, index: usize) -> NextMatches {
+        let main_index = self.main_bracket_index();
+
+        if index >= self.matches.len() {
+            return NextMatches::default();
+        }
+
+        // Main bracket
+        if index >= main_index {
+            let index = index - main_index;
+
+            // The final match of the main bracket.
+            if index + 1 >= self.matches.len() - main_index {
+                return NextMatches::default();
+            }
+
+            let winner_index = main_index + self.pools.len() / 2 + index / 2;
+            return NextMatches::new(Some((winner_index, index % 2)), None);
+        }
+
+        let pool_index = self.pool_of(index).unwrap();
+        let pool = self.pools[pool_index];
+        let index = index - pool.start;
+
+        // The final match of the pool moves the winner into the main bracket.
+        if index + 1 == pool.len {
+            if self.pools.len() < 2 {
+                return NextMatches::default();
+            }
+
+            let initial_matches = self.pools.len() / 2;
+            let winner_index = main_index + pool_index % initial_matches;
+
+            return NextMatches::new(Some((winner_index, pool_index / initial_matches)), None);
+        }
+
+        let winner_index = pool.start + pool.entrants.next_power_of_two() / 2 + index / 2;
+        NextMatches::new(Some((winner_index, index % 2)), None)
+    }
+
+    fn update_match<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(&mut Match<Node<Self::NodeData>>, &mut MatchResult<Self::NodeData>),
+    {
+        let r#match = match self.matches.get_mut(index) {
+            Some(r#match) => r#match,
+            None => return,
+        };
+
+        let mut res = MatchResult::default();
+
+        f(r#match, &mut res);
+
+        let next_matches = self.next_matches(index);
+
+        if let Some((entrant, data)) = res.winner {
+            if let Some(spot) = next_matches.winner_mut(&mut self.matches) {
+                log::debug!("Next winner match is {:?}", next_matches.winner_index());
+
+                *spot = entrant.map(|index| Node::new_with_data(index, data));
+            }
+        }
+
+        if res.reset {
+            let r#match = self.matches.get_mut(index).unwrap();
+
+            for entrant in r#match.entrants.iter_mut() {
+                if let EntrantSpot::Entrant(entrant) = entrant {
+                    entrant.data = D::default();
+                }
+            }
+
+            // Reset all following matches, including those in the main bracket.
+            let mut next_index = index;
+            loop {
+                let next_matches = self.next_matches(next_index);
+                let Some((winner_index, position)) = next_matches.winner else {
+                    break;
+                };
+
+                next_index = winner_index;
+                self.matches[next_index][position] = EntrantSpot::TBD;
+            }
+        }
+    }
+
+    fn rounds(&self) -> Rounds {
+        let mut rounds = Vec::new();
+
+        for (index, pool) in self.pools.iter().enumerate() {
+            let pool_rounds = elimination_rounds(pool.start, pool.len);
+            let num_rounds = pool_rounds.len();
+
+            for (round, matches) in pool_rounds.into_iter().enumerate() {
+                if round + 1 == num_rounds {
+                    rounds.push(Round::new(
+                        format!("Pool {} Final", index + 1),
+                        format!("P{}-F", index + 1),
+                        matches,
+                    ));
+                } else {
+                    rounds.push(Round::new(
+                        format!("Pool {} Round {}", index + 1, round + 1),
+                        format!("P{}-R{}", index + 1, round + 1),
+                        matches,
+                    ));
+                }
+            }
+        }
+
+        let main_index = self.main_bracket_index();
+        let main_rounds = elimination_rounds(main_index, self.matches.len() - main_index);
+        let num_rounds = main_rounds.len();
+
+        for (round, matches) in main_rounds.into_iter().enumerate() {
+            if round + 1 == num_rounds {
+                rounds.push(Round::new("Final", "F", matches));
+            } else {
+                rounds.push(Round::new(
+                    format!("Main Bracket Round {}", round + 1),
+                    format!("MB-R{}", round + 1),
+                    matches,
+                ));
+            }
+        }
+
+        Rounds::new(rounds)
+    }
+
+    fn start_render(&self) -> RenderState<'_, Self> {
+        let mut sections = Vec::new();
+
+        for (index, pool) in self.pools.iter().enumerate() {
+            sections.push(Element::new(Row {
+                label: Some(Label::from(format!("Pool {}", index + 1))),
+                position: Some(Position::Start),
+                children: render_rounds(pool.start, pool.len).into_iter(),
+            }));
+        }
+
+        let main_index = self.main_bracket_index();
+        if main_index < self.matches.len() {
+            sections.push(Element::new(Row {
+                label: Some(Label::from("Main Bracket")),
+                position: Some(Position::Start),
+                children: render_rounds(main_index, self.matches.len() - main_index).into_iter(),
+            }));
+        }
+
+        RenderState {
+            root: Element::new(Column::new(sections)),
+        }
+    }
+
+    fn standings(&self) -> Standings {
+        #[derive(Copy, Clone, Debug, Default)]
+        struct Score {
+            main_bracket: bool,
+            main_wins: u64,
+            wins: u64,
+            loses: u64,
+        }
+
+        let main_index = self.main_bracket_index();
+
+        let mut scores = HashMap::new();
+        for index in 0..self.entrants.len() {
+            scores.insert(index, Score::default());
+        }
+
+        for (index, match_) in self.matches.iter().enumerate() {
+            for entrant in &match_.entrants {
+                let EntrantSpot::Entrant(node) = entrant else {
+                    continue;
+                };
+
+                let score = scores.get_mut(&node.index).unwrap();
+
+                if index >= main_index {
+                    score.main_bracket = true;
+                }
+
+                if match_.is_concluded() {
+                    if node.data.winner() {
+                        score.wins += 1;
+
+                        if index >= main_index {
+                            score.main_wins += 1;
+                        }
+                    } else {
+                        score.loses += 1;
+                    }
+                }
+            }
+        }
+
+        // Entrants that reached the main bracket are always placed above all others.
+        let mut entries: Vec<_> = scores.into_iter().collect();
+        entries.sort_by(|(a_index, a), (b_index, b)| {
+            a.main_bracket
+                .cmp(&b.main_bracket)
+                .then(a.main_wins.cmp(&b.main_wins))
+                .then(a.wins.cmp(&b.wins))
+                .reverse()
+                .then(a.loses.cmp(&b.loses))
+                .then(a_index.cmp(b_index))
+        });
+
+        let mut builder = Standings::builder();
+
+        builder.key("Main Bracket");
+        builder.key("Wins");
+        builder.key("Losses");
+
+        for (index, score) in entries {
+            builder.entry(index, |builder| {
+                builder.value(score.main_bracket);
+                builder.value(score.wins);
+                builder.value(score.loses);
+            });
+        }
+
+        builder.build()
+    }
+}
+
+impl<T, D> Borrow<Entrants<T>> for Pools<T, D> {
+    #[inline]
+    fn borrow(&self) -> &Entrants<T> {
+        &self.entrants
+    }
+}
+
+impl<T, D> Borrow<Matches<D>> for Pools<T, D> {
+    #[inline]
+    fn borrow(&self) -> &Matches<D> {
+        &self.matches
+    }
+}
+
+/// The position of a single pool in the list of matches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Pool {
+    /// The index of the first match of the pool.
+    start: usize,
+    /// The number of matches in the pool.
+    len: usize,
+    /// The number of entrants in the pool.
+    entrants: usize,
+}
+
+/// Returns the layout of all pools for `entrants` entrants and a main bracket with `main_size`
+/// entrants.
+fn layout(entrants: usize, main_size: usize) -> Vec<Pool> {
+    if entrants == 0 {
+        return Vec::new();
+    }
+
+    // The largest power of two that fits both the main bracket and the entrants.
+    let max = main_size.clamp(1, entrants);
+    let num_pools = match max.next_power_of_two() {
+        n if n == max => n,
+        n => n / 2,
+    };
+
+    let mut start = 0;
+    (0..num_pools)
+        .map(|index| {
+            let entrants = entrants / num_pools + usize::from(index < entrants % num_pools);
+            let len = match entrants {
+                1 | 2 => 1,
+                n => n.next_power_of_two() - 1,
+            };
+
+            let pool = Pool {
+                start,
+                len,
+                entrants,
+            };
+
+            start += len;
+            pool
+        })
+        .collect()
+}
+
+/// Returns the number of matches of all `pools` and the main bracket.
+fn calculate_matches(pools: &[Pool]) -> usize {
+    let pool_matches: usize = pools.iter().map(|pool| pool.len).sum();
+
+    pool_matches + pools.len().saturating_sub(1)
+}
+
+/// Returns the match indices of every round of a single elimination bracket with `len` matches
+/// starting at `start`.
+fn elimination_rounds(start: usize, len: usize) -> Vec<std::ops::Range<usize>> {
+    let mut rounds = Vec::new();
+
+    let end = start + len;
+    let mut index = start;
+    let mut num_matches = len.div_ceil(2);
+    while index < end {
+        rounds.push(index..(index + num_matches).min(end));
+
+        index += num_matches;
+        num_matches = (num_matches / 2).max(1);
+    }
+
+    rounds
+}
+
+/// Returns a [`Column`] for every round of a single elimination bracket with `len` matches
+/// starting at `start`.
+fn render_rounds<'a, T>(start: usize, len: usize) -> Vec<Element<'a, T>>
+where
+    T: System,
+{
+    elimination_rounds(start, len)
+        .into_iter()
+        .map(|round| {
+            let matches = round
+                .map(|index| {
+                    Element::new(crate::render::Match {
+                        index,
+                        predecessors: vec![],
+                        sides: None,
+                        _marker: PhantomData,
+                        label: None,
+                        position: None,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            Element::new(Column {
+                label: None,
+                position: Some(Position::SpaceAround),
+                children: matches.into_iter(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, Debug)]
+struct PoolsOptions {
+    main_size: usize,
+}
+
+impl PoolsOptions {
+    fn new(mut options: TournamentOptionValues) -> Self {
+        let mut this = Self::default();
+
+        if let Some(val) = options.take("main_size") {
+            this.main_size = val.unwrap_u64_or(8) as usize;
+        }
+
+        this
+    }
+}
+
+impl Default for PoolsOptions {
+    fn default() -> Self {
+        Self { main_size: 8 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{layout, Pool, Pools};
+    use crate::tests::{TColumn, TElement, TMatch, TRow, TestRenderer};
+    use crate::{option_values, EntrantScore, EntrantSpot, Node, System};
+
+    fn win(tournament: &mut Pools<u32, EntrantScore<u32>>, index: usize) {
+        tournament.update_match(index, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[0] {
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[0]);
+            res.loser_default(&m.entrants[1]);
+        });
+    }
+
+    #[test]
+    fn test_pools_layout() {
+        assert_eq!(layout(0, 8), []);
+        assert_eq!(
+            layout(3, 8),
+            [
+                Pool {
+                    start: 0,
+                    len: 1,
+                    entrants: 2
+                },
+                Pool {
+                    start: 1,
+                    len: 1,
+                    entrants: 1
+                },
+            ]
+        );
+
+        let pools = layout(256, 8);
+        assert_eq!(pools.len(), 8);
+        assert!(pools
+            .iter()
+            .all(|pool| pool.entrants == 32 && pool.len == 31));
+
+        // The main size is rounded down to a power of two.
+        assert_eq!(layout(100, 12).len(), 8);
+        assert_eq!(layout(100, 0).len(), 1);
+    }
+
+    #[test]
+    fn test_pools() {
+        let options = option_values!("main_size" => 4u64);
+        let mut tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..16, options);
+
+        assert_eq!(tournament.num_pools(), 4);
+        assert_eq!(tournament.main_bracket_index(), 12);
+        assert_eq!(tournament.pool_of(5), Some(1));
+        assert_eq!(tournament.pool_of(12), None);
+
+        // Entrants are spread over the pools by seed.
+        let first = tournament.matches()[0].entrants.map(|s| s.unwrap().index);
+        assert_eq!(first, [0, 8]);
+        let first = tournament.matches()[3].entrants.map(|s| s.unwrap().index);
+        assert_eq!(first, [1, 9]);
+
+        // Play all pools.
+        for pool in 0..4 {
+            for index in pool * 3..pool * 3 + 3 {
+                win(&mut tournament, index);
+            }
+        }
+
+        // The pool winners fill the main bracket in seed order.
+        assert_eq!(
+            tournament.matches()[12].entrants,
+            [
+                EntrantSpot::Entrant(Node::new(0)),
+                EntrantSpot::Entrant(Node::new(2)),
+            ]
+        );
+        assert_eq!(
+            tournament.matches()[13].entrants,
+            [
+                EntrantSpot::Entrant(Node::new(1)),
+                EntrantSpot::Entrant(Node::new(3)),
+            ]
+        );
+
+        win(&mut tournament, 12);
+        win(&mut tournament, 13);
+        assert_eq!(
+            tournament.matches()[14].entrants,
+            [
+                EntrantSpot::Entrant(Node::new(0)),
+                EntrantSpot::Entrant(Node::new(1)),
+            ]
+        );
+
+        // Resetting a pool match resets the main bracket.
+        tournament.update_match(2, |_, res| {
+            res.reset_default();
+        });
+        assert_eq!(tournament.matches()[12].entrants[0], EntrantSpot::TBD);
+        assert_eq!(tournament.matches()[14].entrants[0], EntrantSpot::TBD);
+    }
+
+    #[test]
+    fn test_pools_single_entrant() {
+        let options = option_values!("main_size" => 4u64);
+        let tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..6, options);
+
+        // Pools 2 and 3 only have a single entrant.
+        assert_eq!(tournament.num_pools(), 4);
+        assert_eq!(tournament.main_bracket_index(), 4);
+        assert_eq!(
+            tournament.matches()[4].entrants,
+            [EntrantSpot::TBD, EntrantSpot::Entrant(Node::new(2))]
+        );
+        assert_eq!(
+            tournament.matches()[5].entrants,
+            [EntrantSpot::TBD, EntrantSpot::Entrant(Node::new(3))]
+        );
+    }
+
+    #[test]
+    fn test_pools_resume() {
+        let options = option_values!("main_size" => 4u64);
+        let tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..16, options.clone());
+
+        let entrants = tournament.entrants().clone();
+        let matches = tournament.matches().clone();
+        assert!(Pools::resume(entrants.clone(), matches.clone(), options.clone()).is_ok());
+
+        let mut short = matches;
+        short.pop();
+        assert!(Pools::resume(entrants, short, options).is_err());
+    }
+
+    #[test]
+    fn test_pools_options() {
+        let options = option_values!("main_size" => 0u64);
+        assert!(options
+            .merge(Pools::<u32, EntrantScore<u32>>::options())
+            .is_err());
+
+        let options = option_values!("main_size" => 1u64);
+        assert!(options
+            .merge(Pools::<u32, EntrantScore<u32>>::options())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_pools_rounds() {
+        let options = option_values!("main_size" => 2u64);
+        let tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..8, options);
+
+        let rounds = tournament.rounds();
+        let labels: Vec<_> = rounds.iter().map(|r| r.label.as_ref()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Pool 1 Round 1",
+                "Pool 1 Final",
+                "Pool 2 Round 1",
+                "Pool 2 Final",
+                "Final"
+            ]
+        );
+        assert_eq!(rounds.code(3).unwrap(), "P2-R1-M1");
+        assert_eq!(rounds.code(6).unwrap(), "F-M1");
+    }
+
+    #[test]
+    fn test_pools_standings() {
+        let options = option_values!("main_size" => 2u64);
+        let mut tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..8, options);
+
+        for index in 0..7 {
+            win(&mut tournament, index);
+        }
+
+        let standings = tournament.standings();
+        let order: Vec<_> = standings.iter().map(|entry| entry.index).collect();
+
+        // Winner and finalist first, then the pool finalists and first round losers.
+        assert_eq!(
+            order[0],
+            tournament.matches()[6].entrants[0].unwrap_ref().index
+        );
+        assert_eq!(
+            order[1],
+            tournament.matches()[6].entrants[1].unwrap_ref().index
+        );
+        assert_eq!(order.len(), 8);
+    }
+
+    #[test]
+    fn test_pools_render() {
+        let options = option_values!("main_size" => 2u64);
+        let tournament = Pools::<u32, EntrantScore<u32>>::new_with_options(0..4, options);
+
+        let mut renderer = TestRenderer::new();
+        tournament.render(&mut renderer);
+
+        assert_eq!(
+            renderer,
+            TElement::Column(TColumn(vec![
+                TElement::Row(TRow(vec![TElement::Column(TColumn(vec![
+                    TElement::Match(TMatch { index: 0 })
+                ]))])),
+                TElement::Row(TRow(vec![TElement::Column(TColumn(vec![
+                    TElement::Match(TMatch { index: 1 })
+                ]))])),
+                TElement::Row(TRow(vec![TElement::Column(TColumn(vec![
+                    TElement::Match(TMatch { index: 2 })
+                ]))])),
+            ]))
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Outcome prediction using Monte Carlo simulation.
+//!
+//! A [`Simulation`] plays all remaining matches of a tournament many times. The winner of every
+//! simulated match is chosen randomly using the Elo win probability of both entrants. The final
+//! [`Standings`] of all runs are collected into a [`Prediction`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::{EntrantScore, SingleElimination};
+//! # use dynamic_tournament_core::prediction::Simulation;
+//! let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+//!
+//! let prediction = Simulation::new([2000.0, 1500.0, 1500.0, 1000.0]).run(&tournament);
+//! assert!(prediction.win(0) > prediction.win(3));
+//! ```
+use crate::standings::Standings;
+use crate::utils::Rng;
+use crate::{EntrantData, EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The rating used for entrants without a rating.
+pub const DEFAULT_RATING: f64 = 1500.0;
+
+/// The number of runs a [`Simulation`] does by default.
+pub const DEFAULT_ITERATIONS: usize = 1000;
+
+/// Returns the probability that an entrant with the rating `a` wins against an entrant with the
+/// rating `b`.
+#[inline]
+pub fn win_probability(a: f64, b: f64) -> f64 {
+    1.0 / (1.0 + 10_f64.powf((b - a) / 400.0))
+}
+
+/// A Monte Carlo simulation of the remaining matches of a tournament.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    ratings: Vec<f64>,
+    iterations: usize,
+    seed: u64,
+}
+
+impl Simulation {
+    /// Creates a new `Simulation` using the given `ratings`. The rating at index `i` belongs to
+    /// the entrant at index `i`. Entrants without a rating use [`DEFAULT_RATING`].
+    pub fn new<I>(ratings: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        Self {
+            ratings: ratings.into_iter().collect(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: 0,
+        }
+    }
+
+    /// Sets the number of simulated runs. Defaults to [`DEFAULT_ITERATIONS`].
+    #[inline]
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the seed of the random number generator. The same seed always produces the same
+    /// [`Prediction`] for the same tournament.
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Simulates the remaining matches of `tournament` and returns the resulting [`Prediction`].
+    /// The `tournament` itself is never modified.
+    pub fn run<S>(&self, tournament: &S) -> Prediction
+    where
+        S: System + Clone,
+        S::NodeData: Default,
+    {
+        let num_entrants = tournament.entrants().len();
+        let mut placements = vec![vec![0; num_entrants]; num_entrants];
+
+        let mut rng = Rng::new(self.seed);
+        for _ in 0..self.iterations {
+            let mut tournament = tournament.clone();
+            self.simulate(&mut tournament, &mut rng);
+
+            for (index, position) in positions(&tournament.standings()) {
+                placements[index][position] += 1;
+            }
+        }
+
+        Prediction {
+            iterations: self.iterations as u64,
+            placements,
+        }
+    }
+
+    /// Plays all matches of `tournament` until no more matches are playable.
+    fn simulate<S>(&self, tournament: &mut S, rng: &mut Rng)
+    where
+        S: System,
+        S::NodeData: Default,
+    {
+        loop {
+            let mut played = false;
+
+            for index in 0..tournament.matches().len() {
+                let match_ = &tournament.matches()[index];
+                if !match_.is_occupied() || match_.is_concluded() {
+                    continue;
+                }
+
+                let [a, b] = [0, 1].map(|i| self.rating(match_.entrants[i].unwrap_ref().index));
+
+                let winner = if rng.next_f64() < win_probability(a, b) {
+                    0
+                } else {
+                    1
+                };
+
+                tournament.update_match(index, |m, res| {
+                    if let EntrantSpot::Entrant(node) = &mut m.entrants[winner] {
+                        node.data.set_winner(true);
+                    }
+
+                    res.winner_default(&m.entrants[winner]);
+                    res.loser_default(&m.entrants[1 - winner]);
+                });
+
+                played = true;
+            }
+
+            if !played {
+                break;
+            }
+        }
+    }
+
+    #[inline]
+    fn rating(&self, index: usize) -> f64 {
+        self.ratings.get(index).copied().unwrap_or(DEFAULT_RATING)
+    }
+}
+
+/// Returns the final position of every entrant in the [`Standings`]. Entrants with equal values
+/// share the same position.
+fn positions(standings: &Standings) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+
+    let mut position = 0;
+    let mut prev = None;
+    for (index, entry) in standings.iter().enumerate() {
+        if prev != Some(&entry.values) {
+            position = index;
+        }
+
+        positions.push((entry.index, position));
+        prev = Some(&entry.values);
+    }
+
+    positions
+}
+
+/// The result of a [`Simulation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Prediction {
+    iterations: u64,
+    /// The number of runs in which an entrant (first index) finished at a position (second
+    /// index).
+    placements: Vec<Vec<u64>>,
+}
+
+impl Prediction {
+    /// Returns the number of simulated runs.
+    #[inline]
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// Returns the probability that the entrant at `index` finishes at `position`. The first
+    /// position is `0`.
+    pub fn placement(&self, index: usize, position: usize) -> f64 {
+        let count = self
+            .placements
+            .get(index)
+            .and_then(|placements| placements.get(position))
+            .copied()
+            .unwrap_or(0);
+
+        self.probability(count)
+    }
+
+    /// Returns the probabilities of the entrant at `index` for every position.
+    pub fn placements(&self, index: usize) -> Vec<f64> {
+        match self.placements.get(index) {
+            Some(placements) => placements.iter().map(|c| self.probability(*c)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the probability that the entrant at `index` finishes within the first `n`
+    /// positions, i.e. advances from the tournament if the best `n` entrants advance.
+    pub fn top(&self, index: usize, n: usize) -> f64 {
+        let count = match self.placements.get(index) {
+            Some(placements) => placements.iter().take(n).sum(),
+            None => 0,
+        };
+
+        self.probability(count)
+    }
+
+    /// Returns the probability that the entrant at `index` wins the tournament.
+    #[inline]
+    pub fn win(&self, index: usize) -> f64 {
+        self.top(index, 1)
+    }
+
+    #[inline]
+    fn probability(&self, count: u64) -> f64 {
+        if self.iterations == 0 {
+            0.0
+        } else {
+            count as f64 / self.iterations as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{win_probability, Simulation};
+    use crate::{EntrantScore, EntrantSpot, RoundRobin, SingleElimination, System};
+
+    #[test]
+    fn test_win_probability() {
+        assert_eq!(win_probability(1500.0, 1500.0), 0.5);
+        assert!((win_probability(1900.0, 1500.0) - 10.0 / 11.0).abs() < 1e-9);
+        assert!((win_probability(1500.0, 1900.0) - 1.0 / 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simulation_single_elimination() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+
+        let ratings = [
+            2400.0, 1500.0, 1500.0, 1500.0, 1500.0, 1500.0, 1500.0, 600.0,
+        ];
+        let prediction = Simulation::new(ratings).iterations(2000).run(&tournament);
+
+        assert_eq!(prediction.iterations(), 2000);
+
+        for index in 0..8 {
+            let sum: f64 = prediction.placements(index).iter().sum();
+            assert!((sum - 1.0).abs() < 1e-9);
+        }
+
+        let sum: f64 = (0..8).map(|index| prediction.win(index)).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+
+        assert!(prediction.win(0) > 0.9);
+        assert!(prediction.win(7) < 0.01);
+        assert_eq!(prediction.top(0, 8), 1.0);
+
+        // Losers of the semi finals share the third position.
+        assert_eq!(prediction.placement(0, 3), 0.0);
+    }
+
+    #[test]
+    fn test_simulation_concluded() {
+        let mut tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..2);
+        tournament.update_match(0, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[1] {
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[1]);
+            res.loser_default(&m.entrants[0]);
+        });
+
+        let prediction = Simulation::new([3000.0, 0.0]).run(&tournament);
+        assert_eq!(prediction.win(0), 0.0);
+        assert_eq!(prediction.win(1), 1.0);
+        assert_eq!(prediction.placement(0, 1), 1.0);
+    }
+
+    #[test]
+    fn test_simulation_seed() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..5);
+
+        let simulation = Simulation::new([1500.0, 1600.0, 1400.0]).iterations(100);
+        assert_eq!(
+            simulation.clone().seed(7).run(&tournament),
+            simulation.seed(7).run(&tournament)
+        );
+    }
+
+    #[test]
+    fn test_simulation_no_iterations() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+
+        let prediction = Simulation::new([]).iterations(0).run(&tournament);
+        assert_eq!(prediction.win(0), 0.0);
+    }
+}
@@ -0,0 +1,445 @@
+//! # Progress and Estimation
+//!
+//! An [`Estimator`] computes how far a [`System`] has progressed and estimates when every
+//! remaining match starts and ends. Estimates are based on the dependency graph of the matches
+//! (their [`predecessors`]) and the average duration of the already concluded matches.
+//!
+//! The estimation assumes that a match starts as soon as all of its predecessors are concluded
+//! and both entrants finished their previous match. It does not consider the number of stations
+//! matches can be played on, use a [`Scheduler`] for that.
+//!
+//! All times are plain seconds. The reference point is up to the caller, usually a unix
+//! timestamp.
+//!
+//! [`System`]: crate::System
+//! [`predecessors`]: crate::System::predecessors
+//! [`Scheduler`]: crate::schedule::Scheduler
+use std::collections::HashMap;
+
+use crate::schedule::topological_order;
+use crate::{EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The estimated start and end time of a match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Estimate {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// The progress of a [`System`] and the [`Estimate`]s of all remaining matches.
+///
+/// [`System`]: crate::System
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Progress {
+    completed: usize,
+    total: usize,
+    match_duration: u64,
+    estimates: Vec<Option<Estimate>>,
+    critical_path: Vec<usize>,
+}
+
+impl Progress {
+    /// Returns the number of concluded matches.
+    #[inline]
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// Returns the number of matches that are played in total. Matches that are never played
+    /// (e.g. byes) are not included.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the percentage of concluded matches in the range `0.0..=100.0`.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.completed as f64 / self.total as f64 * 100.0
+        }
+    }
+
+    /// Returns the match duration in seconds the [`Estimate`]s are based on.
+    #[inline]
+    pub fn match_duration(&self) -> u64 {
+        self.match_duration
+    }
+
+    /// Returns the [`Estimate`] of the match with the given `index`. Returns `None` if the match
+    /// is concluded, is never played or `index` is out-of-bounds.
+    #[inline]
+    pub fn estimate(&self, index: usize) -> Option<Estimate> {
+        self.estimates.get(index).copied().flatten()
+    }
+
+    /// Returns an iterator over the [`Estimate`]s of all matches, in match order.
+    #[inline]
+    pub fn estimates(&self) -> impl Iterator<Item = Option<Estimate>> + '_ {
+        self.estimates.iter().copied()
+    }
+
+    /// Returns the indices of all remaining matches on the critical path in the order they are
+    /// played. The critical path is the longest chain of dependent matches and determines when
+    /// the last match ends.
+    #[inline]
+    pub fn critical_path(&self) -> &[usize] {
+        &self.critical_path
+    }
+
+    /// Returns the estimated end time of the last match. Returns `None` if all matches are
+    /// concluded.
+    pub fn end(&self) -> Option<u64> {
+        self.estimates.iter().flatten().map(|e| e.end).max()
+    }
+}
+
+/// Estimates the [`Progress`] of a [`System`].
+///
+/// # Examples
+///
+/// ```
+/// # use dynamic_tournament_core::{EntrantScore, SingleElimination};
+/// # use dynamic_tournament_core::progress::Estimator;
+/// let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+///
+/// // It is currently 1000, matches take 30 minutes if no match was observed yet.
+/// let progress = Estimator::new(1000, 1800).estimate(&tournament);
+///
+/// assert_eq!(progress.percentage(), 0.0);
+/// assert_eq!(progress.estimate(0).unwrap().start, 1000);
+/// assert_eq!(progress.estimate(2).unwrap().start, 2800);
+/// assert_eq!(progress.end(), Some(4600));
+/// ```
+///
+/// [`System`]: crate::System
+#[derive(Clone, Debug)]
+pub struct Estimator {
+    now: u64,
+    match_duration: u64,
+    finished: HashMap<usize, u64>,
+}
+
+impl Estimator {
+    /// Creates a new `Estimator`. No match is estimated to start before `now`. Every match is
+    /// expected to take `match_duration` seconds until durations could be observed from
+    /// [`finished`] matches.
+    ///
+    /// [`finished`]: Self::finished
+    pub fn new(now: u64, match_duration: u64) -> Self {
+        Self {
+            now,
+            match_duration,
+            finished: HashMap::new(),
+        }
+    }
+
+    /// Sets the time at which the match with the given `index` was concluded.
+    pub fn finished(mut self, index: usize, time: u64) -> Self {
+        self.finished.insert(index, time);
+        self
+    }
+
+    /// Returns the average duration of all concluded matches in `system`. Returns `None` if no
+    /// duration could be observed.
+    ///
+    /// The duration of a match is the time between its conclusion and the latest conclusion of
+    /// a predecessor or a previous match of either entrant. Matches without such a previous
+    /// match (e.g. in the first round) are not considered.
+    pub fn observed_duration<S>(&self, system: &S) -> Option<u64>
+    where
+        S: System,
+    {
+        let matches = system.matches();
+
+        let mut sum = 0;
+        let mut count = 0;
+        for (index, end) in &self.finished {
+            let Some(match_) = matches.get(*index) else {
+                continue;
+            };
+
+            if !match_.is_concluded() {
+                continue;
+            }
+
+            let predecessors = system.predecessors(*index);
+
+            let start = self
+                .finished
+                .iter()
+                .filter(|(other, time)| {
+                    if *time >= end {
+                        return false;
+                    }
+
+                    if predecessors.contains(other) {
+                        return true;
+                    }
+
+                    let Some(other) = matches.get(**other) else {
+                        return false;
+                    };
+
+                    other.entrants.iter().any(|spot| match spot {
+                        EntrantSpot::Entrant(node) => match_.entrants.iter().any(|s| match s {
+                            EntrantSpot::Entrant(n) => n.index == node.index,
+                            _ => false,
+                        }),
+                        _ => false,
+                    })
+                })
+                .map(|(_, time)| *time)
+                .max();
+
+            if let Some(start) = start {
+                sum += end - start;
+                count += 1;
+            }
+        }
+
+        sum.checked_div(count)
+    }
+
+    /// Creates the [`Progress`] of `system`.
+    pub fn estimate<S>(&self, system: &S) -> Progress
+    where
+        S: System,
+    {
+        let num_matches = system.matches().len();
+        let match_duration = self
+            .observed_duration(system)
+            .unwrap_or(self.match_duration);
+
+        let predecessors: Vec<Vec<usize>> = (0..num_matches)
+            .map(|index| {
+                system
+                    .predecessors(index)
+                    .into_iter()
+                    .filter(|i| *i < num_matches)
+                    .collect()
+            })
+            .collect();
+
+        let mut completed = 0;
+        let mut total = 0;
+
+        // The time at which the result of a match is known.
+        let mut ready = vec![self.now; num_matches];
+        // The time at which an entrant is ready and the match it played last.
+        let mut entrants: HashMap<usize, (u64, usize)> = HashMap::new();
+        // The match that delayed the start of a match.
+        let mut delayed_by = vec![None; num_matches];
+        let mut estimates = vec![None; num_matches];
+
+        for index in topological_order(&predecessors) {
+            let match_ = &system.matches()[index];
+
+            if match_.is_concluded() {
+                completed += 1;
+                total += 1;
+
+                ready[index] = self.finished.get(&index).copied().unwrap_or(self.now);
+                continue;
+            }
+
+            let mut start = self.now;
+            for pred in &predecessors[index] {
+                if ready[*pred] > start {
+                    start = ready[*pred];
+                    delayed_by[index] = Some(*pred);
+                }
+            }
+
+            // Matches that are never played pass on the time of their predecessors.
+            if match_.entrants.iter().any(EntrantSpot::is_empty) {
+                ready[index] = start;
+                continue;
+            }
+
+            total += 1;
+
+            for spot in &match_.entrants {
+                if let EntrantSpot::Entrant(node) = spot {
+                    if let Some((time, prev)) = entrants.get(&node.index) {
+                        if *time > start {
+                            start = *time;
+                            delayed_by[index] = Some(*prev);
+                        }
+                    }
+                }
+            }
+
+            let end = start + match_duration;
+            for spot in &match_.entrants {
+                if let EntrantSpot::Entrant(node) = spot {
+                    entrants.insert(node.index, (end, index));
+                }
+            }
+
+            ready[index] = end;
+            estimates[index] = Some(Estimate { start, end });
+        }
+
+        // Walk back from the last match through all matches that delayed it.
+        let mut critical_path = Vec::new();
+        let last = estimates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, e)| e.map(|e| (index, e.end)))
+            .fold(
+                None,
+                |last: Option<(usize, u64)>, (index, end)| match last {
+                    Some((_, max)) if max >= end => last,
+                    _ => Some((index, end)),
+                },
+            );
+
+        let mut next = last.map(|(index, _)| index);
+        while let Some(index) = next {
+            if estimates[index].is_some() {
+                critical_path.push(index);
+            }
+
+            next = delayed_by[index];
+        }
+        critical_path.reverse();
+
+        Progress {
+            completed,
+            total,
+            match_duration,
+            estimates,
+            critical_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Estimate, Estimator};
+    use crate::{EntrantScore, EntrantSpot, RoundRobin, SingleElimination, System};
+
+    fn win<S>(tournament: &mut S, index: usize)
+    where
+        S: System<NodeData = EntrantScore<u32>>,
+    {
+        tournament.update_match(index, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[0] {
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[0]);
+            res.loser_default(&m.entrants[1]);
+        });
+    }
+
+    #[test]
+    fn test_progress_single_elimination() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+
+        let progress = Estimator::new(100, 10).estimate(&tournament);
+        assert_eq!(progress.completed(), 0);
+        assert_eq!(progress.total(), 7);
+        assert_eq!(progress.percentage(), 0.0);
+        assert_eq!(progress.match_duration(), 10);
+
+        for index in 0..4 {
+            assert_eq!(
+                progress.estimate(index),
+                Some(Estimate {
+                    start: 100,
+                    end: 110
+                })
+            );
+        }
+        assert_eq!(progress.estimate(4).unwrap().start, 110);
+        assert_eq!(progress.estimate(6).unwrap().start, 120);
+        assert_eq!(progress.end(), Some(130));
+        assert_eq!(progress.critical_path(), [0, 4, 6]);
+    }
+
+    #[test]
+    fn test_progress_observed_duration() {
+        let mut tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        win(&mut tournament, 0);
+        win(&mut tournament, 1);
+        win(&mut tournament, 4);
+
+        let estimator = Estimator::new(200, 10)
+            .finished(0, 100)
+            .finished(1, 110)
+            .finished(4, 150);
+        assert_eq!(estimator.observed_duration(&tournament), Some(40));
+
+        let progress = estimator.estimate(&tournament);
+        assert_eq!(progress.completed(), 3);
+        assert_eq!(progress.match_duration(), 40);
+        assert_eq!(progress.estimate(0), None);
+        assert_eq!(progress.estimate(4), None);
+        assert_eq!(
+            progress.estimate(2),
+            Some(Estimate {
+                start: 200,
+                end: 240
+            })
+        );
+        assert_eq!(progress.estimate(5).unwrap().start, 240);
+        assert_eq!(progress.estimate(6).unwrap().start, 280);
+        assert_eq!(progress.critical_path(), [2, 5, 6]);
+
+        // Without any finish times no duration is observed.
+        let progress = Estimator::new(200, 10).estimate(&tournament);
+        assert_eq!(progress.match_duration(), 10);
+    }
+
+    #[test]
+    fn test_progress_byes() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..3);
+
+        let progress = Estimator::new(0, 10).estimate(&tournament);
+        assert_eq!(progress.total(), 2);
+
+        for (index, estimate) in progress.estimates().enumerate() {
+            let is_bye = tournament.matches()[index]
+                .entrants
+                .iter()
+                .any(EntrantSpot::is_empty);
+
+            assert_eq!(estimate.is_none(), is_bye);
+        }
+
+        assert_eq!(progress.end(), Some(20));
+    }
+
+    #[test]
+    fn test_progress_round_robin() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+
+        // Matches without predecessors still wait for the previous match of their entrants.
+        let progress = Estimator::new(0, 10).estimate(&tournament);
+        assert_eq!(progress.total(), 6);
+        assert_eq!(progress.estimate(0).unwrap().start, 0);
+        assert_eq!(progress.estimate(1).unwrap().start, 0);
+        assert_eq!(progress.estimate(2).unwrap().start, 10);
+        assert_eq!(progress.end(), Some(30));
+        assert_eq!(progress.critical_path().len(), 3);
+    }
+
+    #[test]
+    fn test_progress_finished() {
+        let mut tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..2);
+        win(&mut tournament, 0);
+
+        let progress = Estimator::new(0, 10).estimate(&tournament);
+        assert_eq!(progress.percentage(), 100.0);
+        assert_eq!(progress.end(), None);
+        assert!(progress.critical_path().is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+//! Rating systems used to update the ratings of entrants from match results.
+//!
+//! Two systems are provided:
+//! - [`Elo`]: The classic Elo system. Only uses [`Rating::rating`].
+//! - [`Glicko2`]: The Glicko-2 system. Additionally tracks the [`deviation`] and the
+//!   [`volatility`] of every rating.
+//!
+//! [`deviation`]: Rating::deviation
+//! [`volatility`]: Rating::volatility
+use std::f64::consts::PI;
+
+use crate::prediction::{win_probability, DEFAULT_RATING};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The deviation of an unrated entrant.
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+
+/// The volatility of an unrated entrant.
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+
+/// The factor used to convert between the Glicko and the Glicko-2 scale.
+const GLICKO2_SCALE: f64 = 173.7178;
+
+/// The convergence tolerance used when computing the new volatility.
+const GLICKO2_EPSILON: f64 = 0.000001;
+
+/// The rating of a single entrant.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rating {
+    pub rating: f64,
+    /// The rating deviation. Only used by [`Glicko2`].
+    pub deviation: f64,
+    /// The rating volatility. Only used by [`Glicko2`].
+    pub volatility: f64,
+}
+
+impl Rating {
+    /// Creates a new `Rating` with the given `rating` and the default deviation and volatility.
+    #[inline]
+    pub const fn new(rating: f64) -> Self {
+        Self {
+            rating,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+
+    /// Combines the ratings of multiple entrants (e.g. the players of a team) into a single
+    /// `Rating`. Returns the default `Rating` if `ratings` is empty.
+    pub fn combine<I>(ratings: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut count = 0;
+        let mut sum = Self {
+            rating: 0.0,
+            deviation: 0.0,
+            volatility: 0.0,
+        };
+
+        for rating in ratings {
+            count += 1;
+            sum.rating += rating.rating;
+            // Deviations are combined as the root mean square.
+            sum.deviation += rating.deviation * rating.deviation;
+            sum.volatility += rating.volatility;
+        }
+
+        if count == 0 {
+            return Self::default();
+        }
+
+        let count = count as f64;
+        Self {
+            rating: sum.rating / count,
+            deviation: (sum.deviation / count).sqrt(),
+            volatility: sum.volatility / count,
+        }
+    }
+}
+
+impl Default for Rating {
+    #[inline]
+    fn default() -> Self {
+        Self::new(DEFAULT_RATING)
+    }
+}
+
+/// The outcome of a single match against an opponent.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Outcome {
+    /// The rating of the opponent.
+    pub opponent: Rating,
+    /// The score of the match: `1.0` for a win, `0.5` for a draw and `0.0` for a loss.
+    pub score: f64,
+}
+
+impl Outcome {
+    #[inline]
+    pub const fn win(opponent: Rating) -> Self {
+        Self {
+            opponent,
+            score: 1.0,
+        }
+    }
+
+    #[inline]
+    pub const fn loss(opponent: Rating) -> Self {
+        Self {
+            opponent,
+            score: 0.0,
+        }
+    }
+}
+
+/// The Elo rating system.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Elo {
+    /// The maximum rating change of a single match.
+    pub k: f64,
+}
+
+impl Elo {
+    #[inline]
+    pub const fn new(k: f64) -> Self {
+        Self { k }
+    }
+
+    /// Returns the new [`Rating`] after playing all matches in `outcomes`. The deviation and
+    /// volatility are not changed.
+    pub fn rate(&self, rating: Rating, outcomes: &[Outcome]) -> Rating {
+        let change: f64 = outcomes
+            .iter()
+            .map(|outcome| {
+                let expected = win_probability(rating.rating, outcome.opponent.rating);
+                self.k * (outcome.score - expected)
+            })
+            .sum();
+
+        Rating {
+            rating: rating.rating + change,
+            ..rating
+        }
+    }
+}
+
+impl Default for Elo {
+    #[inline]
+    fn default() -> Self {
+        Self::new(32.0)
+    }
+}
+
+/// The Glicko-2 rating system.
+///
+/// See <http://www.glicko.net/glicko/glicko2.pdf> for a description of the algorithm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Glicko2 {
+    /// The system constant which constrains the change in volatility over time.
+    pub tau: f64,
+}
+
+impl Glicko2 {
+    #[inline]
+    pub const fn new(tau: f64) -> Self {
+        Self { tau }
+    }
+
+    /// Returns the new [`Rating`] after playing all matches in `outcomes` in a single rating
+    /// period. If `outcomes` is empty only the deviation increases.
+    pub fn rate(&self, rating: Rating, outcomes: &[Outcome]) -> Rating {
+        let mu = (rating.rating - DEFAULT_RATING) / GLICKO2_SCALE;
+        let phi = rating.deviation / GLICKO2_SCALE;
+        let sigma = rating.volatility;
+
+        if outcomes.is_empty() {
+            let phi = (phi * phi + sigma * sigma).sqrt();
+
+            return Rating {
+                deviation: phi * GLICKO2_SCALE,
+                ..rating
+            };
+        }
+
+        // The estimated variance and the sum of all weighted score differences.
+        let mut v_inv = 0.0;
+        let mut diff = 0.0;
+        for outcome in outcomes {
+            let mu_j = (outcome.opponent.rating - DEFAULT_RATING) / GLICKO2_SCALE;
+            let phi_j = outcome.opponent.deviation / GLICKO2_SCALE;
+
+            let g = g(phi_j);
+            let e = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+
+            v_inv += g * g * e * (1.0 - e);
+            diff += g * (outcome.score - e);
+        }
+
+        let v = 1.0 / v_inv;
+        let delta = v * diff;
+
+        let sigma = self.volatility(phi, sigma, v, delta);
+
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+        let mu = mu + phi * phi * diff;
+
+        Rating {
+            rating: mu * GLICKO2_SCALE + DEFAULT_RATING,
+            deviation: phi * GLICKO2_SCALE,
+            volatility: sigma,
+        }
+    }
+
+    /// Computes the new volatility using the Illinois algorithm.
+    fn volatility(&self, phi: f64, sigma: f64, v: f64, delta: f64) -> f64 {
+        let a = (sigma * sigma).ln();
+        let tau = self.tau;
+
+        let f = |x: f64| {
+            let ex = x.exp();
+            let denom = phi * phi + v + ex;
+
+            ex * (delta * delta - phi * phi - v - ex) / (2.0 * denom * denom)
+                - (x - a) / (tau * tau)
+        };
+
+        let mut lower = a;
+        let mut upper = if delta * delta > phi * phi + v {
+            (delta * delta - phi * phi - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * tau) < 0.0 {
+                k += 1.0;
+            }
+
+            a - k * tau
+        };
+
+        let mut f_lower = f(lower);
+        let mut f_upper = f(upper);
+
+        while (upper - lower).abs() > GLICKO2_EPSILON {
+            let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+            let f_c = f(c);
+
+            if f_c * f_upper <= 0.0 {
+                lower = upper;
+                f_lower = f_upper;
+            } else {
+                f_lower /= 2.0;
+            }
+
+            upper = c;
+            f_upper = f_c;
+        }
+
+        (lower / 2.0).exp()
+    }
+}
+
+impl Default for Glicko2 {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+#[inline]
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Elo, Glicko2, Outcome, Rating};
+
+    fn assert_close(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() < epsilon, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_elo() {
+        let elo = Elo::new(32.0);
+
+        let rating = elo.rate(Rating::new(1500.0), &[Outcome::win(Rating::new(1500.0))]);
+        assert_close(rating.rating, 1516.0, 1e-9);
+
+        let rating = elo.rate(Rating::new(1500.0), &[Outcome::loss(Rating::new(1500.0))]);
+        assert_close(rating.rating, 1484.0, 1e-9);
+
+        // Both players together never gain or lose points.
+        let a = Rating::new(1700.0);
+        let b = Rating::new(1400.0);
+        let new_a = elo.rate(a, &[Outcome::loss(b)]);
+        let new_b = elo.rate(b, &[Outcome::win(a)]);
+        assert_close(new_a.rating + new_b.rating, a.rating + b.rating, 1e-9);
+        assert!(new_b.rating - b.rating > 16.0);
+    }
+
+    #[test]
+    fn test_glicko2() {
+        // Example from http://www.glicko.net/glicko/glicko2.pdf
+        let rating = Rating {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+
+        let outcomes = [
+            Outcome::win(Rating {
+                rating: 1400.0,
+                deviation: 30.0,
+                volatility: 0.06,
+            }),
+            Outcome::loss(Rating {
+                rating: 1550.0,
+                deviation: 100.0,
+                volatility: 0.06,
+            }),
+            Outcome::loss(Rating {
+                rating: 1700.0,
+                deviation: 300.0,
+                volatility: 0.06,
+            }),
+        ];
+
+        let rating = Glicko2::new(0.5).rate(rating, &outcomes);
+        assert_close(rating.rating, 1464.06, 0.01);
+        assert_close(rating.deviation, 151.52, 0.01);
+        assert_close(rating.volatility, 0.05999, 0.00001);
+    }
+
+    #[test]
+    fn test_glicko2_no_outcomes() {
+        let rating = Rating {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+
+        let new = Glicko2::default().rate(rating, &[]);
+        assert_eq!(new.rating, rating.rating);
+        assert!(new.deviation > rating.deviation);
+    }
+
+    #[test]
+    fn test_rating_combine() {
+        let rating = Rating::combine([
+            Rating {
+                rating: 1000.0,
+                deviation: 30.0,
+                volatility: 0.05,
+            },
+            Rating {
+                rating: 2000.0,
+                deviation: 40.0,
+                volatility: 0.07,
+            },
+        ]);
+
+        assert_close(rating.rating, 1500.0, 1e-9);
+        assert_close(rating.deviation, 1250_f64.sqrt(), 1e-9);
+        assert_close(rating.volatility, 0.06, 1e-9);
+
+        assert_eq!(Rating::combine([]), Rating::default());
+    }
+}
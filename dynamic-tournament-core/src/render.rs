@@ -19,6 +19,7 @@
 //! almost never required to rerender a tournament when a match changes. Instead it is possible
 //! to only rerender all matches in place.
 //!
+use crate::sides::{Side, Sides};
 use crate::System;
 
 use std::borrow::Cow;
@@ -130,6 +131,34 @@ where
             _ => panic!("called `unwrap_match`on an invalid ElementInner value"),
         }
     }
+
+    /// Sets the sides of all [`Match`]es in this `Element` and all its children.
+    pub(crate) fn with_sides(self, sides: &Sides) -> Self {
+        match self {
+            Self::Row(mut row) => {
+                row.children = row
+                    .children
+                    .map(|elem| elem.with_sides(sides))
+                    .collect::<Vec<_>>()
+                    .into_iter();
+
+                Self::Row(row)
+            }
+            Self::Column(mut col) => {
+                col.children = col
+                    .children
+                    .map(|elem| elem.with_sides(sides))
+                    .collect::<Vec<_>>()
+                    .into_iter();
+
+                Self::Column(col)
+            }
+            Self::Match(mut m) => {
+                m.sides = sides.get(m.index);
+                Self::Match(m)
+            }
+        }
+    }
 }
 
 impl<'a, T> From<Row<'a, T>> for Element<'a, T>
@@ -276,6 +305,7 @@ where
     pub position: Option<Position>,
     pub(crate) index: usize,
     pub(crate) predecessors: Vec<Predecessor>,
+    pub(crate) sides: Option<[Side; 2]>,
     pub(crate) _marker: PhantomData<&'a T>,
 }
 
@@ -294,6 +324,13 @@ where
     pub fn predecessors(&self) -> &[Predecessor] {
         &self.predecessors
     }
+
+    /// Returns the [`Side`]s of both entrants of this `Match`. Returns `None` if the match has no
+    /// sides assigned yet.
+    #[inline]
+    pub fn sides(&self) -> Option<[Side; 2]> {
+        self.sides
+    }
 }
 
 /// A predecessor hint of a match.
@@ -1,6 +1,8 @@
 use std::borrow::Borrow;
 
 use crate::render::{Column, Element, Position, RenderState, Row};
+use crate::rounds::{self, Rounds};
+use crate::sides::SideRule;
 use crate::{
     EntrantData, EntrantSpot, Entrants, Error, Match, MatchResult, Matches, NextMatches, Node,
     Result, System,
@@ -239,7 +241,8 @@ where
     }
 
     fn next_matches(&self, _: usize) -> NextMatches {
-        unimplemented!()
+        // Matches in a round robin tournament never lead into other matches.
+        NextMatches::default()
     }
 
     fn update_match<F>(&mut self, index: usize, f: F)
@@ -255,6 +258,14 @@ where
         f(match_, &mut res);
     }
 
+    fn rounds(&self) -> Rounds {
+        rounds::fixed_size(self.matches.len(), self.entrants_even() / 2)
+    }
+
+    fn side_rule(&self) -> SideRule {
+        SideRule::Balanced
+    }
+
     fn start_render(&self) -> RenderState<'_, Self> {
         let mut rounds = Vec::new();
 
@@ -270,6 +281,7 @@ where
                 round.push(Element::new(crate::render::Match {
                     index,
                     predecessors: vec![],
+                    sides: None,
                     _marker: std::marker::PhantomData,
                     label: None,
                     position: None,
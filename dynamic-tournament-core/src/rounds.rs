@@ -0,0 +1,354 @@
+//! Rounds of a tournament.
+//!
+//! Every [`System`] groups its matches into [`Round`]s. A round has a human-readable label (e.g.
+//! `Upper Bracket Round 2` or `Grand Final`) and a short code (e.g. `UB-R2`). Every match gets a
+//! stable match code from the code of its round and its position in the round, e.g. `UB-R2-M3`
+//! for the third match in the second round of the upper bracket.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::{DoubleElimination, EntrantScore, System};
+//! let tournament = DoubleElimination::<u32, EntrantScore<u32>>::new(0..8);
+//!
+//! let rounds = tournament.rounds();
+//! assert_eq!(rounds.code(0).unwrap(), "UB-R1-M1");
+//! assert_eq!(rounds.code(5).unwrap(), "UB-R2-M2");
+//! assert_eq!(rounds.code(13).unwrap(), "GF-M1");
+//! assert_eq!(rounds.find("LB-R1-M2"), Some(8));
+//! ```
+//!
+//! [`System`]: crate::System
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::System;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single round of a tournament.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Round {
+    /// A human-readable name of the round, e.g. `Upper Bracket Round 2`.
+    pub label: Cow<'static, str>,
+    /// A short code of the round, e.g. `UB-R2`.
+    pub code: Cow<'static, str>,
+    /// The indices of all matches in the round, in order.
+    pub matches: Vec<usize>,
+}
+
+impl Round {
+    /// Creates a new `Round`.
+    pub fn new<L, C, I>(label: L, code: C, matches: I) -> Self
+    where
+        L: Into<Cow<'static, str>>,
+        C: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = usize>,
+    {
+        Self {
+            label: label.into(),
+            code: code.into(),
+            matches: matches.into_iter().collect(),
+        }
+    }
+}
+
+/// All [`Round`]s of a tournament in the order they are played.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Rounds {
+    rounds: Vec<Round>,
+}
+
+impl Rounds {
+    /// Creates a new `Rounds` from a list of [`Round`]s. Rounds without matches are removed.
+    pub fn new(rounds: Vec<Round>) -> Self {
+        Self {
+            rounds: rounds
+                .into_iter()
+                .filter(|round| !round.matches.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Returns the number of rounds.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns `true` if there are no rounds.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rounds.is_empty()
+    }
+
+    /// Returns the [`Round`] at the given position. Returns `None` if `round` is out-of-bounds.
+    #[inline]
+    pub fn get(&self, round: usize) -> Option<&Round> {
+        self.rounds.get(round)
+    }
+
+    /// Returns an iterator over all [`Round`]s in order.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, Round> {
+        self.rounds.iter()
+    }
+
+    /// Returns the position of the round containing the match with the given `index` and the
+    /// position of the match within the round.
+    pub fn position(&self, index: usize) -> Option<(usize, usize)> {
+        self.rounds.iter().enumerate().find_map(|(round, r)| {
+            r.matches
+                .iter()
+                .position(|i| *i == index)
+                .map(|pos| (round, pos))
+        })
+    }
+
+    /// Returns the [`Round`] containing the match with the given `index`.
+    pub fn round_of(&self, index: usize) -> Option<&Round> {
+        let (round, _) = self.position(index)?;
+        self.rounds.get(round)
+    }
+
+    /// Returns the match code of the match with the given `index`, e.g. `UB-R2-M3`. Returns
+    /// `None` if no round contains the match.
+    pub fn code(&self, index: usize) -> Option<String> {
+        let (round, pos) = self.position(index)?;
+        Some(format!("{}-M{}", self.rounds[round].code, pos + 1))
+    }
+
+    /// Returns the index of the match with the given match `code`. The code is compared
+    /// case-insensitively.
+    pub fn find(&self, code: &str) -> Option<usize> {
+        let (round, pos) = code.rsplit_once('-')?;
+        let pos: usize = pos.strip_prefix(['M', 'm'])?.parse().ok()?;
+
+        self.rounds
+            .iter()
+            .find(|r| r.code.eq_ignore_ascii_case(round))?
+            .matches
+            .get(pos.checked_sub(1)?)
+            .copied()
+    }
+}
+
+impl<'a> IntoIterator for &'a Rounds {
+    type Item = &'a Round;
+    type IntoIter = std::slice::Iter<'a, Round>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Groups the matches of `tournament` into rounds using their [`predecessors`]. A match without
+/// predecessors is in the first round, every other match is in the round after its latest
+/// predecessor.
+///
+/// [`predecessors`]: System::predecessors
+pub(crate) fn by_depth<S>(tournament: &S) -> Rounds
+where
+    S: System,
+{
+    fn depth<S: System>(tournament: &S, index: usize, depths: &mut [Option<usize>]) -> usize {
+        if let Some(depth) = depths[index] {
+            return depth;
+        }
+
+        // Guard against cycles.
+        depths[index] = Some(0);
+
+        let len = depths.len();
+        let depth = tournament
+            .predecessors(index)
+            .into_iter()
+            .filter(|pred| *pred < len && *pred != index)
+            .map(|pred| depth(tournament, pred, depths) + 1)
+            .max()
+            .unwrap_or(0);
+
+        depths[index] = Some(depth);
+        depth
+    }
+
+    let len = tournament.matches().len();
+    let mut depths = vec![None; len];
+
+    let mut rounds: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..len {
+        let depth = depth(tournament, index, &mut depths);
+        rounds.entry(depth).or_default().push(index);
+    }
+
+    Rounds::new(
+        rounds
+            .into_values()
+            .enumerate()
+            .map(|(round, matches)| {
+                Round::new(
+                    format!("Round {}", round + 1),
+                    format!("R{}", round + 1),
+                    matches,
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Splits `len` matches into rounds of `matches_per_round` matches each, labeled `Round 1`,
+/// `Round 2`, etc.
+pub(crate) fn fixed_size(len: usize, matches_per_round: usize) -> Rounds {
+    if matches_per_round == 0 {
+        return Rounds::default();
+    }
+
+    Rounds::new(
+        (0..len)
+            .step_by(matches_per_round)
+            .enumerate()
+            .map(|(round, start)| {
+                Round::new(
+                    format!("Round {}", round + 1),
+                    format!("R{}", round + 1),
+                    start..(start + matches_per_round).min(len),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{by_depth, Round, Rounds};
+    use crate::options::TournamentOptionValues;
+    use crate::tournament::{Tournament, TournamentKind};
+    use crate::{option_values, DoubleElimination, EntrantScore, RoundRobin, SingleElimination};
+    use crate::{Swiss, System};
+
+    fn labels(rounds: &Rounds) -> Vec<&str> {
+        rounds.iter().map(|round| round.label.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_rounds_single_elimination() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        let rounds = tournament.rounds();
+
+        assert_eq!(labels(&rounds), ["Round 1", "Round 2", "Final"]);
+        assert_eq!(rounds.get(0).unwrap().matches, [0, 1, 2, 3]);
+        assert_eq!(rounds.code(3).unwrap(), "R1-M4");
+        assert_eq!(rounds.code(5).unwrap(), "R2-M2");
+        assert_eq!(rounds.code(6).unwrap(), "F-M1");
+        assert_eq!(rounds.code(7), None);
+
+        let options = option_values!("third_place_match" => true);
+        let tournament =
+            SingleElimination::<u32, EntrantScore<u32>>::new_with_options(0..8, options);
+        let rounds = tournament.rounds();
+
+        assert_eq!(
+            labels(&rounds),
+            ["Round 1", "Round 2", "Final", "Third Place Match"]
+        );
+        assert_eq!(rounds.code(6).unwrap(), "F-M1");
+        assert_eq!(rounds.code(7).unwrap(), "3P-M1");
+
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..2);
+        assert_eq!(labels(&tournament.rounds()), ["Final"]);
+
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..0);
+        assert!(tournament.rounds().is_empty());
+    }
+
+    #[test]
+    fn test_rounds_double_elimination() {
+        let tournament = DoubleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        let rounds = tournament.rounds();
+
+        assert_eq!(
+            labels(&rounds),
+            [
+                "Upper Bracket Round 1",
+                "Upper Bracket Round 2",
+                "Upper Bracket Round 3",
+                "Lower Bracket Round 1",
+                "Lower Bracket Round 2",
+                "Lower Bracket Round 3",
+                "Lower Bracket Round 4",
+                "Grand Final",
+            ]
+        );
+
+        // Every match is in exactly one round.
+        let mut indices: Vec<usize> = rounds.iter().flat_map(|r| r.matches.clone()).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..tournament.matches().len()).collect::<Vec<_>>());
+
+        assert_eq!(rounds.code(7).unwrap(), "LB-R1-M1");
+        assert_eq!(rounds.code(12).unwrap(), "LB-R4-M1");
+        assert_eq!(rounds.code(13).unwrap(), "GF-M1");
+
+        let tournament = DoubleElimination::<u32, EntrantScore<u32>>::new(0..2);
+        assert_eq!(labels(&tournament.rounds()), ["Grand Final"]);
+    }
+
+    #[test]
+    fn test_rounds_round_robin_swiss() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..5);
+        let rounds = tournament.rounds();
+        assert_eq!(rounds.len(), 4);
+        assert_eq!(rounds.get(1).unwrap().matches, [3, 4, 5]);
+        assert_eq!(rounds.code(4).unwrap(), "R2-M2");
+
+        let tournament = Swiss::<u32, EntrantScore<u32>>::new(0..8);
+        let rounds = tournament.rounds();
+        assert_eq!(rounds.get(0).unwrap().matches, [0, 1, 2, 3]);
+        assert_eq!(rounds.code(4).unwrap(), "R2-M1");
+    }
+
+    #[test]
+    fn test_rounds_by_depth() {
+        // Without labels the rounds of an elimination bracket follow from the predecessors.
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        let rounds = by_depth(&tournament);
+
+        let matches: Vec<_> = rounds.iter().map(|r| r.matches.clone()).collect();
+        assert_eq!(matches, [vec![0, 1, 2, 3], vec![4, 5], vec![6]]);
+        assert_eq!(rounds.code(6).unwrap(), "R3-M1");
+    }
+
+    #[test]
+    fn test_rounds_find() {
+        let rounds = Rounds::new(vec![
+            Round::new("Round 1", "R1", [0, 1]),
+            Round::new("Empty", "E", []),
+            Round::new("Final", "F", [2]),
+        ]);
+
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds.find("R1-M2"), Some(1));
+        assert_eq!(rounds.find("f-m1"), Some(2));
+        assert_eq!(rounds.find("F-M0"), None);
+        assert_eq!(rounds.find("F-M2"), None);
+        assert_eq!(rounds.find("E-M1"), None);
+        assert_eq!(rounds.find("R1"), None);
+
+        let mut tournament: Tournament<u32, EntrantScore<u32>> = Tournament::new(
+            TournamentKind::DoubleElimination,
+            TournamentOptionValues::default(),
+        );
+        tournament.extend(0..4);
+
+        let rounds = tournament.rounds();
+        for index in 0..tournament.matches().len() {
+            let code = rounds.code(index).unwrap();
+            assert_eq!(rounds.find(&code), Some(index));
+        }
+    }
+}
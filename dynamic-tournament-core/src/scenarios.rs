@@ -0,0 +1,434 @@
+//! Clinch and elimination scenarios.
+//!
+//! An [`Analysis`] plays out every possible combination of results of the remaining matches of
+//! a tournament. For every entrant it reports whether the entrant has already clinched a spot in
+//! the top `n` positions, has been eliminated from them, or which results it still requires to
+//! advance.
+//!
+//! Since the number of combinations doubles with every remaining match, the analysis is only
+//! feasible near the end of a tournament. An [`Analysis`] gives up once more than
+//! [`limit`] outcomes exist.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::{EntrantScore, RoundRobin};
+//! # use dynamic_tournament_core::scenarios::{Analysis, Status};
+//! let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+//!
+//! let scenarios = Analysis::new(2).run(&tournament).unwrap();
+//! assert_eq!(scenarios.outcomes(), 64);
+//! assert_eq!(scenarios.get(0).unwrap().status, Status::Alive);
+//! ```
+//!
+//! [`limit`]: Analysis::limit
+use crate::standings::Standings;
+use crate::{EntrantData, EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The number of outcomes an [`Analysis`] enumerates at most by default.
+pub const DEFAULT_LIMIT: u64 = 1 << 16;
+
+/// An exhaustive analysis of the remaining matches of a tournament.
+#[derive(Copy, Clone, Debug)]
+pub struct Analysis {
+    top: usize,
+    limit: u64,
+}
+
+impl Analysis {
+    /// Creates a new `Analysis` for entrants advancing from the first `top` positions.
+    #[inline]
+    pub fn new(top: usize) -> Self {
+        Self {
+            top,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// Sets the maximum number of outcomes that are enumerated. Defaults to [`DEFAULT_LIMIT`].
+    #[inline]
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Enumerates all outcomes of the remaining matches of `tournament` and returns the
+    /// resulting [`Scenarios`]. The `tournament` itself is never modified.
+    ///
+    /// Every match is either won by the first or the second entrant, draws are not considered.
+    ///
+    /// Returns `None` if the remaining matches have more outcomes than the configured
+    /// [`limit`].
+    ///
+    /// [`limit`]: Self::limit
+    pub fn run<S>(&self, tournament: &S) -> Option<Scenarios>
+    where
+        S: System + Clone,
+        S::NodeData: Default,
+    {
+        // Every open match at least doubles the number of outcomes. Give up before enumerating
+        // anything if that alone exceeds the limit.
+        let open = tournament
+            .matches()
+            .iter()
+            .filter(|m| m.is_occupied() && !m.is_concluded())
+            .count();
+        if open >= u64::BITS as usize || 1 << open > self.limit {
+            return None;
+        }
+
+        let mut state = State {
+            outcomes: 0,
+            entrants: vec![Accumulator::default(); tournament.entrants().len()],
+            results: Vec::new(),
+        };
+
+        self.enumerate(tournament.clone(), &mut state)?;
+
+        let entrants = state
+            .entrants
+            .into_iter()
+            .map(|acc| {
+                let status = if acc.always_in {
+                    Status::Clinched
+                } else if acc.advancing == 0 {
+                    Status::Eliminated
+                } else {
+                    Status::Alive
+                };
+
+                let requirements = match status {
+                    Status::Alive => acc.requirements.unwrap_or_default(),
+                    _ => Vec::new(),
+                };
+
+                EntrantScenarios {
+                    status,
+                    advancing: acc.advancing,
+                    requirements,
+                }
+            })
+            .collect();
+
+        Some(Scenarios {
+            top: self.top,
+            outcomes: state.outcomes,
+            entrants,
+        })
+    }
+
+    /// Plays the next open match of `tournament` with both possible winners. Returns `None` once
+    /// the limit is exceeded.
+    fn enumerate<S>(&self, tournament: S, state: &mut State) -> Option<()>
+    where
+        S: System + Clone,
+        S::NodeData: Default,
+    {
+        let next = tournament
+            .matches()
+            .iter()
+            .position(|m| m.is_occupied() && !m.is_concluded());
+
+        let index = match next {
+            Some(index) => index,
+            None => {
+                state.outcomes += 1;
+                if state.outcomes > self.limit {
+                    return None;
+                }
+
+                self.conclude(&tournament.standings(), state);
+                return Some(());
+            }
+        };
+
+        for winner in [0, 1] {
+            let mut tournament = tournament.clone();
+
+            let entrant = tournament.matches()[index].entrants[winner]
+                .unwrap_ref()
+                .index;
+
+            tournament.update_match(index, |m, res| {
+                if let EntrantSpot::Entrant(node) = &mut m.entrants[winner] {
+                    node.data.set_winner(true);
+                }
+
+                res.winner_default(&m.entrants[winner]);
+                res.loser_default(&m.entrants[1 - winner]);
+            });
+
+            state.results.push(Requirement {
+                index,
+                winner: entrant,
+            });
+            let res = self.enumerate(tournament, state);
+            state.results.pop();
+
+            res?;
+        }
+
+        Some(())
+    }
+
+    /// Records the final `standings` of a single outcome.
+    fn conclude(&self, standings: &Standings, state: &mut State) {
+        for (index, placement) in placements(standings, self.top) {
+            let acc = &mut state.entrants[index];
+
+            if placement != Placement::In {
+                acc.always_in = false;
+            }
+
+            if placement == Placement::Out {
+                continue;
+            }
+
+            acc.advancing += 1;
+
+            // Only keep the results that occurred in every outcome the entrant advanced in.
+            match &mut acc.requirements {
+                Some(requirements) => requirements.retain(|r| state.results.contains(r)),
+                None => {
+                    let mut requirements = state.results.clone();
+                    requirements.sort_by_key(|r| r.index);
+                    acc.requirements = Some(requirements);
+                }
+            }
+        }
+    }
+}
+
+/// The result of an [`Analysis`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scenarios {
+    top: usize,
+    outcomes: u64,
+    entrants: Vec<EntrantScenarios>,
+}
+
+impl Scenarios {
+    /// Returns the number of positions that advance.
+    #[inline]
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    /// Returns the number of outcomes that were enumerated.
+    #[inline]
+    pub fn outcomes(&self) -> u64 {
+        self.outcomes
+    }
+
+    /// Returns the [`EntrantScenarios`] of the entrant at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&EntrantScenarios> {
+        self.entrants.get(index)
+    }
+
+    /// Returns an iterator over the [`EntrantScenarios`] of all entrants.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, EntrantScenarios> {
+        self.entrants.iter()
+    }
+}
+
+/// The scenarios of a single entrant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EntrantScenarios {
+    pub status: Status,
+    /// The number of outcomes in which the entrant can advance.
+    pub advancing: u64,
+    /// The results that are required for the entrant to advance. This is only set if the
+    /// `status` is [`Status::Alive`]. An empty list means that no single result is required.
+    pub requirements: Vec<Requirement>,
+}
+
+/// Whether an entrant is guaranteed to advance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Status {
+    /// The entrant advances in every outcome.
+    Clinched,
+    /// The entrant advances in no outcome.
+    Eliminated,
+    /// The entrant advances in some outcomes.
+    Alive,
+}
+
+/// A single match result.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Requirement {
+    /// The index of the match.
+    pub index: usize,
+    /// The index of the entrant that wins the match.
+    pub winner: usize,
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    outcomes: u64,
+    entrants: Vec<Accumulator>,
+    /// The results played in the current outcome.
+    results: Vec<Requirement>,
+}
+
+#[derive(Clone, Debug)]
+struct Accumulator {
+    always_in: bool,
+    advancing: u64,
+    requirements: Option<Vec<Requirement>>,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            always_in: true,
+            advancing: 0,
+            requirements: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Placement {
+    /// The entrant is within the first `top` positions.
+    In,
+    /// The entrant shares a position with other entrants on both sides of the cutoff.
+    Tied,
+    /// The entrant is outside of the first `top` positions.
+    Out,
+}
+
+/// Returns the [`Placement`] of every entrant in the [`Standings`]. Entrants with equal values
+/// share the same position.
+fn placements(standings: &Standings, top: usize) -> Vec<(usize, Placement)> {
+    let entries: Vec<_> = standings.iter().collect();
+    let mut placements = Vec::with_capacity(entries.len());
+
+    let mut start = 0;
+    while start < entries.len() {
+        let end = entries[start..]
+            .iter()
+            .position(|entry| entry.values != entries[start].values)
+            .map(|len| start + len)
+            .unwrap_or(entries.len());
+
+        let placement = if end <= top {
+            Placement::In
+        } else if start >= top {
+            Placement::Out
+        } else {
+            Placement::Tied
+        };
+
+        for entry in &entries[start..end] {
+            placements.push((entry.index, placement));
+        }
+
+        start = end;
+    }
+
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Analysis, Requirement, Status};
+    use crate::{EntrantScore, EntrantSpot, RoundRobin, System};
+
+    fn win(tournament: &mut RoundRobin<u32, EntrantScore<u32>>, index: usize, winner: usize) {
+        tournament.update_match(index, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[winner] {
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[winner]);
+            res.loser_default(&m.entrants[1 - winner]);
+        });
+    }
+
+    #[test]
+    fn test_analysis_concluded() {
+        let mut tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..2);
+        win(&mut tournament, 0, 0);
+
+        let scenarios = Analysis::new(1).run(&tournament).unwrap();
+        assert_eq!(scenarios.outcomes(), 1);
+        assert_eq!(scenarios.get(0).unwrap().status, Status::Clinched);
+        assert_eq!(scenarios.get(1).unwrap().status, Status::Eliminated);
+    }
+
+    #[test]
+    fn test_analysis_last_match() {
+        let mut tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+
+        // Play all matches except the last one between entrants 2 and 3. The entrant with the
+        // lower index wins every match.
+        for index in 0..5 {
+            let [a, b] = tournament.matches()[index]
+                .entrants
+                .map(|e| e.unwrap().index);
+
+            win(&mut tournament, index, if a < b { 0 } else { 1 });
+        }
+
+        let scenarios = Analysis::new(3).run(&tournament).unwrap();
+        assert_eq!(scenarios.outcomes(), 2);
+        assert_eq!(scenarios.top(), 3);
+
+        assert_eq!(scenarios.get(0).unwrap().status, Status::Clinched);
+        assert_eq!(scenarios.get(1).unwrap().status, Status::Clinched);
+        assert!(scenarios.get(0).unwrap().requirements.is_empty());
+
+        // Entrants 2 and 3 need to win the last match.
+        for entrant in [2, 3] {
+            let entrant_scenarios = scenarios.get(entrant).unwrap();
+            assert_eq!(entrant_scenarios.status, Status::Alive);
+            assert_eq!(entrant_scenarios.advancing, 1);
+            assert_eq!(
+                entrant_scenarios.requirements,
+                [Requirement {
+                    index: 5,
+                    winner: entrant,
+                }]
+            );
+        }
+
+        let scenarios = Analysis::new(2).run(&tournament).unwrap();
+        assert_eq!(scenarios.get(2).unwrap().status, Status::Eliminated);
+        assert_eq!(scenarios.get(3).unwrap().status, Status::Eliminated);
+    }
+
+    #[test]
+    fn test_analysis_limit() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+
+        assert!(Analysis::new(2).limit(63).run(&tournament).is_none());
+        assert!(Analysis::new(2).limit(64).run(&tournament).is_some());
+
+        // 2^190 outcomes, rejected without enumerating them.
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..20);
+        assert!(Analysis::new(2).run(&tournament).is_none());
+    }
+
+    #[test]
+    fn test_analysis_ties() {
+        // Every entrant wins exactly one match in a round robin of three entrants if all
+        // results go around in a circle. No entrant clinches the first position.
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..3);
+
+        let scenarios = Analysis::new(1).run(&tournament).unwrap();
+        for entrant in scenarios.iter() {
+            assert_eq!(entrant.status, Status::Alive);
+            assert!(entrant.advancing < scenarios.outcomes());
+        }
+    }
+}
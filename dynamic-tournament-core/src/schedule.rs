@@ -0,0 +1,482 @@
+//! # Match Scheduling
+//!
+//! A [`Scheduler`] assigns every match of a [`System`] a time slot and a station (e.g. a setup,
+//! a server or a table) on which the match is played. Matches are only scheduled once all of
+//! their [`predecessors`] are concluded and every entrant had at least the configured rest time
+//! since their last match.
+//!
+//! All times are plain seconds. The reference point is up to the caller, usually a unix
+//! timestamp.
+//!
+//! [`System`]: crate::System
+//! [`predecessors`]: crate::System::predecessors
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A time slot on a station.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Slot {
+    /// The start time of the slot (inclusive).
+    pub start: u64,
+    /// The end time of the slot (exclusive).
+    pub end: u64,
+    /// The index of the station.
+    pub station: usize,
+}
+
+impl Slot {
+    /// Returns `true` if `self` and `other` use the same station at the same time.
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.station == other.station && self.start < other.end && other.start < self.end
+    }
+}
+
+/// A planned schedule for all matches of a [`System`].
+///
+/// [`System`]: crate::System
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Schedule {
+    slots: Vec<Option<Slot>>,
+}
+
+impl Schedule {
+    /// Returns the [`Slot`] of the match with the given `index`. Returns `None` if the match is
+    /// never played (e.g. a bye) or `index` is out-of-bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Slot> {
+        self.slots.get(index).copied().flatten()
+    }
+
+    /// Returns the number of matches in the `Schedule`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the `Schedule` contains no matches.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns an iterator over the [`Slot`]s of all matches, in match order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Option<Slot>> + '_ {
+        self.slots.iter().copied()
+    }
+
+    /// Returns the end time of the last match. Returns `None` if no match is scheduled.
+    pub fn end(&self) -> Option<u64> {
+        self.slots.iter().flatten().map(|slot| slot.end).max()
+    }
+}
+
+/// A greedy scheduler assigning time slots and stations to matches.
+///
+/// # Examples
+///
+/// ```
+/// # use dynamic_tournament_core::{EntrantScore, SingleElimination};
+/// # use dynamic_tournament_core::schedule::Scheduler;
+/// let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+///
+/// // Two stations, starting at 0 with 30 minute matches and 10 minutes of rest.
+/// let schedule = Scheduler::new(0, 2, 1800).rest(600).schedule(&tournament);
+///
+/// // Both semi finals are played at the same time.
+/// assert_eq!(schedule.get(0).unwrap().start, 0);
+/// assert_eq!(schedule.get(1).unwrap().start, 0);
+/// // The final starts after the semi finals and the rest time.
+/// assert_eq!(schedule.get(2).unwrap().start, 2400);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    start: u64,
+    stations: usize,
+    match_duration: u64,
+    rest: u64,
+    durations: HashMap<usize, u64>,
+    fixed: HashMap<usize, Slot>,
+}
+
+impl Scheduler {
+    /// Creates a new `Scheduler`. No match will be scheduled before `start`. Every match is
+    /// expected to take `match_duration` seconds.
+    ///
+    /// At least a single station is always used.
+    pub fn new(start: u64, stations: usize, match_duration: u64) -> Self {
+        Self {
+            start,
+            stations: stations.max(1),
+            match_duration,
+            rest: 0,
+            durations: HashMap::new(),
+            fixed: HashMap::new(),
+        }
+    }
+
+    /// Sets the minimum time an entrant must rest between two matches.
+    pub fn rest(mut self, rest: u64) -> Self {
+        self.rest = rest;
+        self
+    }
+
+    /// Sets the expected duration of the match with the given `index`, overwriting the default
+    /// match duration.
+    pub fn duration(mut self, index: usize, duration: u64) -> Self {
+        self.durations.insert(index, duration);
+        self
+    }
+
+    /// Fixes the match with the given `index` to `slot`. Fixed matches are never moved by the
+    /// scheduler. This should be used for manual overrides and for matches that were already
+    /// played.
+    ///
+    /// Note that a fixed match may be placed before `start`.
+    pub fn fix(mut self, index: usize, slot: Slot) -> Self {
+        self.fixed.insert(index, slot);
+        self
+    }
+
+    /// Creates a new [`Schedule`] for all matches in `system`.
+    ///
+    /// Matches that are never played (those containing an [`Empty`] spot) and concluded matches
+    /// that are not [`fix`]ed receive no slot.
+    ///
+    /// [`Empty`]: EntrantSpot::Empty
+    /// [`fix`]: Self::fix
+    pub fn schedule<S>(&self, system: &S) -> Schedule
+    where
+        S: System,
+    {
+        let num_matches = system.matches().len();
+
+        let predecessors: Vec<Vec<usize>> = (0..num_matches)
+            .map(|index| {
+                system
+                    .predecessors(index)
+                    .into_iter()
+                    .filter(|i| *i < num_matches)
+                    .collect()
+            })
+            .collect();
+
+        let mut calendars = vec![Vec::new(); self.stations];
+        for slot in self.fixed.values() {
+            if let Some(calendar) = calendars.get_mut(slot.station) {
+                insert_sorted(calendar, (slot.start, slot.end));
+            }
+        }
+
+        // The time at which the result of a match is known.
+        let mut ready = vec![self.start; num_matches];
+        // The time at which an entrant is ready for the next match.
+        let mut entrants = HashMap::new();
+        let mut slots = vec![None; num_matches];
+
+        for index in topological_order(&predecessors) {
+            let match_ = &system.matches()[index];
+
+            let mut earliest = self.start;
+            for pred in &predecessors[index] {
+                earliest = earliest.max(ready[*pred]);
+            }
+
+            for spot in &match_.entrants {
+                if let EntrantSpot::Entrant(node) = spot {
+                    if let Some(time) = entrants.get(&node.index) {
+                        earliest = earliest.max(*time);
+                    }
+                }
+            }
+
+            let slot = match self.fixed.get(&index) {
+                Some(slot) => Some(*slot),
+                None => {
+                    let is_bye = match_.entrants.iter().any(|spot| spot.is_empty());
+
+                    if is_bye || match_.is_concluded() {
+                        None
+                    } else {
+                        let duration = self
+                            .durations
+                            .get(&index)
+                            .copied()
+                            .unwrap_or(self.match_duration);
+
+                        Some(self.find_slot(&mut calendars, earliest, duration))
+                    }
+                }
+            };
+
+            match slot {
+                Some(slot) => {
+                    ready[index] = slot.end + self.rest;
+
+                    for spot in &match_.entrants {
+                        if let EntrantSpot::Entrant(node) = spot {
+                            let time = entrants.entry(node.index).or_insert(0);
+                            *time = (*time).max(slot.end + self.rest);
+                        }
+                    }
+                }
+                None => ready[index] = earliest,
+            }
+
+            slots[index] = slot;
+        }
+
+        Schedule { slots }
+    }
+
+    /// Finds the earliest slot starting at or after `earliest` on any station and reserves it.
+    fn find_slot(&self, calendars: &mut [Vec<(u64, u64)>], earliest: u64, duration: u64) -> Slot {
+        let mut best: Option<Slot> = None;
+
+        for (station, calendar) in calendars.iter().enumerate() {
+            let mut start = earliest;
+            for (busy_start, busy_end) in calendar.iter() {
+                if start + duration <= *busy_start {
+                    break;
+                }
+
+                start = start.max(*busy_end);
+            }
+
+            if best.map(|slot| start < slot.start).unwrap_or(true) {
+                best = Some(Slot {
+                    start,
+                    end: start + duration,
+                    station,
+                });
+            }
+        }
+
+        // There is always at least one station.
+        let slot = best.unwrap();
+        insert_sorted(&mut calendars[slot.station], (slot.start, slot.end));
+        slot
+    }
+}
+
+/// Inserts `interval` into the `calendar`, keeping it sorted by the start time.
+fn insert_sorted(calendar: &mut Vec<(u64, u64)>, interval: (u64, u64)) {
+    let index = calendar.partition_point(|elem| elem.0 <= interval.0);
+    calendar.insert(index, interval);
+}
+
+/// Returns all match indices ordered so that every match comes after all of its predecessors.
+/// Matches without any ordering constraint keep their index order. Matches that are part of a
+/// cycle are appended in index order.
+pub(crate) fn topological_order(predecessors: &[Vec<usize>]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = predecessors.iter().map(|preds| preds.len()).collect();
+    let mut successors = vec![Vec::new(); predecessors.len()];
+    for (index, preds) in predecessors.iter().enumerate() {
+        for pred in preds {
+            successors[*pred].push(index);
+        }
+    }
+
+    let mut queue: BTreeSet<usize> = (0..predecessors.len())
+        .filter(|index| remaining[*index] == 0)
+        .collect();
+
+    let mut order = Vec::with_capacity(predecessors.len());
+    let mut visited = vec![false; predecessors.len()];
+    while let Some(index) = queue.pop_first() {
+        order.push(index);
+        visited[index] = true;
+
+        for succ in &successors[index] {
+            remaining[*succ] -= 1;
+            if remaining[*succ] == 0 {
+                queue.insert(*succ);
+            }
+        }
+    }
+
+    for (index, visited) in visited.into_iter().enumerate() {
+        if !visited {
+            order.push(index);
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scheduler, Slot};
+    use crate::{
+        DoubleElimination, EntrantScore, EntrantSpot, RoundRobin, SingleElimination, Swiss, System,
+    };
+
+    #[test]
+    fn test_schedule_single_elimination() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+
+        let schedule = Scheduler::new(100, 2, 10).schedule(&tournament);
+        assert_eq!(schedule.len(), 7);
+
+        // First round: 4 matches on 2 stations.
+        assert_eq!(
+            schedule.get(0),
+            Some(Slot {
+                start: 100,
+                end: 110,
+                station: 0
+            })
+        );
+        assert_eq!(
+            schedule.get(1),
+            Some(Slot {
+                start: 100,
+                end: 110,
+                station: 1
+            })
+        );
+        assert_eq!(schedule.get(2).unwrap().start, 110);
+        assert_eq!(schedule.get(3).unwrap().start, 110);
+
+        // Second round waits for its predecessors and a free station.
+        assert_eq!(schedule.get(4).unwrap().start, 120);
+        assert_eq!(schedule.get(5).unwrap().start, 120);
+
+        // Final
+        assert_eq!(schedule.get(6).unwrap().start, 130);
+        assert_eq!(schedule.end(), Some(140));
+    }
+
+    #[test]
+    fn test_schedule_rest() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+
+        let schedule = Scheduler::new(0, 4, 10).rest(5).schedule(&tournament);
+        assert_eq!(schedule.get(0).unwrap().start, 0);
+        assert_eq!(schedule.get(1).unwrap().start, 0);
+        assert_eq!(schedule.get(2).unwrap().start, 15);
+    }
+
+    #[test]
+    fn test_schedule_byes() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..3);
+
+        let schedule = Scheduler::new(0, 1, 10).schedule(&tournament);
+        let byes = (0..tournament.matches().len())
+            .filter(|index| {
+                tournament.matches()[*index]
+                    .entrants
+                    .iter()
+                    .any(EntrantSpot::is_empty)
+            })
+            .count();
+
+        assert_eq!(byes, 1);
+        assert_eq!(
+            schedule.iter().filter(Option::is_none).count(),
+            byes,
+            "byes must not be scheduled"
+        );
+    }
+
+    #[test]
+    fn test_schedule_fixed() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+
+        let fixed = Slot {
+            start: 0,
+            end: 50,
+            station: 0,
+        };
+
+        let schedule = Scheduler::new(0, 1, 10).fix(0, fixed).schedule(&tournament);
+
+        assert_eq!(schedule.get(0), Some(fixed));
+        assert_eq!(schedule.get(1).unwrap().start, 50);
+        assert_eq!(schedule.get(2).unwrap().start, 60);
+    }
+
+    #[test]
+    fn test_schedule_duration() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+
+        let schedule = Scheduler::new(0, 2, 10)
+            .duration(1, 30)
+            .schedule(&tournament);
+
+        assert_eq!(schedule.get(0).unwrap().end, 10);
+        assert_eq!(schedule.get(1).unwrap().end, 30);
+        assert_eq!(schedule.get(2).unwrap().start, 30);
+    }
+
+    #[test]
+    fn test_schedule_round_robin() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+
+        let schedule = Scheduler::new(0, 4, 10).schedule(&tournament);
+
+        // No entrant plays two matches at the same time.
+        for (a, slot_a) in schedule.iter().enumerate() {
+            for (b, slot_b) in schedule.iter().enumerate().skip(a + 1) {
+                let (slot_a, slot_b) = (slot_a.unwrap(), slot_b.unwrap());
+
+                let shares_entrant = tournament.matches()[a]
+                    .entrants
+                    .iter()
+                    .any(|spot| tournament.matches()[b].entrants.contains(spot));
+
+                if shares_entrant {
+                    assert!(slot_a.end <= slot_b.start || slot_b.end <= slot_a.start);
+                }
+            }
+        }
+
+        // 3 rounds of 2 matches each.
+        assert_eq!(schedule.end(), Some(30));
+    }
+
+    #[test]
+    fn test_schedule_swiss() {
+        let tournament = Swiss::<u32, EntrantScore<u32>>::new(0..8);
+
+        let schedule = Scheduler::new(0, 4, 10).schedule(&tournament);
+        for index in 0..tournament.matches().len() {
+            assert_eq!(schedule.get(index).unwrap().start, index as u64 / 4 * 10);
+        }
+    }
+
+    #[test]
+    fn test_schedule_no_overlap() {
+        for entrants in 2..32 {
+            let tournament = DoubleElimination::<u32, EntrantScore<u32>>::new(0..entrants);
+            let schedule = Scheduler::new(0, 3, 10).rest(2).schedule(&tournament);
+
+            let slots: Vec<Slot> = schedule.iter().flatten().collect();
+            for (index, slot) in slots.iter().enumerate() {
+                for other in &slots[index + 1..] {
+                    assert!(!slot.overlaps(other));
+                }
+            }
+
+            for index in 0..tournament.matches().len() {
+                let Some(slot) = schedule.get(index) else {
+                    continue;
+                };
+
+                for pred in tournament.predecessors(index) {
+                    if let Some(pred) = schedule.get(pred) {
+                        assert!(pred.end <= slot.start);
+                    }
+                }
+            }
+        }
+    }
+}
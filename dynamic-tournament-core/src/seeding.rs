@@ -0,0 +1,68 @@
+//! Seeding of entrants.
+//!
+//! The initial placements of all entrants in a tournament are defined by the order of its
+//! [`Entrants`]. The functions in this module can be used to determine that order.
+//!
+//! [`Entrants`]: crate::Entrants
+use crate::utils::Rng;
+
+/// Shuffles `entrants` in place using the given `seed`.
+///
+/// The shuffle is a Fisher-Yates shuffle driven by a SplitMix64 generator initialized with
+/// `seed`. The same `seed` and the same input order always produce the same result, which allows
+/// anyone knowing the seed to reproduce a draw.
+///
+/// # Examples
+///
+/// ```
+/// # use dynamic_tournament_core::seeding::shuffle;
+/// let mut a = [1, 2, 3, 4, 5, 6, 7, 8];
+/// let mut b = a;
+///
+/// shuffle(&mut a, 42);
+/// shuffle(&mut b, 42);
+/// assert_eq!(a, b);
+/// ```
+pub fn shuffle<T>(entrants: &mut [T], seed: u64) {
+    let mut rng = Rng::new(seed);
+
+    for i in (1..entrants.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        entrants.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shuffle;
+
+    #[test]
+    fn test_shuffle() {
+        let mut entrants: Vec<u32> = (0..16).collect();
+        shuffle(&mut entrants, 1);
+
+        // The result is a permutation of the input.
+        let mut sorted = entrants.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+        assert_ne!(entrants, sorted);
+
+        let mut other: Vec<u32> = (0..16).collect();
+        shuffle(&mut other, 1);
+        assert_eq!(entrants, other);
+
+        let mut other: Vec<u32> = (0..16).collect();
+        shuffle(&mut other, 2);
+        assert_ne!(entrants, other);
+    }
+
+    #[test]
+    fn test_shuffle_empty() {
+        let mut entrants: [u32; 0] = [];
+        shuffle(&mut entrants, 0);
+
+        let mut entrants = [1];
+        shuffle(&mut entrants, 0);
+        assert_eq!(entrants, [1]);
+    }
+}
@@ -0,0 +1,363 @@
+//! Side assignment of matches.
+//!
+//! In many games the side an entrant plays on matters, e.g. the colour in chess or the starting
+//! side of a map. [`Sides`] assigns a [`Side`] to both entrants of every match using a
+//! [`SideRule`]:
+//! - [`SideRule::Balanced`]: Every entrant gets both sides as equally often as possible and
+//!   avoids playing the same side three times in a row. Used by [`Swiss`] and [`RoundRobin`].
+//! - [`SideRule::HigherSeed`]: The higher seeded entrant always plays [`Side::Home`]. Used by
+//!   elimination systems.
+//! - [`SideRule::CoinFlip`]: The sides are chosen randomly using a seed. Elimination systems use
+//!   it if the `sides` option is set to `coin_flip`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::{EntrantScore, RoundRobin, System};
+//! # use dynamic_tournament_core::sides::Side;
+//! let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+//!
+//! let sides = tournament.sides();
+//! for index in 0..4 {
+//!     let history = sides.history(index);
+//!     let home = history.iter().filter(|side| **side == Side::Home).count();
+//!     assert!(home == 1 || home == 2);
+//! }
+//! ```
+//!
+//! [`Swiss`]: crate::Swiss
+//! [`RoundRobin`]: crate::RoundRobin
+use std::cmp::Ordering;
+
+use crate::options::{Builder, OptionValue, TournamentOption, TournamentOptionValues};
+use crate::utils::Rng;
+use crate::{EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The side of an entrant in a match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Side {
+    /// The first or home side, e.g. white in chess.
+    Home,
+    /// The second or away side, e.g. black in chess.
+    Away,
+}
+
+impl Side {
+    /// Returns the opposite `Side`.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Home => Self::Away,
+            Self::Away => Self::Home,
+        }
+    }
+}
+
+/// The rule used to assign [`Side`]s in matches.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SideRule {
+    /// Balances the sides of every entrant across all rounds.
+    Balanced,
+    /// The higher seeded entrant, i.e. the entrant with the lower index, plays [`Side::Home`].
+    #[default]
+    HigherSeed,
+    /// Chooses the sides randomly using the given seed.
+    CoinFlip { seed: u64 },
+}
+
+/// The assigned [`Side`]s of all matches in a [`System`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sides {
+    matches: Vec<Option<[Side; 2]>>,
+    history: Vec<Vec<Side>>,
+}
+
+impl Sides {
+    /// Assigns the sides of all matches in `tournament` using the given [`SideRule`].
+    ///
+    /// Matches are processed in the order of their indices. Only matches with two entrants get
+    /// sides assigned.
+    pub fn new<S>(tournament: &S, rule: SideRule) -> Self
+    where
+        S: System,
+    {
+        let mut matches = Vec::with_capacity(tournament.matches().len());
+        let mut history = vec![Vec::new(); tournament.entrants().len()];
+
+        for (index, match_) in tournament.matches().iter().enumerate() {
+            let (EntrantSpot::Entrant(a), EntrantSpot::Entrant(b)) =
+                (&match_.entrants[0], &match_.entrants[1])
+            else {
+                matches.push(None);
+                continue;
+            };
+
+            let first = match rule {
+                SideRule::Balanced => {
+                    balanced(&history[a.index], &history[b.index], a.index, b.index)
+                }
+                SideRule::HigherSeed => {
+                    if a.index <= b.index {
+                        Side::Home
+                    } else {
+                        Side::Away
+                    }
+                }
+                SideRule::CoinFlip { seed } => {
+                    let mut rng = Rng::new(seed.wrapping_add(index as u64));
+                    if rng.next_u64() & 1 == 0 {
+                        Side::Home
+                    } else {
+                        Side::Away
+                    }
+                }
+            };
+
+            history[a.index].push(first);
+            history[b.index].push(first.opposite());
+            matches.push(Some([first, first.opposite()]));
+        }
+
+        Self { matches, history }
+    }
+
+    /// Returns the [`Side`]s of both entrants of the match with the given `index`. Returns `None`
+    /// if the match has no sides assigned.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<[Side; 2]> {
+        self.matches.get(index).copied().flatten()
+    }
+
+    /// Returns all [`Side`]s played by the entrant at `index`, in the order of the matches.
+    #[inline]
+    pub fn history(&self, index: usize) -> &[Side] {
+        self.history
+            .get(index)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Adds the options selecting the [`SideRule`] of an elimination system to `builder`. The rule
+/// is read back using [`take_elimination_rule`].
+pub(crate) fn elimination_options(builder: Builder) -> Builder {
+    builder
+        .insert(
+            "sides",
+            TournamentOption::new("How the sides of matches are assigned", "higher_seed")
+                .choices(["higher_seed", "coin_flip"]),
+        )
+        .option("side_seed", "The seed of the coin flip for sides", 0u64)
+}
+
+/// Takes the options added by [`elimination_options`] from `options` and returns the selected
+/// [`SideRule`].
+pub(crate) fn take_elimination_rule(options: &mut TournamentOptionValues) -> SideRule {
+    let seed = options
+        .take("side_seed")
+        .map(|val| val.unwrap_u64_or(0))
+        .unwrap_or(0);
+
+    match options.take("sides") {
+        Some(OptionValue::String(rule)) if rule == "coin_flip" => SideRule::CoinFlip { seed },
+        _ => SideRule::HigherSeed,
+    }
+}
+
+/// Returns the [`Side`] of the entrant `a` when playing against `b`.
+///
+/// The entrant with the stronger claim on [`Side::Home`] gets it. Absolute preferences (to avoid
+/// the same side three times in a row or an imbalance of more than two) have precedence over the
+/// overall balance, which in turn has precedence over alternating from the last match. If both entrants have the same claim, the higher seeded
+/// entrant gets the side it prefers.
+fn balanced(a: &[Side], b: &[Side], a_index: usize, b_index: usize) -> Side {
+    let a = claim(a);
+
+    match a.cmp(&claim(b)) {
+        Ordering::Greater => Side::Home,
+        Ordering::Less => Side::Away,
+        Ordering::Equal => {
+            // Both entrants prefer the same side. The higher seeded entrant gets its preference.
+            let preferred = if a >= (0, 0, 0) {
+                Side::Home
+            } else {
+                Side::Away
+            };
+
+            if a_index <= b_index {
+                preferred
+            } else {
+                preferred.opposite()
+            }
+        }
+    }
+}
+
+/// Returns how strongly an entrant with the given `history` should play [`Side::Home`] next.
+fn claim(history: &[Side]) -> (i8, isize, i8) {
+    let value = |side: &Side| match side {
+        Side::Home => -1,
+        Side::Away => 1,
+    };
+
+    let balance: isize = history.iter().map(|side| value(side) as isize).sum();
+
+    // The preference is absolute if the entrant played the same side twice in a row or one side
+    // two times more often than the other.
+    let absolute = match history {
+        _ if balance.abs() >= 2 => balance.signum() as i8,
+        [.., x, y] if x == y => value(y),
+        _ => 0,
+    };
+
+    let last = history.last().map(value).unwrap_or(0);
+
+    (absolute, balance, last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{balanced, Side, SideRule, Sides};
+    use crate::{
+        option_values, EntrantScore, EntrantSpot, RoundRobin, SingleElimination, Swiss, System,
+    };
+
+    #[test]
+    fn test_sides_round_robin() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..6);
+        let sides = tournament.sides();
+
+        for index in 0..6 {
+            let history = sides.history(index);
+            assert_eq!(history.len(), 5);
+
+            let home = history.iter().filter(|side| **side == Side::Home).count();
+            assert!((2..=3).contains(&home), "{:?}", history);
+        }
+    }
+
+    #[test]
+    fn test_sides_balanced() {
+        use Side::{Away, Home};
+
+        // Same side twice in a row.
+        assert_eq!(balanced(&[Home, Home], &[], 0, 1), Away);
+        assert_eq!(balanced(&[], &[Away, Away], 0, 1), Away);
+
+        // Imbalance of two has precedence over the last side.
+        assert_eq!(
+            balanced(&[Home, Away], &[Away, Away, Home, Away], 0, 1),
+            Away
+        );
+
+        // Balance has precedence over alternation.
+        assert_eq!(balanced(&[Away, Home], &[Home, Away, Home], 0, 1), Home);
+
+        // Alternation from the last match.
+        assert_eq!(balanced(&[Away, Home], &[Home, Away], 0, 1), Away);
+
+        // Equal claims: the higher seed gets its preference.
+        assert_eq!(balanced(&[Home], &[Home], 0, 1), Away);
+        assert_eq!(balanced(&[Home], &[Home], 1, 0), Home);
+        assert_eq!(balanced(&[], &[], 0, 1), Home);
+    }
+
+    #[test]
+    fn test_sides_swiss() {
+        let mut tournament = Swiss::<u32, EntrantScore<u32>>::new(0..8);
+
+        // Play all rounds with the first entrant always winning.
+        let mut index = 0;
+        while index < tournament.matches().len() {
+            tournament.update_match(index, |m, res| {
+                if let EntrantSpot::Entrant(node) = &mut m.entrants[0] {
+                    node.data.winner = true;
+                }
+
+                res.winner_default(&m.entrants[0]);
+                res.loser_default(&m.entrants[1]);
+            });
+
+            index += 1;
+        }
+
+        let sides = tournament.sides();
+        for index in 0..tournament.matches().len() {
+            let [a, b] = sides.get(index).unwrap();
+            assert_eq!(a, b.opposite());
+        }
+
+        // The first round has no history and the higher seed plays home.
+        for index in 0..4 {
+            let match_ = &tournament.matches()[index];
+            let [a, b] = [0, 1].map(|i| match_.entrants[i].unwrap_ref().index);
+
+            let expected = if a < b { Side::Home } else { Side::Away };
+            assert_eq!(sides.get(index).unwrap()[0], expected);
+        }
+
+        // Every entrant played in all three rounds.
+        for index in 0..8 {
+            assert_eq!(sides.history(index).len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_sides_higher_seed() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        let sides = tournament.sides();
+
+        for (index, match_) in tournament.matches().iter().enumerate() {
+            match (&match_.entrants[0], &match_.entrants[1]) {
+                (EntrantSpot::Entrant(a), EntrantSpot::Entrant(b)) => {
+                    let expected = if a.index < b.index {
+                        [Side::Home, Side::Away]
+                    } else {
+                        [Side::Away, Side::Home]
+                    };
+
+                    assert_eq!(sides.get(index), Some(expected));
+                }
+                _ => assert_eq!(sides.get(index), None),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sides_coin_flip() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..8);
+
+        let a = Sides::new(&tournament, SideRule::CoinFlip { seed: 7 });
+        let b = Sides::new(&tournament, SideRule::CoinFlip { seed: 7 });
+        assert_eq!(a, b);
+
+        let home = (0..tournament.matches().len())
+            .filter(|index| a.get(*index).unwrap()[0] == Side::Home)
+            .count();
+        assert!(home > 0 && home < tournament.matches().len());
+    }
+
+    #[test]
+    fn test_sides_coin_flip_option() {
+        let options = option_values!("sides" => "coin_flip", "side_seed" => 7u64);
+        let tournament =
+            SingleElimination::<u32, EntrantScore<u32>>::new_with_options(0..8, options);
+        assert_eq!(tournament.side_rule(), SideRule::CoinFlip { seed: 7 });
+
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+        assert_eq!(tournament.side_rule(), SideRule::HigherSeed);
+
+        let options = option_values!("sides" => "random");
+        assert!(options
+            .merge(SingleElimination::<u32, EntrantScore<u32>>::options())
+            .is_err());
+    }
+}
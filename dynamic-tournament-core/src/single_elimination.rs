@@ -1,5 +1,7 @@
 use crate::options::{OptionValue, TournamentOptionValues, TournamentOptions};
 use crate::render::{Column, Element, Position, RenderState, Row};
+use crate::rounds::{Round, Rounds};
+use crate::sides::{self, SideRule};
 use crate::{EntrantData, Entrants, Match, Matches, NextMatches, System};
 use crate::{EntrantSpot, Error, MatchResult, Node, Result};
 
@@ -133,13 +135,13 @@ where
 
     /// Returns the [`TournamentOptions`] accepted by this system.
     pub fn options() -> TournamentOptions {
-        TournamentOptions::builder()
-            .option(
-                "third_place_match",
-                "Include a match for the third place",
-                false,
-            )
-            .build()
+        let builder = TournamentOptions::builder().option(
+            "third_place_match",
+            "Include a match for the third place",
+            false,
+        );
+
+        sides::elimination_options(builder).build()
     }
 
     /// Resumes the bracket from existing matches.
@@ -439,6 +441,46 @@ where
         }
     }
 
+    fn side_rule(&self) -> SideRule {
+        self.options.sides
+    }
+
+    fn rounds(&self) -> Rounds {
+        let mut len = self.matches.len();
+
+        let third_place_match = self.options.third_place_match && self.entrants.len() > 2;
+        if third_place_match {
+            len -= 1;
+        }
+
+        let mut rounds = Vec::new();
+        let mut start = 0;
+        let mut num_matches = len.div_ceil(2);
+        while start < len {
+            let end = (start + num_matches).min(len);
+
+            if end == len {
+                rounds.push(Round::new("Final", "F", start..end));
+            } else {
+                let round = rounds.len() + 1;
+                rounds.push(Round::new(
+                    format!("Round {}", round),
+                    format!("R{}", round),
+                    start..end,
+                ));
+            }
+
+            start = end;
+            num_matches = (num_matches / 2).max(1);
+        }
+
+        if third_place_match {
+            rounds.push(Round::new("Third Place Match", "3P", [len]));
+        }
+
+        Rounds::new(rounds)
+    }
+
     fn start_render(&self) -> RenderState<'_, Self> {
         let mut columns = Vec::new();
 
@@ -452,6 +494,7 @@ where
                 matches.push(Element::new(crate::render::Match {
                     index: i,
                     predecessors: vec![],
+                    sides: None,
                     _marker: PhantomData,
                     label: None,
                     position: None,
@@ -489,6 +532,7 @@ impl<T, D> Borrow<Matches<D>> for SingleElimination<T, D> {
 #[derive(Copy, Clone, Debug, Default)]
 struct SingleEliminationOptions {
     third_place_match: bool,
+    sides: SideRule,
 }
 
 impl SingleEliminationOptions {
@@ -499,6 +543,8 @@ impl SingleEliminationOptions {
             this.third_place_match = val.unwrap_bool_or(false);
         }
 
+        this.sides = sides::take_elimination_rule(&mut options);
+
         this
     }
 }
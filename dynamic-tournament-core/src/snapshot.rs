@@ -0,0 +1,229 @@
+//! A portable, versioned format for the complete state of a [`Tournament`].
+//!
+//! A [`Snapshot`] contains the [`TournamentKind`], the options, the entrants and the matches of a
+//! tournament. It can be created using [`Tournament::snapshot`] and turned back into a
+//! [`Tournament`] using [`Tournament::from_snapshot`].
+//!
+//! Every snapshot records the [`SNAPSHOT_VERSION`] it was written with. Snapshots written by
+//! older versions are migrated to the current version by [`Snapshot::migrate`] before they are
+//! loaded.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::EntrantScore;
+//! # use dynamic_tournament_core::options::TournamentOptionValues;
+//! # use dynamic_tournament_core::tournament::{Tournament, TournamentKind};
+//! let mut tournament = Tournament::<u32, EntrantScore<u32>>::new(
+//!     TournamentKind::SingleElimination,
+//!     TournamentOptionValues::default(),
+//! );
+//! tournament.extend(0..4);
+//!
+//! let snapshot = tournament.snapshot();
+//! let tournament = Tournament::from_snapshot(snapshot).unwrap();
+//! assert_eq!(tournament.kind(), TournamentKind::SingleElimination);
+//! ```
+use crate::options::TournamentOptionValues;
+use crate::tournament::{Tournament, TournamentKind};
+use crate::{EntrantData, Entrants, Error, Matches, Result, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The current version of the [`Snapshot`] format.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The complete state of a [`Tournament`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Snapshot<T, D> {
+    /// The version of the format the snapshot was written with.
+    pub version: u32,
+    pub kind: TournamentKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub options: TournamentOptionValues,
+    pub entrants: Entrants<T>,
+    pub matches: Matches<D>,
+}
+
+impl<T, D> Snapshot<T, D> {
+    /// Creates a new `Snapshot` using the current [`SNAPSHOT_VERSION`].
+    #[inline]
+    pub fn new(
+        kind: TournamentKind,
+        options: TournamentOptionValues,
+        entrants: Entrants<T>,
+        matches: Matches<D>,
+    ) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            kind,
+            options,
+            entrants,
+            matches,
+        }
+    }
+
+    /// Creates a new `Snapshot` from the separately stored parts written before the snapshot
+    /// format existed. A `None` value for `matches` means that no state was stored yet.
+    ///
+    /// The returned `Snapshot` has the version `0` and must be migrated before it is loaded.
+    pub fn legacy(
+        kind: TournamentKind,
+        options: TournamentOptionValues,
+        entrants: Entrants<T>,
+        matches: Option<Matches<D>>,
+    ) -> Self {
+        Self {
+            version: 0,
+            kind,
+            options,
+            entrants,
+            matches: matches.unwrap_or_else(Matches::new),
+        }
+    }
+}
+
+impl<T, D> Snapshot<T, D>
+where
+    T: Clone,
+    D: EntrantData + Clone,
+{
+    /// Migrates the `Snapshot` to the current [`SNAPSHOT_VERSION`]. Snapshots that already use
+    /// the current version are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnsupportedSnapshotVersion`] if the snapshot was written by a newer
+    /// version.
+    pub fn migrate(mut self) -> Result<Self> {
+        if self.version > SNAPSHOT_VERSION {
+            return Err(Error::UnsupportedSnapshotVersion {
+                version: self.version,
+                supported: SNAPSHOT_VERSION,
+            });
+        }
+
+        while self.version < SNAPSHOT_VERSION {
+            self = match self.version {
+                0 => self.migrate_v0(),
+                _ => unreachable!(),
+            };
+        }
+
+        Ok(self)
+    }
+
+    /// Version `0` brackets may not have any state stored. The initial matches are created from
+    /// the entrants in that case.
+    fn migrate_v0(mut self) -> Self {
+        if self.matches.is_empty() && !self.entrants.is_empty() {
+            let mut tournament = Tournament::new(self.kind, self.options.clone());
+            tournament.extend(self.entrants.iter().cloned());
+
+            self.matches = tournament.matches().clone();
+        }
+
+        self.version = 1;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Snapshot, SNAPSHOT_VERSION};
+    use crate::options::TournamentOptionValues;
+    use crate::tournament::{Tournament, TournamentKind};
+    use crate::{EntrantScore, EntrantSpot, Error, System};
+
+    const KINDS: [TournamentKind; 5] = [
+        TournamentKind::SingleElimination,
+        TournamentKind::DoubleElimination,
+        TournamentKind::RoundRobin,
+        TournamentKind::Swiss,
+        TournamentKind::Pools,
+    ];
+
+    fn tournament(kind: TournamentKind) -> Tournament<u32, EntrantScore<u32>> {
+        let mut tournament: Tournament<u32, EntrantScore<u32>> =
+            Tournament::new(kind, TournamentOptionValues::default());
+        tournament.extend(0..8);
+
+        tournament.update_match(0, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[0] {
+                node.data.score = 2;
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[0]);
+            res.loser_default(&m.entrants[1]);
+        });
+
+        tournament
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        for kind in KINDS {
+            let tournament = tournament(kind);
+
+            let snapshot = tournament.snapshot();
+            assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+            assert_eq!(snapshot.kind, kind);
+
+            let restored = Tournament::from_snapshot(snapshot).unwrap();
+            assert_eq!(restored.kind(), kind);
+            assert_eq!(restored.entrants().len(), tournament.entrants().len());
+            assert_eq!(restored.matches(), tournament.matches());
+        }
+    }
+
+    #[test]
+    fn test_snapshot_migrate_v0() {
+        for kind in KINDS {
+            let expected = tournament(kind);
+
+            // Legacy brackets with state.
+            let snapshot = Snapshot::legacy(
+                kind,
+                TournamentOptionValues::default(),
+                expected.entrants().clone(),
+                Some(expected.matches().clone()),
+            );
+            assert_eq!(snapshot.version, 0);
+
+            let restored = Tournament::from_snapshot(snapshot).unwrap();
+            assert_eq!(restored.matches(), expected.matches());
+
+            // Legacy brackets without state.
+            let mut expected: Tournament<u32, EntrantScore<u32>> =
+                Tournament::new(kind, TournamentOptionValues::default());
+            expected.extend(0..8);
+
+            let snapshot = Snapshot::legacy(
+                kind,
+                TournamentOptionValues::default(),
+                expected.entrants().clone(),
+                None,
+            );
+
+            let restored = Tournament::from_snapshot(snapshot).unwrap();
+            assert_eq!(restored.matches(), expected.matches());
+        }
+    }
+
+    #[test]
+    fn test_snapshot_unsupported_version() {
+        let mut snapshot = tournament(TournamentKind::SingleElimination).snapshot();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+
+        assert_eq!(
+            snapshot.migrate().unwrap_err(),
+            Error::UnsupportedSnapshotVersion {
+                version: SNAPSHOT_VERSION + 1,
+                supported: SNAPSHOT_VERSION,
+            }
+        );
+    }
+}
@@ -2,8 +2,10 @@ use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
-use crate::options::{TournamentOptionValues, TournamentOptions};
+use crate::options::{TournamentOption, TournamentOptionValues, TournamentOptions};
 use crate::render::{Column, Element, Label, Position, RenderState, Row};
+use crate::rounds::{self, Rounds};
+use crate::sides::SideRule;
 use crate::standings::Standings;
 use crate::utils::NumExt;
 use crate::{
@@ -11,6 +13,9 @@ use crate::{
     Result, System,
 };
 
+/// The largest number of points a single match result may award.
+const MAX_SCORE: u64 = 100;
+
 /// A swiss group stage tournament.
 ///
 /// # Implementation notes
@@ -120,9 +125,20 @@ where
     /// Returns the [`TournamentOptions`] accepted by this system.
     pub fn options() -> TournamentOptions {
         TournamentOptions::builder()
-            .option("score_win", "How many points to award for a win.", 1u64)
-            .option("score_loss", "How many points to award for a loss.", 0u64)
-            .option("score_bye", "How many points to award for a bye.", 1u64)
+            .insert(
+                "score_win",
+                TournamentOption::new("How many points to award for a win.", 1u64)
+                    .min(1u64)
+                    .max(MAX_SCORE),
+            )
+            .insert(
+                "score_loss",
+                TournamentOption::new("How many points to award for a loss.", 0u64).max(MAX_SCORE),
+            )
+            .insert(
+                "score_bye",
+                TournamentOption::new("How many points to award for a bye.", 1u64).max(MAX_SCORE),
+            )
             .build()
     }
 
@@ -504,6 +520,22 @@ where
         NextMatches::default()
     }
 
+    fn predecessors(&self, index: usize) -> Vec<usize> {
+        if index >= self.matches.len() {
+            return Vec::new();
+        }
+
+        // The pairings of a round depend on all results of the previous round.
+        let round = index / self.matches_per_round();
+        match round {
+            0 => Vec::new(),
+            round => {
+                let start = (round - 1) * self.matches_per_round();
+                (start..start + self.matches_per_round()).collect()
+            }
+        }
+    }
+
     fn update_match<F>(&mut self, index: usize, f: F)
     where
         F: FnOnce(&mut Match<Node<Self::NodeData>>, &mut MatchResult<Self::NodeData>),
@@ -557,6 +589,14 @@ where
         self.build_next_round();
     }
 
+    fn rounds(&self) -> Rounds {
+        rounds::fixed_size(self.matches.len(), self.matches_per_round())
+    }
+
+    fn side_rule(&self) -> SideRule {
+        SideRule::Balanced
+    }
+
     fn start_render(&self) -> RenderState<'_, Self> {
         let mut rounds = Vec::new();
 
@@ -576,6 +616,7 @@ where
                 round.push(Element::new(crate::render::Match {
                     index,
                     predecessors: vec![],
+                    sides: None,
                     _marker: std::marker::PhantomData,
                     label: None,
                     position: None,
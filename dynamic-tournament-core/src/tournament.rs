@@ -1,11 +1,16 @@
 use std::borrow::Borrow;
 
 use crate::options::{TournamentOptionValues, TournamentOptions};
+use crate::render::RenderState;
+use crate::snapshot::Snapshot;
 use crate::{
-    DoubleElimination, EntrantData, Entrants, Match, MatchResult, Matches, Node, Result,
+    DoubleElimination, EntrantData, Entrants, Match, MatchResult, Matches, Node, Pools, Result,
     RoundRobin, SingleElimination, Swiss, System,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug)]
 pub struct Tournament<T, D>
 where
@@ -13,6 +18,7 @@ where
     D: EntrantData + Clone,
 {
     inner: InnerTournament<T, D>,
+    options: TournamentOptionValues,
 }
 
 impl<T, D> Tournament<T, D>
@@ -21,28 +27,18 @@ where
     D: EntrantData + Clone,
 {
     pub fn new(kind: TournamentKind, options: TournamentOptionValues) -> Self {
-        let inner = match kind {
-            TournamentKind::SingleElimination => InnerTournament::SingleElimination(
-                SingleElimination::new_with_options(vec![].into_iter(), options),
-            ),
-            TournamentKind::DoubleElimination => {
-                InnerTournament::DoubleElimination(DoubleElimination::new(vec![].into_iter()))
-            }
-            TournamentKind::RoundRobin => {
-                InnerTournament::RountRobin(RoundRobin::new(vec![].into_iter()))
-            }
-            TournamentKind::Swiss => InnerTournament::Swiss(Swiss::new(vec![].into_iter())),
-        };
+        let inner = InnerTournament::new(kind, Vec::new(), options.clone());
 
-        Self { inner }
+        Self { inner, options }
     }
 
     pub fn options(kind: TournamentKind) -> TournamentOptions {
         match kind {
             TournamentKind::SingleElimination => SingleElimination::<T, D>::options(),
-            TournamentKind::DoubleElimination => TournamentOptions::default(),
+            TournamentKind::DoubleElimination => DoubleElimination::<T, D>::options(),
             TournamentKind::RoundRobin => TournamentOptions::default(),
             TournamentKind::Swiss => Swiss::<T, D>::options(),
+            TournamentKind::Pools => Pools::<T, D>::options(),
         }
     }
 
@@ -54,47 +50,76 @@ where
     ) -> Result<Self> {
         let inner = match kind {
             TournamentKind::SingleElimination => InnerTournament::SingleElimination(
-                SingleElimination::resume(entrants, matches, options)?,
+                SingleElimination::resume(entrants, matches, options.clone())?,
+            ),
+            TournamentKind::DoubleElimination => InnerTournament::DoubleElimination(
+                DoubleElimination::resume(entrants, matches, options.clone())?,
             ),
-            TournamentKind::DoubleElimination => {
-                InnerTournament::DoubleElimination(DoubleElimination::resume(entrants, matches)?)
-            }
             TournamentKind::RoundRobin => {
                 InnerTournament::RountRobin(RoundRobin::resume(entrants, matches)?)
             }
             TournamentKind::Swiss => {
-                InnerTournament::Swiss(Swiss::resume(entrants, matches, options)?)
+                InnerTournament::Swiss(Swiss::resume(entrants, matches, options.clone())?)
+            }
+            TournamentKind::Pools => {
+                InnerTournament::Pools(Pools::resume(entrants, matches, options.clone())?)
             }
         };
 
-        Ok(Self { inner })
+        Ok(Self { inner, options })
     }
 
-    pub fn push(&mut self, entrant: T) {
-        match &mut self.inner {
-            InnerTournament::SingleElimination(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.push(entrant);
-                *t = SingleElimination::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::DoubleElimination(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.push(entrant);
-                *t = DoubleElimination::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::RountRobin(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.push(entrant);
-                *t = RoundRobin::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::Swiss(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.push(entrant);
-                *t = Swiss::new(entrants.entrants.into_iter());
-            }
+    /// Returns the [`TournamentKind`] of the `Tournament`.
+    pub fn kind(&self) -> TournamentKind {
+        match &self.inner {
+            InnerTournament::SingleElimination(_) => TournamentKind::SingleElimination,
+            InnerTournament::DoubleElimination(_) => TournamentKind::DoubleElimination,
+            InnerTournament::RountRobin(_) => TournamentKind::RoundRobin,
+            InnerTournament::Swiss(_) => TournamentKind::Swiss,
+            InnerTournament::Pools(_) => TournamentKind::Pools,
         }
     }
 
+    /// Returns the [`TournamentOptionValues`] the `Tournament` was created with.
+    #[inline]
+    pub fn option_values(&self) -> &TournamentOptionValues {
+        &self.options
+    }
+
+    /// Creates a [`Snapshot`] of the current state of the `Tournament`.
+    pub fn snapshot(&self) -> Snapshot<T, D> {
+        Snapshot::new(
+            self.kind(),
+            self.options.clone(),
+            self.entrants().clone(),
+            self.matches().clone(),
+        )
+    }
+
+    /// Restores a `Tournament` from a [`Snapshot`]. Snapshots written by older versions are
+    /// migrated to the current version first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the snapshot was written by a newer, unsupported version or
+    /// contains an invalid state.
+    ///
+    /// [`Error`]: crate::Error
+    pub fn from_snapshot(snapshot: Snapshot<T, D>) -> Result<Self> {
+        let snapshot = snapshot.migrate()?;
+
+        Self::resume(
+            snapshot.kind,
+            snapshot.entrants,
+            snapshot.matches,
+            snapshot.options,
+        )
+    }
+
+    pub fn push(&mut self, entrant: T) {
+        self.extend(std::iter::once(entrant));
+    }
+
     pub fn update_match<F>(&mut self, index: usize, f: F)
     where
         F: FnOnce(&mut Match<Node<D>>, &mut MatchResult<D>),
@@ -104,6 +129,7 @@ where
             InnerTournament::DoubleElimination(t) => t.update_match(index, f),
             InnerTournament::RountRobin(t) => t.update_match(index, f),
             InnerTournament::Swiss(t) => t.update_match(index, f),
+            InnerTournament::Pools(t) => t.update_match(index, f),
         }
     }
 }
@@ -117,28 +143,12 @@ where
     where
         I: IntoIterator<Item = T>,
     {
-        match &mut self.inner {
-            InnerTournament::SingleElimination(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.extend(iter);
-                *t = SingleElimination::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::DoubleElimination(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.extend(iter);
-                *t = DoubleElimination::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::RountRobin(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.extend(iter);
-                *t = RoundRobin::new(entrants.entrants.into_iter());
-            }
-            InnerTournament::Swiss(t) => {
-                let mut entrants = t.clone().into_entrants();
-                entrants.extend(iter);
-                *t = Swiss::new(entrants.entrants.into_iter());
-            }
-        }
+        let kind = self.kind();
+
+        let mut entrants = self.entrants().clone();
+        entrants.extend(iter);
+
+        self.inner = InnerTournament::new(kind, entrants.entrants, self.options.clone());
     }
 }
 
@@ -156,6 +166,7 @@ where
             InnerTournament::DoubleElimination(t) => t.entrants(),
             InnerTournament::RountRobin(t) => t.entrants(),
             InnerTournament::Swiss(t) => t.entrants(),
+            InnerTournament::Pools(t) => t.entrants(),
         }
     }
 
@@ -166,6 +177,7 @@ where
                 InnerTournament::DoubleElimination(t) => t.entrants_mut(),
                 InnerTournament::RountRobin(t) => t.entrants_mut(),
                 InnerTournament::Swiss(t) => t.entrants_mut(),
+                InnerTournament::Pools(t) => t.entrants_mut(),
             }
         }
     }
@@ -176,6 +188,7 @@ where
             InnerTournament::DoubleElimination(t) => t.into_entrants(),
             InnerTournament::RountRobin(t) => t.into_entrants(),
             InnerTournament::Swiss(t) => t.into_entrants(),
+            InnerTournament::Pools(t) => t.into_entrants(),
         }
     }
 
@@ -185,6 +198,7 @@ where
             InnerTournament::DoubleElimination(t) => t.matches(),
             InnerTournament::RountRobin(t) => t.matches(),
             InnerTournament::Swiss(t) => t.matches(),
+            InnerTournament::Pools(t) => t.matches(),
         }
     }
 
@@ -195,6 +209,7 @@ where
                 InnerTournament::DoubleElimination(t) => t.matches_mut(),
                 InnerTournament::RountRobin(t) => t.matches_mut(),
                 InnerTournament::Swiss(t) => t.matches_mut(),
+                InnerTournament::Pools(t) => t.matches_mut(),
             }
         }
     }
@@ -205,6 +220,7 @@ where
             InnerTournament::DoubleElimination(t) => t.into_matches(),
             InnerTournament::RountRobin(t) => t.into_matches(),
             InnerTournament::Swiss(t) => t.into_matches(),
+            InnerTournament::Pools(t) => t.into_matches(),
         }
     }
 
@@ -214,6 +230,17 @@ where
             InnerTournament::DoubleElimination(t) => t.next_matches(index),
             InnerTournament::RountRobin(t) => t.next_matches(index),
             InnerTournament::Swiss(t) => t.next_matches(index),
+            InnerTournament::Pools(t) => t.next_matches(index),
+        }
+    }
+
+    fn predecessors(&self, index: usize) -> Vec<usize> {
+        match &self.inner {
+            InnerTournament::SingleElimination(t) => t.predecessors(index),
+            InnerTournament::DoubleElimination(t) => t.predecessors(index),
+            InnerTournament::RountRobin(t) => t.predecessors(index),
+            InnerTournament::Swiss(t) => t.predecessors(index),
+            InnerTournament::Pools(t) => t.predecessors(index),
         }
     }
 
@@ -226,22 +253,58 @@ where
             InnerTournament::DoubleElimination(t) => t.update_match(index, f),
             InnerTournament::RountRobin(t) => t.update_match(index, f),
             InnerTournament::Swiss(t) => t.update_match(index, f),
+            InnerTournament::Pools(t) => t.update_match(index, f),
         }
     }
 
-    fn start_render(&self) -> crate::render::RenderState<'_, Self> {
+    fn start_render(&self) -> RenderState<'_, Self> {
+        use std::mem::transmute;
+
         // Transmute the returned `RenderState<T>` into `RenderState<Self>`. This is safe since a
         // `RenderState` only contains a reference to the system and all operations will go through
         // `self`.
         match &self.inner {
             InnerTournament::SingleElimination(t) => unsafe {
-                std::mem::transmute(t.start_render())
+                transmute::<RenderState<'_, SingleElimination<T, D>>, RenderState<'_, Self>>(
+                    t.start_render(),
+                )
             },
             InnerTournament::DoubleElimination(t) => unsafe {
-                std::mem::transmute(t.start_render())
+                transmute::<RenderState<'_, DoubleElimination<T, D>>, RenderState<'_, Self>>(
+                    t.start_render(),
+                )
+            },
+            InnerTournament::RountRobin(t) => unsafe {
+                transmute::<RenderState<'_, RoundRobin<T, D>>, RenderState<'_, Self>>(
+                    t.start_render(),
+                )
+            },
+            InnerTournament::Swiss(t) => unsafe {
+                transmute::<RenderState<'_, Swiss<T, D>>, RenderState<'_, Self>>(t.start_render())
+            },
+            InnerTournament::Pools(t) => unsafe {
+                transmute::<RenderState<'_, Pools<T, D>>, RenderState<'_, Self>>(t.start_render())
             },
-            InnerTournament::RountRobin(t) => unsafe { std::mem::transmute(t.start_render()) },
-            InnerTournament::Swiss(t) => unsafe { std::mem::transmute(t.start_render()) },
+        }
+    }
+
+    fn rounds(&self) -> crate::rounds::Rounds {
+        match &self.inner {
+            InnerTournament::SingleElimination(t) => t.rounds(),
+            InnerTournament::DoubleElimination(t) => t.rounds(),
+            InnerTournament::RountRobin(t) => t.rounds(),
+            InnerTournament::Swiss(t) => t.rounds(),
+            InnerTournament::Pools(t) => t.rounds(),
+        }
+    }
+
+    fn side_rule(&self) -> crate::sides::SideRule {
+        match &self.inner {
+            InnerTournament::SingleElimination(t) => t.side_rule(),
+            InnerTournament::DoubleElimination(t) => t.side_rule(),
+            InnerTournament::RountRobin(t) => t.side_rule(),
+            InnerTournament::Swiss(t) => t.side_rule(),
+            InnerTournament::Pools(t) => t.side_rule(),
         }
     }
 
@@ -251,6 +314,7 @@ where
             InnerTournament::DoubleElimination(t) => t.standings(),
             InnerTournament::RountRobin(t) => t.standings(),
             InnerTournament::Swiss(t) => t.standings(),
+            InnerTournament::Pools(t) => t.standings(),
         }
     }
 }
@@ -265,12 +329,15 @@ where
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TournamentKind {
     SingleElimination,
     DoubleElimination,
     RoundRobin,
     Swiss,
+    Pools,
 }
 
 #[derive(Clone, Debug)]
@@ -282,4 +349,26 @@ where
     DoubleElimination(DoubleElimination<T, D>),
     RountRobin(RoundRobin<T, D>),
     Swiss(Swiss<T, D>),
+    Pools(Pools<T, D>),
+}
+
+impl<T, D> InnerTournament<T, D>
+where
+    D: EntrantData,
+{
+    fn new(kind: TournamentKind, entrants: Vec<T>, options: TournamentOptionValues) -> Self {
+        let entrants = entrants.into_iter();
+
+        match kind {
+            TournamentKind::SingleElimination => {
+                Self::SingleElimination(SingleElimination::new_with_options(entrants, options))
+            }
+            TournamentKind::DoubleElimination => {
+                Self::DoubleElimination(DoubleElimination::new_with_options(entrants, options))
+            }
+            TournamentKind::RoundRobin => Self::RountRobin(RoundRobin::new(entrants)),
+            TournamentKind::Swiss => Self::Swiss(Swiss::new_with_options(entrants, options)),
+            TournamentKind::Pools => Self::Pools(Pools::new_with_options(entrants, options)),
+        }
+    }
 }
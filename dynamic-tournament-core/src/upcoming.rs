@@ -0,0 +1,263 @@
+//! Queries for the matches that can be played next.
+//!
+//! [`Upcoming`] splits all open matches of a [`System`] into playable and blocked matches:
+//! - A match is playable if both entrants are known and the match is not concluded.
+//! - A match is blocked if at least one entrant is still to be determined. A [`BlockedMatch`]
+//!   contains the matches it is waiting on.
+//!
+//! Matches with an [`Empty`] spot are never played and are neither playable nor blocked.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::{EntrantScore, SingleElimination, System};
+//! let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..4);
+//!
+//! let upcoming = tournament.upcoming();
+//! assert_eq!(upcoming.playable(), [0, 1]);
+//! assert_eq!(upcoming.blocked()[0].index, 2);
+//! assert_eq!(upcoming.blocked()[0].waiting_on, [0, 1]);
+//! ```
+//!
+//! [`Empty`]: EntrantSpot::Empty
+use std::collections::BTreeSet;
+
+use crate::{EntrantSpot, System};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The playable and blocked matches of a [`System`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Upcoming {
+    playable: Vec<usize>,
+    blocked: Vec<BlockedMatch>,
+    next: Vec<Option<usize>>,
+}
+
+/// A match that cannot be played yet.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockedMatch {
+    /// The index of the match.
+    pub index: usize,
+    /// The indices of all unconcluded matches that must be played first, in ascending order.
+    pub waiting_on: Vec<usize>,
+}
+
+impl Upcoming {
+    /// Collects the upcoming matches of `tournament`.
+    pub fn new<S>(tournament: &S) -> Self
+    where
+        S: System,
+    {
+        let mut playable = Vec::new();
+        let mut blocked = Vec::new();
+        let mut next = vec![None; tournament.entrants().len()];
+
+        for (index, match_) in tournament.matches().iter().enumerate() {
+            if match_.is_concluded() || match_.entrants.iter().any(EntrantSpot::is_empty) {
+                continue;
+            }
+
+            // The first open match of an entrant is its next match.
+            for spot in &match_.entrants {
+                if let EntrantSpot::Entrant(node) = spot {
+                    if let Some(slot @ None) = next.get_mut(node.index) {
+                        *slot = Some(index);
+                    }
+                }
+            }
+
+            if match_.entrants.iter().all(EntrantSpot::is_entrant) {
+                playable.push(index);
+            } else {
+                let mut waiting_on = BTreeSet::new();
+                waiting(tournament, index, &mut waiting_on);
+
+                blocked.push(BlockedMatch {
+                    index,
+                    waiting_on: waiting_on.into_iter().collect(),
+                });
+            }
+        }
+
+        Self {
+            playable,
+            blocked,
+            next,
+        }
+    }
+
+    /// Returns the indices of all playable matches in ascending order.
+    #[inline]
+    pub fn playable(&self) -> &[usize] {
+        &self.playable
+    }
+
+    /// Returns all blocked matches ordered by their index.
+    #[inline]
+    pub fn blocked(&self) -> &[BlockedMatch] {
+        &self.blocked
+    }
+
+    /// Returns the index of the next match of the entrant at `index`. The match may still be
+    /// blocked. Returns `None` if the entrant has no open match with a known position.
+    #[inline]
+    pub fn next_match(&self, index: usize) -> Option<usize> {
+        self.next.get(index).copied().flatten()
+    }
+}
+
+/// Inserts all unconcluded matches that the match with the given `index` depends on into
+/// `matches`. Matches that are never played (e.g. byes) are skipped in favor of their own
+/// predecessors.
+fn waiting<S>(tournament: &S, index: usize, matches: &mut BTreeSet<usize>)
+where
+    S: System,
+{
+    for pred in tournament.predecessors(index) {
+        let Some(match_) = tournament.matches().get(pred) else {
+            continue;
+        };
+
+        if match_.is_concluded() {
+            continue;
+        }
+
+        if match_.entrants.iter().any(EntrantSpot::is_empty) {
+            waiting(tournament, pred, matches);
+        } else {
+            matches.insert(pred);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockedMatch;
+    use crate::{DoubleElimination, EntrantScore, EntrantSpot, RoundRobin, SingleElimination};
+    use crate::{Swiss, System};
+
+    fn win<S>(tournament: &mut S, index: usize)
+    where
+        S: System<NodeData = EntrantScore<u32>>,
+    {
+        tournament.update_match(index, |m, res| {
+            if let EntrantSpot::Entrant(node) = &mut m.entrants[0] {
+                node.data.winner = true;
+            }
+
+            res.winner_default(&m.entrants[0]);
+            res.loser_default(&m.entrants[1]);
+        });
+    }
+
+    #[test]
+    fn test_upcoming_single_elimination() {
+        let mut tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..8);
+
+        let upcoming = tournament.upcoming();
+        assert_eq!(upcoming.playable(), [0, 1, 2, 3]);
+        assert_eq!(
+            upcoming.blocked(),
+            [
+                BlockedMatch {
+                    index: 4,
+                    waiting_on: vec![0, 1],
+                },
+                BlockedMatch {
+                    index: 5,
+                    waiting_on: vec![2, 3],
+                },
+                BlockedMatch {
+                    index: 6,
+                    waiting_on: vec![4, 5],
+                },
+            ]
+        );
+
+        for index in 0..8 {
+            assert!(upcoming.next_match(index).unwrap() < 4);
+        }
+
+        win(&mut tournament, 0);
+
+        let upcoming = tournament.upcoming();
+        assert_eq!(upcoming.playable(), [1, 2, 3]);
+        assert_eq!(upcoming.blocked()[0].waiting_on, [1]);
+
+        // The winner of match 0 waits in match 4, the loser has no match left.
+        let match_ = &tournament.matches()[0];
+        let winner = match_.entrants[0].unwrap_ref().index;
+        let loser = match_.entrants[1].unwrap_ref().index;
+        assert_eq!(upcoming.next_match(winner), Some(4));
+        assert_eq!(upcoming.next_match(loser), None);
+    }
+
+    #[test]
+    fn test_upcoming_single_elimination_byes() {
+        let tournament = SingleElimination::<u32, EntrantScore<u32>>::new(0..3);
+
+        let upcoming = tournament.upcoming();
+        for index in upcoming.playable() {
+            let match_ = &tournament.matches()[*index];
+            assert!(match_.entrants.iter().all(EntrantSpot::is_entrant));
+        }
+
+        // Byes are never waited on.
+        for blocked in upcoming.blocked() {
+            for index in &blocked.waiting_on {
+                assert!(upcoming.playable().contains(index));
+            }
+        }
+
+        for index in 0..3 {
+            assert!(upcoming.next_match(index).is_some());
+        }
+    }
+
+    #[test]
+    fn test_upcoming_double_elimination() {
+        let mut tournament = DoubleElimination::<u32, EntrantScore<u32>>::new(0..4);
+
+        win(&mut tournament, 0);
+        win(&mut tournament, 1);
+
+        let upcoming = tournament.upcoming();
+
+        // Both losers play in the lower bracket.
+        for index in [0, 1] {
+            let loser = tournament.matches()[index].entrants[1].unwrap_ref().index;
+            let next = upcoming.next_match(loser).unwrap();
+            assert!(upcoming.playable().contains(&next));
+        }
+
+        for blocked in upcoming.blocked() {
+            assert!(!blocked.waiting_on.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_upcoming_round_robin() {
+        let tournament = RoundRobin::<u32, EntrantScore<u32>>::new(0..4);
+
+        let upcoming = tournament.upcoming();
+        assert_eq!(upcoming.playable().len(), tournament.matches().len());
+        assert!(upcoming.blocked().is_empty());
+    }
+
+    #[test]
+    fn test_upcoming_swiss() {
+        let tournament = Swiss::<u32, EntrantScore<u32>>::new(0..8);
+
+        let upcoming = tournament.upcoming();
+        assert_eq!(upcoming.playable(), [0, 1, 2, 3]);
+
+        // Every round waits on the complete previous round.
+        for blocked in upcoming.blocked() {
+            assert_eq!(blocked.waiting_on, tournament.predecessors(blocked.index));
+        }
+    }
+}
@@ -10,9 +10,39 @@ impl NumExt for usize {
     }
 }
 
+/// A small, non-cryptographic pseudo random number generator based on SplitMix64.
+///
+/// The same seed always produces the same sequence of numbers.
+#[derive(Clone, Debug)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[inline]
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random `f64` in the range `[0, 1)`.
+    #[inline]
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::NumExt;
+    use super::{NumExt, Rng};
 
     #[test]
     fn test_ilog2() {
@@ -26,4 +56,18 @@ mod tests {
         assert_eq!(17_usize.ilog2_ceil(), 5);
         assert_eq!(32_usize.ilog2_ceil(), 5);
     }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+
+            let val = a.next_f64();
+            assert!((0.0..1.0).contains(&val));
+            b.next_f64();
+        }
+    }
 }
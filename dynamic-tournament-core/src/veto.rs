@@ -0,0 +1,312 @@
+//! Map (or stage) pick-and-ban vetoes.
+//!
+//! A [`Veto`] is played by both entrants of a match before the match starts. The entrants take
+//! turns banning and picking maps from a pool as defined by the [`VetoConfig`]. The last step of
+//! the sequence may be a [`VetoStep::Decider`], which automatically picks the only remaining map.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dynamic_tournament_core::veto::{Veto, VetoConfig, VetoStep};
+//! let config = VetoConfig {
+//!     pool: vec!["a".into(), "b".into(), "c".into()],
+//!     sequence: vec![VetoStep::Ban, VetoStep::Ban, VetoStep::Decider],
+//! };
+//!
+//! let mut veto = Veto::new(config, 0).unwrap();
+//! veto.apply(0, 0).unwrap();
+//! veto.apply(1, 2).unwrap();
+//!
+//! assert!(veto.is_finished());
+//! assert_eq!(veto.maps(), ["b"]);
+//! ```
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single step in the sequence of a [`Veto`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VetoStep {
+    /// The entrant removes a map from the pool.
+    Ban,
+    /// The entrant picks a map that will be played.
+    Pick,
+    /// The last remaining map is picked automatically.
+    Decider,
+}
+
+/// The map pool and the sequence of a [`Veto`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VetoConfig {
+    /// The names of all maps in the pool.
+    pub pool: Vec<String>,
+    /// The steps of the veto in order. Entrants take turns for every [`VetoStep::Ban`] and
+    /// [`VetoStep::Pick`].
+    pub sequence: Vec<VetoStep>,
+}
+
+impl VetoConfig {
+    /// Checks whether the `VetoConfig` can be played.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VetoError::InvalidConfig`] if the pool contains duplicate or not enough maps for
+    /// the sequence, the sequence is empty or a [`VetoStep::Decider`] is not the last step.
+    pub fn validate(&self) -> Result<(), VetoError> {
+        if self.sequence.is_empty() {
+            return Err(VetoError::InvalidConfig("sequence is empty"));
+        }
+
+        for (index, map) in self.pool.iter().enumerate() {
+            if self.pool[..index].contains(map) {
+                return Err(VetoError::InvalidConfig("pool contains duplicate maps"));
+            }
+        }
+
+        if let Some(pos) = self.sequence.iter().position(|s| *s == VetoStep::Decider) {
+            if pos != self.sequence.len() - 1 {
+                return Err(VetoError::InvalidConfig("decider must be the last step"));
+            }
+
+            if self.sequence.len() != self.pool.len() {
+                return Err(VetoError::InvalidConfig(
+                    "sequence with a decider must use all maps",
+                ));
+            }
+        } else if self.sequence.len() > self.pool.len() {
+            return Err(VetoError::InvalidConfig("not enough maps for sequence"));
+        }
+
+        Ok(())
+    }
+}
+
+/// A single played step of a [`Veto`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VetoEntry {
+    pub step: VetoStep,
+    /// The position of the entrant in the match that played the step. `None` for a
+    /// [`VetoStep::Decider`].
+    pub team: Option<usize>,
+    /// The index of the map in [`VetoConfig::pool`].
+    pub map: usize,
+}
+
+/// The state of the veto of a single match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Veto {
+    config: VetoConfig,
+    /// The position of the entrant playing the first step.
+    first: usize,
+    entries: Vec<VetoEntry>,
+}
+
+impl Veto {
+    /// Creates a new `Veto` using the given [`VetoConfig`]. The entrant at position `first` in
+    /// the match plays the first step.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VetoError::InvalidConfig`] if the `config` is invalid.
+    pub fn new(config: VetoConfig, first: usize) -> Result<Self, VetoError> {
+        config.validate()?;
+
+        let mut veto = Self {
+            config,
+            first: first % 2,
+            entries: Vec::new(),
+        };
+        veto.advance();
+
+        Ok(veto)
+    }
+
+    /// Returns the [`VetoConfig`] of the `Veto`.
+    #[inline]
+    pub fn config(&self) -> &VetoConfig {
+        &self.config
+    }
+
+    /// Returns all played steps in order.
+    #[inline]
+    pub fn entries(&self) -> &[VetoEntry] {
+        &self.entries
+    }
+
+    /// Returns the next [`VetoStep`] and the position of the entrant that has to play it.
+    /// Returns `None` if the `Veto` is finished.
+    pub fn next(&self) -> Option<(VetoStep, usize)> {
+        let index = self.entries.len();
+        let step = *self.config.sequence.get(index)?;
+
+        Some((step, (self.first + index) % 2))
+    }
+
+    /// Returns `true` if all steps were played.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.entries.len() == self.config.sequence.len()
+    }
+
+    /// Returns the indices of all maps that were not banned or picked yet.
+    pub fn remaining(&self) -> Vec<usize> {
+        (0..self.config.pool.len())
+            .filter(|map| !self.entries.iter().any(|entry| entry.map == *map))
+            .collect()
+    }
+
+    /// Returns the names of all picked maps in the order they are played.
+    pub fn maps(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.step != VetoStep::Ban)
+            .map(|entry| self.config.pool[entry.map].as_str())
+            .collect()
+    }
+
+    /// Plays the next step as the entrant at position `team`, banning or picking the map at index
+    /// `map` in the pool. A following [`VetoStep::Decider`] is played automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`VetoError`] if the `Veto` is already finished, it is not the turn of `team`
+    /// or the map is not available.
+    pub fn apply(&mut self, team: usize, map: usize) -> Result<(), VetoError> {
+        let (step, expected) = self.next().ok_or(VetoError::Finished)?;
+
+        if team != expected {
+            return Err(VetoError::WrongTeam { expected });
+        }
+
+        if map >= self.config.pool.len() || self.entries.iter().any(|entry| entry.map == map) {
+            return Err(VetoError::UnavailableMap(map));
+        }
+
+        self.entries.push(VetoEntry {
+            step,
+            team: Some(team),
+            map,
+        });
+        self.advance();
+
+        Ok(())
+    }
+
+    /// Plays the [`VetoStep::Decider`] if it is the next step.
+    fn advance(&mut self) {
+        if let Some((VetoStep::Decider, _)) = self.next() {
+            if let [map] = self.remaining()[..] {
+                self.entries.push(VetoEntry {
+                    step: VetoStep::Decider,
+                    team: None,
+                    map,
+                });
+            }
+        }
+    }
+}
+
+/// An error that can occur when playing a [`Veto`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum VetoError {
+    #[error("invalid veto config: {0}")]
+    InvalidConfig(&'static str),
+    #[error("veto is already finished")]
+    Finished,
+    #[error("wrong team: expected team {expected}")]
+    WrongTeam { expected: usize },
+    #[error("map {0} is not available")]
+    UnavailableMap(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Veto, VetoConfig, VetoError, VetoStep};
+
+    fn bo3() -> VetoConfig {
+        use VetoStep::{Ban, Decider, Pick};
+
+        VetoConfig {
+            pool: ["a", "b", "c", "d", "e", "f", "g"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            sequence: vec![Ban, Ban, Pick, Pick, Ban, Ban, Decider],
+        }
+    }
+
+    #[test]
+    fn test_veto_config_validate() {
+        assert_eq!(bo3().validate(), Ok(()));
+
+        let mut config = bo3();
+        config.pool.pop();
+        assert!(config.validate().is_err());
+
+        let mut config = bo3();
+        config.sequence.swap(0, 6);
+        assert!(config.validate().is_err());
+
+        let mut config = bo3();
+        config.pool[1] = String::from("a");
+        assert!(config.validate().is_err());
+
+        let config = VetoConfig {
+            pool: vec![String::from("a"), String::from("b")],
+            sequence: vec![VetoStep::Ban],
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = VetoConfig {
+            pool: vec![String::from("a")],
+            sequence: vec![],
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_veto_bo3() {
+        let mut veto = Veto::new(bo3(), 1).unwrap();
+
+        assert_eq!(veto.next(), Some((VetoStep::Ban, 1)));
+        assert_eq!(veto.apply(0, 0), Err(VetoError::WrongTeam { expected: 1 }));
+
+        veto.apply(1, 0).unwrap();
+        assert_eq!(veto.apply(0, 0), Err(VetoError::UnavailableMap(0)));
+        assert_eq!(veto.apply(0, 7), Err(VetoError::UnavailableMap(7)));
+
+        veto.apply(0, 1).unwrap();
+        veto.apply(1, 4).unwrap();
+        veto.apply(0, 2).unwrap();
+        veto.apply(1, 3).unwrap();
+        assert!(!veto.is_finished());
+        veto.apply(0, 6).unwrap();
+
+        // The decider is played automatically.
+        assert!(veto.is_finished());
+        assert_eq!(veto.next(), None);
+        assert_eq!(veto.remaining(), Vec::<usize>::new());
+        assert_eq!(veto.maps(), ["e", "c", "f"]);
+        assert_eq!(veto.entries().last().unwrap().team, None);
+
+        assert_eq!(veto.apply(1, 5), Err(VetoError::Finished));
+    }
+
+    #[test]
+    fn test_veto_only_decider() {
+        let config = VetoConfig {
+            pool: vec![String::from("a")],
+            sequence: vec![VetoStep::Decider],
+        };
+
+        let veto = Veto::new(config, 0).unwrap();
+        assert!(veto.is_finished());
+        assert_eq!(veto.maps(), ["a"]);
+    }
+}
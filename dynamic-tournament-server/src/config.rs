@@ -206,10 +206,18 @@ pub struct Database {
 
 impl Database {
     pub fn connect_string(&self) -> String {
-        format!(
-            "{}://{}:{}@{}:{}/{}?ssl-mode=DISABLED",
-            self.driver, self.user, self.password, self.host, self.port, self.database
-        )
+        match self.driver.as_str() {
+            "postgres" | "postgresql" => format!(
+                "postgres://{}:{}@{}:{}/{}",
+                self.user, self.password, self.host, self.port, self.database
+            ),
+            // The database is a file path. Create the file if it doesn't exist.
+            "sqlite" => format!("sqlite://{}?mode=rwc", self.database),
+            _ => format!(
+                "mysql://{}:{}@{}:{}/{}?ssl-mode=DISABLED",
+                self.user, self.password, self.host, self.port, self.database
+            ),
+        }
     }
 
     pub fn from_environment() -> Result<Self, ConfigError> {
@@ -33,6 +33,35 @@ impl Etag {
     {
         self == Self::new(value)
     }
+
+    /// Returns `true` if the list of entity tags in an `If-Match` or `If-None-Match` header
+    /// `value` contains this `Etag`. A `*` matches every `Etag`. Tags may be quoted. Weak tags
+    /// (`W/"..."`) only match if `weak` comparison is used, i.e. for `If-None-Match`.
+    pub fn matches_header(self, value: &[u8], weak: bool) -> bool {
+        let Ok(value) = std::str::from_utf8(value) else {
+            return false;
+        };
+
+        let etag = self.to_string();
+
+        value.split(',').map(str::trim).any(|tag| {
+            if tag == "*" {
+                return true;
+            }
+
+            let (is_weak, tag) = match tag.strip_prefix("W/") {
+                Some(tag) => (true, tag),
+                None => (false, tag),
+            };
+
+            let tag = tag
+                .strip_prefix('"')
+                .and_then(|tag| tag.strip_suffix('"'))
+                .unwrap_or(tag);
+
+            tag == etag && (weak || !is_weak)
+        })
+    }
 }
 
 impl Display for Etag {
@@ -89,3 +118,28 @@ impl Hasher for EtagHasher {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Etag;
+
+    #[test]
+    fn test_etag_matches_header() {
+        let etag: Etag = "a1b2c3d4e5f6a7b8c9d0a1b2c3d4e5f6a7b8c9d0".parse().unwrap();
+        let other: Etag = "d0c9b8a7f6e5d4c3b2a1d0c9b8a7f6e5d4c3b2a1".parse().unwrap();
+
+        let tag = etag.to_string();
+        assert!(etag.matches_header(tag.as_bytes(), false));
+        assert!(etag.matches_header(format!("\"{}\"", tag).as_bytes(), false));
+        assert!(etag.matches_header(format!("{}, \"{}\"", other, tag).as_bytes(), false));
+        assert!(!etag.matches_header(other.to_string().as_bytes(), false));
+
+        assert!(etag.matches_header(b"*", false));
+        assert!(etag.matches_header(b"*", true));
+
+        // Weak tags only match using weak comparison.
+        let weak = format!("W/\"{}\"", tag);
+        assert!(!etag.matches_header(weak.as_bytes(), false));
+        assert!(etag.matches_header(weak.as_bytes(), true));
+    }
+}
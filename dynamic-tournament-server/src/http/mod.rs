@@ -349,8 +349,8 @@ impl Context {
     }
 
     /// Asserts that the request is authenticated and the token satisfies all [`Flags`] provided.
-    /// Returns an [`enum@Error`] if this is not the case.
-    pub fn require_authentication(&self, flags: Flags) -> std::result::Result<(), Error> {
+    /// Returns the id of the authenticated user or an [`enum@Error`] if this is not the case.
+    pub fn require_authentication(&self, flags: Flags) -> std::result::Result<u64, Error> {
         let header = self.req.authorization()?;
 
         let mut parts = header.split(' ');
@@ -369,7 +369,7 @@ impl Context {
             Ok(token) => {
                 // Validates the permissions flags.
                 if token.claims().flags.intersects(flags) {
-                    Ok(())
+                    Ok(token.claims().sub)
                 } else {
                     Err(StatusCodeError::forbidden().into())
                 }
@@ -383,54 +383,7 @@ impl Context {
     }
 
     pub fn compare_etag(&self, etag: Etag) -> Option<Result> {
-        // Comparing ETag and `If-Match`/`If-None-Match` headers can have two different
-        // meanings. If the request method is "safe" i.e. GET or HEAD we return 304 if the
-        // content DID NOT change. On all other methods we return 412 if the content DID
-        // change.
-
-        let if_match = self.req.if_match();
-        let if_none_match = self.req.if_none_match();
-
-        // We don't need to check anything if the client did not make any conditional
-        // requests.
-        if if_match.is_none() && if_none_match.is_none() {
-            return None;
-        }
-
-        match *self.req.method() {
-            // Safe methods
-            Method::GET | Method::HEAD => {
-                if let Some(val) = self.req.if_none_match() {
-                    // TODO: This should happen the other way around, i.e. try to convert
-                    // val into an Etag to avoid the allocation of `to_string`.
-                    if etag.to_string().as_bytes() == val {
-                        return Some(Ok(Response::not_modified()));
-                    }
-                }
-
-                if let Some(val) = self.req.if_match() {
-                    if etag.to_string().as_bytes() != val {
-                        return Some(Err(StatusCodeError::precondition_failed().into()));
-                    }
-                }
-            }
-            // Non-safe methods
-            _ => {
-                if let Some(val) = self.req.if_none_match() {
-                    if etag.to_string().as_bytes() == val {
-                        return Some(Err(StatusCodeError::precondition_failed().into()));
-                    }
-                }
-
-                if let Some(val) = self.req.if_match() {
-                    if etag.to_string().as_bytes() == val {
-                        return Some(Err(StatusCodeError::precondition_failed().into()));
-                    }
-                }
-            }
-        }
-
-        None
+        self.req.compare_etag(etag)
     }
 }
 
@@ -570,6 +523,57 @@ impl Request {
     pub fn if_none_match(&self) -> Option<&[u8]> {
         self.headers().get(IF_NONE_MATCH).map(|val| val.as_bytes())
     }
+
+    /// Compares `etag` with the `If-Match` and `If-None-Match` headers of the request. Returns
+    /// the response if the request should not be processed any further.
+    pub fn compare_etag(&self, etag: Etag) -> Option<Result> {
+        // Comparing ETag and `If-Match`/`If-None-Match` headers can have two different
+        // meanings. If the request method is "safe" i.e. GET or HEAD we return 304 if the
+        // content DID NOT change. On all other methods we return 412 if the content DID
+        // change.
+
+        let if_match = self.if_match();
+        let if_none_match = self.if_none_match();
+
+        // We don't need to check anything if the client did not make any conditional
+        // requests.
+        if if_match.is_none() && if_none_match.is_none() {
+            return None;
+        }
+
+        match *self.method() {
+            // Safe methods
+            Method::GET | Method::HEAD => {
+                if let Some(val) = if_none_match {
+                    if etag.matches_header(val, true) {
+                        return Some(Ok(Response::not_modified()));
+                    }
+                }
+
+                if let Some(val) = if_match {
+                    if !etag.matches_header(val, false) {
+                        return Some(Err(StatusCodeError::precondition_failed().into()));
+                    }
+                }
+            }
+            // Non-safe methods
+            _ => {
+                if let Some(val) = if_none_match {
+                    if etag.matches_header(val, true) {
+                        return Some(Err(StatusCodeError::precondition_failed().into()));
+                    }
+                }
+
+                if let Some(val) = if_match {
+                    if !etag.matches_header(val, false) {
+                        return Some(Err(StatusCodeError::precondition_failed().into()));
+                    }
+                }
+            }
+        }
+
+        None
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -714,3 +718,82 @@ macro_rules! compare_etag {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::{IF_MATCH, IF_NONE_MATCH};
+    use hyper::{Body, Method, StatusCode};
+
+    use super::etag::Etag;
+    use super::Request;
+    use crate::Error;
+
+    fn request(method: Method, header: Option<(&str, String)>) -> Request {
+        let mut req = hyper::Request::builder().method(method);
+        if let Some((name, value)) = header {
+            req = req.header(name, value);
+        }
+
+        Request::new(req.body(Body::empty()).unwrap())
+    }
+
+    #[test]
+    fn test_compare_etag() {
+        let etag: Etag = "a1b2c3d4e5f6a7b8c9d0a1b2c3d4e5f6a7b8c9d0".parse().unwrap();
+        let other: Etag = "d0c9b8a7f6e5d4c3b2a1d0c9b8a7f6e5d4c3b2a1".parse().unwrap();
+
+        let is_precondition_failed = |res| matches!(res, Some(Err(Error::StatusCodeError(_))));
+
+        assert!(request(Method::GET, None).compare_etag(etag).is_none());
+
+        // Safe methods
+        let res = request(
+            Method::GET,
+            Some((IF_NONE_MATCH.as_str(), etag.to_string())),
+        )
+        .compare_etag(etag);
+        assert_eq!(res.unwrap().unwrap().status, StatusCode::NOT_MODIFIED);
+
+        let res = request(
+            Method::GET,
+            Some((IF_NONE_MATCH.as_str(), other.to_string())),
+        )
+        .compare_etag(etag);
+        assert!(res.is_none());
+
+        // Non-safe methods
+        let res =
+            request(Method::PUT, Some((IF_MATCH.as_str(), etag.to_string()))).compare_etag(etag);
+        assert!(res.is_none());
+
+        let res = request(
+            Method::PUT,
+            Some((IF_MATCH.as_str(), format!("\"{}\"", etag))),
+        )
+        .compare_etag(etag);
+        assert!(res.is_none());
+
+        let res =
+            request(Method::PUT, Some((IF_MATCH.as_str(), String::from("*")))).compare_etag(etag);
+        assert!(res.is_none());
+
+        let res =
+            request(Method::PUT, Some((IF_MATCH.as_str(), other.to_string()))).compare_etag(etag);
+        assert!(is_precondition_failed(res));
+
+        // `If-Match` uses the strong comparison.
+        let res = request(
+            Method::PUT,
+            Some((IF_MATCH.as_str(), format!("W/\"{}\"", etag))),
+        )
+        .compare_etag(etag);
+        assert!(is_precondition_failed(res));
+
+        let res = request(
+            Method::PUT,
+            Some((IF_NONE_MATCH.as_str(), String::from("*"))),
+        )
+        .compare_etag(etag);
+        assert!(is_precondition_failed(res));
+    }
+}
@@ -48,11 +48,11 @@ async fn login(mut ctx: Context) -> Result {
 async fn refresh(mut ctx: Context) -> Result {
     let body: RefreshToken = ctx.req.json().await?;
 
-    match ctx.state.auth.validate_refresh_token(body.refresh_token) {
-        Ok(token) => {
-            let tokens = ctx.state.auth.create_tokens(token.into_claims())?;
-            Ok(Response::ok().json(&tokens))
-        }
-        Err(_) => Err(StatusCodeError::unauthorized().into()),
-    }
+    let token = match ctx.state.auth.validate_refresh_token(body.refresh_token) {
+        Ok(token) => token,
+        Err(_) => return Err(StatusCodeError::unauthorized().into()),
+    };
+
+    let tokens = ctx.state.auth.create_tokens(token.into_claims())?;
+    Ok(Response::ok().json(&tokens))
 }
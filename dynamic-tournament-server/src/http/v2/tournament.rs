@@ -8,9 +8,10 @@ use dynamic_tournament_api::tournament::{
     BracketType, Entrants, Player, Role, Team, Tournament, TournamentId, TournamentOverview,
 };
 use dynamic_tournament_api::v3::id::SystemId;
-use dynamic_tournament_api::v3::tournaments::brackets::Bracket as Bracket2;
+use dynamic_tournament_api::v3::tournaments::brackets::{Bracket as Bracket2, Seeding};
 use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant};
 use dynamic_tournament_api::v3::tournaments::entrants::{Player as Player2, Team as Team2};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
 use dynamic_tournament_api::v3::tournaments::roles::Role as Role2;
 use dynamic_tournament_api::v3::tournaments::{EntrantKind, Tournament as Tournament2};
 use dynamic_tournament_core::options::TournamentOptionValues;
@@ -68,7 +69,7 @@ async fn list(ctx: Context) -> Result {
 }
 
 async fn create(mut ctx: Context) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let body: Tournament = ctx.req.json().await?;
 
@@ -77,7 +78,7 @@ async fn create(mut ctx: Context) -> Result {
         Entrants::Teams(_) => EntrantKind::Team,
     };
 
-    let tournament = Tournament2 {
+    let mut tournament = Tournament2 {
         id: 0.into(),
         name: body.name,
         description: body.description,
@@ -86,6 +87,21 @@ async fn create(mut ctx: Context) -> Result {
     };
 
     let id = ctx.state.store.insert_tournament(&tournament).await?;
+    tournament.id = id;
+
+    ctx.state
+        .store
+        .system_log()
+        .push(
+            author,
+            LogEventBody::CreateTournament {
+                id,
+                tournament: tournament.clone(),
+            },
+        )
+        .await?;
+
+    let log = ctx.state.store.event_log(id);
 
     let mut roles = HashMap::new();
     for role in [
@@ -95,16 +111,21 @@ async fn create(mut ctx: Context) -> Result {
         Role::Duelist,
         Role::Support,
     ] {
-        let id = ctx
-            .state
-            .store
-            .roles(id)
-            .insert(&Role2 {
-                id: 0.into(),
-                name: role.to_string(),
-            })
-            .await?;
-        roles.insert(role, id);
+        let mut role2 = Role2 {
+            id: 0.into(),
+            name: role.to_string(),
+        };
+        role2.id = ctx.state.store.roles(id).insert(&role2).await?;
+
+        log.push(
+            author,
+            LogEventBody::CreateRole {
+                id: role2.id,
+                role: role2.clone(),
+            },
+        )
+        .await?;
+        roles.insert(role, role2.id);
     }
 
     let entrants: Vec<Entrant> = match body.entrants {
@@ -140,12 +161,21 @@ async fn create(mut ctx: Context) -> Result {
     };
 
     let mut entrant_ids = Vec::new();
-    for entrant in entrants {
-        let id = ctx.state.store.insert_entrant(id, entrant).await?;
-        entrant_ids.push(id);
+    for mut entrant in entrants {
+        entrant.id = ctx.state.store.insert_entrant(id, entrant.clone()).await?;
+        entrant_ids.push(entrant.id);
+
+        log.push(
+            author,
+            LogEventBody::CreateEntrant {
+                id: entrant.id,
+                entrant,
+            },
+        )
+        .await?;
     }
 
-    let bracket = Bracket2 {
+    let mut bracket = Bracket2 {
         id: 0.into(),
         name: tournament.name.clone(),
         system: match body.bracket_type {
@@ -159,9 +189,19 @@ async fn create(mut ctx: Context) -> Result {
             BracketType::DoubleElimination => TournamentOptionValues::default(),
         },
         entrants: entrant_ids,
+        seeding: Seeding::Ordered,
     };
 
-    ctx.state.store.insert_bracket(id, &bracket).await?;
+    bracket.id = ctx.state.store.insert_bracket(id, &bracket).await?;
+
+    log.push(
+        author,
+        LogEventBody::CreateBracket {
+            id: bracket.id,
+            bracket,
+        },
+    )
+    .await?;
 
     Ok(Response::created().body(id.to_string()))
 }
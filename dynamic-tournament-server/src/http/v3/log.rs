@@ -0,0 +1,19 @@
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_macros::{method, path};
+
+use crate::http::{Context, Response, Result};
+
+pub async fn route(mut ctx: Context) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => list(ctx).await,
+        })
+    })
+}
+
+async fn list(ctx: Context) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let events = ctx.state.store.system_log().list().await?;
+    Ok(Response::ok().json(&events))
+}
@@ -1,3 +1,4 @@
+mod log;
 mod systems;
 mod tournaments;
 pub mod users;
@@ -15,5 +16,6 @@ pub async fn route(mut ctx: Context) -> Result {
         "tournaments" => tournaments::route(ctx).await,
         "systems" => systems::route(ctx).await,
         "users" => users::route(ctx).await,
+        "log" => log::route(ctx).await,
     })
 }
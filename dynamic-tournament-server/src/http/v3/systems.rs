@@ -5,7 +5,7 @@ use crate::StatusCodeError;
 
 use dynamic_tournament_api::v3::id::SystemId;
 use dynamic_tournament_api::v3::systems::{System, SystemOverview};
-use dynamic_tournament_core::{EntrantScore, SingleElimination, Swiss};
+use dynamic_tournament_core::{DoubleElimination, EntrantScore, Pools, SingleElimination, Swiss};
 use dynamic_tournament_macros::{method, path};
 
 pub async fn route(mut ctx: Context) -> Result {
@@ -38,6 +38,10 @@ async fn list(_ctx: Context) -> Result {
             id: SystemId(4),
             name: "Swiss (Monrad/Buchholz)".into(),
         },
+        SystemOverview {
+            id: SystemId(5),
+            name: "Pools".into(),
+        },
     ];
 
     Ok(Response::ok().json(&systems))
@@ -53,7 +57,7 @@ async fn get(_ctx: Context, id: SystemId) -> Result {
         2 => Some(System {
             id: SystemId(2),
             name: "Double Elimination".into(),
-            options: TournamentOptions::default(),
+            options: DoubleElimination::<u8, EntrantScore<u8>>::options(),
         }),
         3 => Some(System {
             id: SystemId(3),
@@ -65,6 +69,11 @@ async fn get(_ctx: Context, id: SystemId) -> Result {
             name: "Swiss (Monrad/Buchholz)".into(),
             options: Swiss::<u8, EntrantScore<u8>>::options(),
         }),
+        5 => Some(System {
+            id: SystemId(5),
+            name: "Pools".into(),
+            options: Pools::<u8, EntrantScore<u8>>::options(),
+        }),
         _ => None,
     };
 
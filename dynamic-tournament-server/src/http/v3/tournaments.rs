@@ -1,11 +1,15 @@
 mod brackets;
 mod entrants;
 mod log;
+mod matches;
+mod ratings;
 mod roles;
+mod webhooks;
 
 use std::hash::{Hash, Hasher};
 
 use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
 use dynamic_tournament_api::v3::tournaments::Tournament;
 use dynamic_tournament_api::v3::{id::TournamentId, tournaments::TournamentOverview};
 use dynamic_tournament_api::Payload;
@@ -38,6 +42,9 @@ pub async fn route(mut ctx: Context) -> Result {
                 "brackets" => brackets::route(ctx, id).await,
                 "roles" => roles::route(ctx, id).await,
                 "log" => log::route(ctx, id).await,
+                "ratings" => ratings::route(ctx, id).await,
+                "matches" => matches::route(ctx, id).await,
+                "webhooks" => webhooks::route(ctx, id).await,
                 @ => method!(ctx, {
                     GET => get(ctx, id).await,
                     PATCH => patch(ctx, id).await,
@@ -69,19 +76,31 @@ async fn get(ctx: Context, id: TournamentId) -> Result {
 }
 
 async fn create(mut ctx: Context) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let mut tournaments: Payload<Tournament> = ctx.req.json().await?;
 
     for tournament in tournaments.iter_mut() {
         tournament.id = ctx.state.store.tournaments().insert(tournament).await?;
+
+        ctx.state
+            .store
+            .system_log()
+            .push(
+                author,
+                LogEventBody::CreateTournament {
+                    id: tournament.id,
+                    tournament: tournament.clone(),
+                },
+            )
+            .await?;
     }
 
     Ok(Response::created().json(&tournaments))
 }
 
 async fn patch(mut ctx: Context, id: TournamentId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let mut tournament = ctx.state.store.tournaments().get(id).await.map_404()?;
 
@@ -92,13 +111,26 @@ async fn patch(mut ctx: Context, id: TournamentId) -> Result {
     ctx.state.store.tournaments().update(id, &partial).await?;
 
     // Merge the patch.
+    let before = tournament.clone();
     tournament.update(partial);
 
+    ctx.state
+        .store
+        .event_log(id)
+        .push(
+            author,
+            LogEventBody::UpdateTournament {
+                before,
+                after: tournament.clone(),
+            },
+        )
+        .await?;
+
     Ok(Response::ok().json(&tournament))
 }
 
 async fn delete(ctx: Context, id: TournamentId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let tournament = ctx.state.store.tournaments().get(id).await.map_404()?;
 
@@ -106,6 +138,13 @@ async fn delete(ctx: Context, id: TournamentId) -> Result {
     compare_etag!(ctx, etag);
 
     ctx.state.store.tournaments().delete(id).await?;
+
+    ctx.state
+        .store
+        .system_log()
+        .push(author, LogEventBody::DeleteTournament { id, tournament })
+        .await?;
+
     Ok(Response::ok())
 }
 
@@ -1,21 +1,43 @@
+mod events;
 mod matches;
+mod schedule;
+mod veto;
 
 use dynamic_tournament_api::{
     auth::Flags,
     v3::{
-        id::{BracketId, SystemId, TournamentId},
-        tournaments::brackets::Bracket,
+        id::{BracketId, EntrantId, EventId, SystemId, TournamentId},
+        tournaments::{
+            brackets::{
+                Bracket, EntrantPrediction, EntrantScenarios, PartialBracket, Prediction,
+                Requirement, Scenarios, Seeding,
+            },
+            log::LogEventBody,
+        },
     },
     Payload,
 };
-use dynamic_tournament_core::{options::TournamentOptions, EntrantScore, SingleElimination, Swiss};
+use dynamic_tournament_core::prediction::{Simulation, DEFAULT_RATING};
+use dynamic_tournament_core::scenarios::Analysis;
+use dynamic_tournament_core::tournament::TournamentKind;
+use dynamic_tournament_core::{
+    options::{OptionValue, TournamentOptionValues, TournamentOptions},
+    DoubleElimination, EntrantScore, Pools, SingleElimination, Swiss, System,
+};
 use dynamic_tournament_macros::{method, path};
+use rand::rngs::OsRng;
+use rand::RngCore;
 
 use crate::{
-    http::{Context, Response, Result},
-    StatusCodeError,
+    http::{Context, HttpResult, Response, Result},
+    websocket::live_bracket::{self, requires_rebuild},
+    Error, StatusCodeError,
 };
 
+/// The expected match duration in seconds if no duration could be observed and the bracket has
+/// no schedule.
+const DEFAULT_MATCH_DURATION: u64 = 30 * 60;
+
 pub async fn route(mut ctx: Context, tournament_id: TournamentId) -> Result {
     path!(ctx, {
         @ => method!(ctx, {
@@ -25,9 +47,39 @@ pub async fn route(mut ctx: Context, tournament_id: TournamentId) -> Result {
         id => path!(ctx, {
             @ => method!(ctx, {
                 GET => get(ctx, tournament_id, id).await,
+                PATCH => patch(ctx, tournament_id, id).await,
                 DELETE => delete(ctx, tournament_id, id).await,
             }),
             "matches" => matches::route(ctx, tournament_id, id).await,
+            "events" => events::route(ctx, tournament_id, id).await,
+            "schedule" => schedule::route(ctx, tournament_id, id).await,
+            "veto" => veto::route(ctx, tournament_id, id).await,
+            "progress" => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => progress(ctx, tournament_id, id).await,
+                }),
+            }),
+            "rounds" => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => rounds(ctx, tournament_id, id).await,
+                }),
+            }),
+            "prediction" => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => prediction(ctx, tournament_id, id).await,
+                }),
+            }),
+            "scenarios" => path!(ctx, {
+                top => method!(ctx, {
+                    GET => scenarios(ctx, tournament_id, id, top).await,
+                }),
+            }),
+            "replay" => path!(ctx, {
+                event_id => method!(ctx, {
+                    GET => replay(ctx, tournament_id, id, event_id).await,
+                    POST => rollback(ctx, tournament_id, id, event_id).await,
+                }),
+            }),
         })
     })
 }
@@ -48,64 +100,469 @@ async fn get(ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result
 }
 
 async fn create(mut ctx: Context, tournament_id: TournamentId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let mut brackets: Payload<Bracket> = ctx.req.json().await?;
 
     for bracket in brackets.iter_mut() {
-        // Make sure all entrants in the bracket actually exist.
-        let entrants = ctx.state.store.get_entrants(tournament_id).await?;
-
-        // Keep track of consumed ids to deny duplicates.
-        let mut consumed = Vec::with_capacity(bracket.entrants.len());
-
-        for id in bracket.entrants.iter() {
-            if consumed.contains(id) {
-                return Err(StatusCodeError::bad_request()
-                    .message(format!("found entrant {} multiple times", id))
-                    .into());
-            }
-
-            if !entrants.iter().any(|e| e.id == *id) {
-                return Err(StatusCodeError::bad_request()
-                    .message(format!(
-                        "invalid entrant {}, does not exist for tournament",
-                        id
-                    ))
-                    .into());
-            }
-
-            consumed.push(*id);
-        }
+        validate_entrants(&ctx, tournament_id, &bracket.entrants).await?;
+        generate_side_seed(&mut bracket.options);
+        merge_options(bracket)?;
 
-        let options = match bracket.system {
-            SystemId(1) => SingleElimination::<u8, EntrantScore<u8>>::options(),
-            SystemId(2) => TournamentOptions::default(),
-            SystemId(3) => TournamentOptions::default(),
-            SystemId(4) => Swiss::<u8, EntrantScore<u8>>::options(),
-            _ => return Err(StatusCodeError::bad_request().into()),
-        };
+        // Draw the initial placements. A missing seed is generated and stored with the bracket
+        // so the draw can be reproduced.
+        if let Seeding::Shuffle { seed } = &mut bracket.seeding {
+            seed.get_or_insert_with(|| OsRng.next_u64());
+        }
 
-        bracket.options = match bracket.options.clone().merge(options) {
-            Ok(v) => v,
-            Err(err) => {
-                return Err(StatusCodeError::bad_request().message(err).into());
-            }
-        };
+        bracket.seeding.apply(&mut bracket.entrants);
 
         bracket.id = ctx
             .state
             .store
             .insert_bracket(tournament_id, bracket)
             .await?;
+
+        let log = ctx.state.store.event_log(tournament_id);
+
+        let body = LogEventBody::CreateBracket {
+            id: bracket.id,
+            bracket: bracket.clone(),
+        };
+
+        ctx.state.webhooks.dispatch(tournament_id, author, &body);
+        log.push(author, body).await?;
+
+        if let Seeding::Shuffle { seed: Some(seed) } = bracket.seeding {
+            log.push(
+                author,
+                LogEventBody::ShuffleBracket {
+                    bracket_id: bracket.id,
+                    seed,
+                },
+            )
+            .await?;
+        }
     }
 
     Ok(Response::created().json(&brackets))
 }
 
+async fn patch(mut ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let mut bracket = ctx
+        .state
+        .store
+        .get_bracket(tournament_id, id)
+        .await
+        .map_404()?;
+
+    let partial: PartialBracket = ctx.req.json().await?;
+    let update_entrants = partial.entrants.is_some();
+    let update_options = partial.options.is_some();
+
+    let before = bracket.clone();
+    bracket.update(partial);
+
+    if update_entrants || update_options {
+        validate_entrants(&ctx, tournament_id, &bracket.entrants).await?;
+        generate_side_seed(&mut bracket.options);
+        merge_options(&mut bracket)?;
+    }
+
+    // Changing the entrants or options recreates all matches. This is only allowed as long as
+    // no results would be lost. The bracket is stored together with the new matches.
+    if requires_rebuild(&before, &bracket) {
+        let live_bracket = ctx.state.live_brackets.get(tournament_id, id).await?;
+
+        if !live_bracket.rebuild(bracket.clone()).await? {
+            return Err(StatusCodeError::bad_request()
+                .message("cannot change the entrants or options of a bracket with results")
+                .into());
+        }
+    } else {
+        ctx.state
+            .store
+            .update_bracket(tournament_id, id, &bracket)
+            .await?;
+    }
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateBracket {
+                id,
+                before,
+                after: bracket.clone(),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&bracket))
+}
+
 async fn delete(ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let bracket = ctx
+        .state
+        .store
+        .get_bracket(tournament_id, id)
+        .await
+        .map_404()?;
 
     ctx.state.store.brackets(tournament_id).delete(id).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(author, LogEventBody::DeleteBracket { id, bracket })
+        .await?;
+
     Ok(Response::ok())
 }
+
+/// Verifies that all `entrants` exist in the tournament and no entrant is given multiple times.
+async fn validate_entrants(
+    ctx: &Context,
+    tournament_id: TournamentId,
+    entrants: &[EntrantId],
+) -> std::result::Result<(), Error> {
+    let existing = ctx.state.store.get_entrants(tournament_id).await?;
+
+    // Keep track of consumed ids to deny duplicates.
+    let mut consumed = Vec::with_capacity(entrants.len());
+
+    for id in entrants {
+        if consumed.contains(id) {
+            return Err(StatusCodeError::bad_request()
+                .message(format!("found entrant {} multiple times", id))
+                .into());
+        }
+
+        if !existing.iter().any(|e| e.id == *id) {
+            return Err(StatusCodeError::bad_request()
+                .message(format!(
+                    "invalid entrant {}, does not exist for tournament",
+                    id
+                ))
+                .into());
+        }
+
+        consumed.push(*id);
+    }
+
+    Ok(())
+}
+
+/// Merges the options of the `bracket` with the options of its system.
+fn merge_options(bracket: &mut Bracket) -> std::result::Result<(), Error> {
+    let options = match bracket.system {
+        SystemId(1) => SingleElimination::<u8, EntrantScore<u8>>::options(),
+        SystemId(2) => DoubleElimination::<u8, EntrantScore<u8>>::options(),
+        SystemId(3) => TournamentOptions::default(),
+        SystemId(4) => Swiss::<u8, EntrantScore<u8>>::options(),
+        SystemId(5) => Pools::<u8, EntrantScore<u8>>::options(),
+        _ => return Err(StatusCodeError::bad_request().into()),
+    };
+
+    bracket.options = match bracket.options.clone().merge(options) {
+        Ok(v) => v,
+        Err(err) => {
+            return Err(StatusCodeError::bad_request().message(err).into());
+        }
+    };
+
+    Ok(())
+}
+
+/// Generates the seed of the coin flip for sides if the sides are assigned by a coin flip and
+/// no seed is given. The default seed would assign the same sides in every bracket.
+fn generate_side_seed(options: &mut TournamentOptionValues) {
+    let coin_flip = matches!(
+        options.get("sides"),
+        Some(OptionValue::String(rule)) if rule == "coin_flip"
+    );
+
+    if coin_flip && options.get("side_seed").is_none() {
+        options.set("side_seed", OsRng.next_u64());
+    }
+}
+
+/// Returns the state of the bracket after replaying the event log up to the event with the
+/// given id.
+async fn replay(
+    ctx: Context,
+    tournament_id: TournamentId,
+    id: BracketId,
+    event_id: EventId,
+) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let (_, tournament) = crate::replay::replay(&ctx.state.store, tournament_id, id, event_id)
+        .await
+        .map_404()?;
+
+    Ok(Response::ok().json(&tournament.into_matches()))
+}
+
+/// Rolls the bracket back to the state after the event with the given id. This also recovers
+/// brackets whose stored state cannot be loaded.
+async fn rollback(
+    ctx: Context,
+    tournament_id: TournamentId,
+    id: BracketId,
+    event_id: EventId,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let current = ctx
+        .state
+        .store
+        .get_bracket(tournament_id, id)
+        .await
+        .map_404()?;
+
+    let (bracket, tournament) =
+        crate::replay::replay(&ctx.state.store, tournament_id, id, event_id)
+            .await
+            .map_404()?;
+
+    // The matches of an earlier version of the bracket don't fit the current entrants or
+    // options.
+    if requires_rebuild(&bracket, &current) {
+        return Err(StatusCodeError::bad_request()
+            .message("cannot roll back to before the entrants or options of the bracket changed")
+            .into());
+    }
+
+    let matches = tournament.clone().into_matches();
+
+    // A bracket whose stored state cannot be loaded is recovered by storing the new state.
+    match ctx.state.live_brackets.get(tournament_id, id).await {
+        Ok(mut live_bracket) => {
+            live_bracket.set_user_id(Some(author));
+            live_bracket.replace(tournament).await?;
+        }
+        Err(err) => {
+            log::warn!("Failed to load bracket {}, recovering it: {}", id, err);
+            live_bracket::recover(&ctx.state.store, tournament_id, id, tournament).await?;
+        }
+    }
+
+    // Only logged once the state was stored, so the log never contains a failed rollback.
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::Rollback {
+                bracket_id: id,
+                event_id,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&matches))
+}
+
+async fn progress(ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result {
+    let bracket = ctx.state.live_brackets.get(tournament_id, id).await?;
+
+    let match_duration = ctx
+        .state
+        .store
+        .schedules(tournament_id)
+        .get(id)
+        .await?
+        .map(|schedule| schedule.config.match_duration)
+        .unwrap_or(DEFAULT_MATCH_DURATION);
+
+    Ok(Response::ok().json(&bracket.progress(match_duration)))
+}
+
+async fn rounds(ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result {
+    let bracket = ctx.state.live_brackets.get(tournament_id, id).await?;
+
+    Ok(Response::ok().json(&bracket.tournament().rounds()))
+}
+
+async fn prediction(ctx: Context, tournament_id: TournamentId, id: BracketId) -> Result {
+    let bracket = ctx
+        .state
+        .store
+        .get_bracket(tournament_id, id)
+        .await
+        .map_404()?;
+
+    let entrants = ctx.state.store.get_entrants(tournament_id).await?;
+
+    // Entrants without a (complete) rating use the default rating.
+    let ratings: Vec<f64> = bracket
+        .entrants
+        .iter()
+        .map(|id| {
+            entrants
+                .iter()
+                .find(|entrant| entrant.id == *id)
+                .and_then(|entrant| entrant.rating())
+                .map(|rating| rating as f64)
+                .unwrap_or(DEFAULT_RATING)
+        })
+        .collect();
+
+    let live_bracket = ctx.state.live_brackets.get(tournament_id, id).await?;
+
+    if let Some(prediction) = live_bracket.cached_prediction(&ratings) {
+        return Ok(Response::ok().json(&prediction));
+    }
+
+    let (generation, tournament) = live_bracket.tournament_with_generation();
+
+    // Simulating the bracket is expensive, don't block the runtime.
+    let simulation = Simulation::new(ratings.clone());
+    let prediction = tokio::task::spawn_blocking(move || simulation.run(&tournament)).await;
+
+    let prediction = match prediction {
+        Ok(prediction) => prediction,
+        Err(err) => {
+            log::error!("Failed to run bracket prediction: {}", err);
+            return Err(StatusCodeError::internal_server_error().into());
+        }
+    };
+
+    let body = Prediction {
+        iterations: prediction.iterations(),
+        entrants: bracket
+            .entrants
+            .iter()
+            .enumerate()
+            .map(|(index, entrant)| EntrantPrediction {
+                entrant: *entrant,
+                placements: prediction.placements(index),
+            })
+            .collect(),
+    };
+
+    live_bracket.cache_prediction(generation, ratings, body.clone());
+
+    Ok(Response::ok().json(&body))
+}
+
+async fn scenarios(ctx: Context, tournament_id: TournamentId, id: BracketId, top: usize) -> Result {
+    let bracket = ctx
+        .state
+        .store
+        .get_bracket(tournament_id, id)
+        .await
+        .map_404()?;
+
+    // Every `top` is cached separately, only allow values that can be meaningful.
+    if top > bracket.entrants.len() {
+        return Err(StatusCodeError::bad_request()
+            .message("top is larger than the number of entrants")
+            .into());
+    }
+
+    let live_bracket = ctx.state.live_brackets.get(tournament_id, id).await?;
+
+    if let Some(scenarios) = live_bracket.cached_scenarios(top) {
+        return Ok(Response::ok().json(&scenarios));
+    }
+
+    let (generation, tournament) = live_bracket.tournament_with_generation();
+
+    // Only systems with a ranking of all entrants have meaningful top positions.
+    match tournament.kind() {
+        TournamentKind::RoundRobin | TournamentKind::Swiss => (),
+        _ => {
+            return Err(StatusCodeError::bad_request()
+                .message("scenarios are only available for round robin and swiss brackets")
+                .into());
+        }
+    }
+
+    let rounds = tournament.rounds();
+
+    // Enumerating all outcomes is expensive, don't block the runtime.
+    let scenarios = tokio::task::spawn_blocking(move || Analysis::new(top).run(&tournament)).await;
+
+    let scenarios = match scenarios {
+        Ok(Some(scenarios)) => scenarios,
+        Ok(None) => {
+            return Err(StatusCodeError::bad_request()
+                .message("too many remaining matches to enumerate all outcomes")
+                .into());
+        }
+        Err(err) => {
+            log::error!("Failed to run bracket scenarios: {}", err);
+            return Err(StatusCodeError::internal_server_error().into());
+        }
+    };
+
+    let body = Scenarios {
+        top: scenarios.top(),
+        outcomes: scenarios.outcomes(),
+        entrants: bracket
+            .entrants
+            .iter()
+            .zip(scenarios.iter())
+            .map(|(entrant, scenarios)| EntrantScenarios {
+                entrant: *entrant,
+                status: scenarios.status,
+                advancing: scenarios.advancing,
+                requirements: scenarios
+                    .requirements
+                    .iter()
+                    .map(|requirement| Requirement {
+                        index: requirement.index,
+                        code: rounds.code(requirement.index).unwrap_or_default(),
+                        winner: bracket.entrants[requirement.winner],
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+
+    let response = Response::ok().json(&body);
+    live_bracket.cache_scenarios(generation, body);
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use dynamic_tournament_core::options::{OptionValue, TournamentOptionValues};
+
+    use super::generate_side_seed;
+
+    fn options(sides: &str) -> TournamentOptionValues {
+        let mut options = TournamentOptionValues::new();
+        options.set("sides", OptionValue::string(sides));
+        options
+    }
+
+    #[test]
+    fn test_generate_side_seed() {
+        let mut a = options("coin_flip");
+        let mut b = options("coin_flip");
+        generate_side_seed(&mut a);
+        generate_side_seed(&mut b);
+
+        assert!(a.get("side_seed").is_some());
+        assert_ne!(a.get("side_seed"), b.get("side_seed"));
+
+        // A given seed is kept.
+        let mut given = options("coin_flip");
+        given.set("side_seed", 1u64);
+        generate_side_seed(&mut given);
+        assert_eq!(given.get("side_seed"), Some(&OptionValue::U64(1)));
+
+        let mut higher_seed = options("higher_seed");
+        generate_side_seed(&mut higher_seed);
+        assert!(higher_seed.get("side_seed").is_none());
+    }
+}
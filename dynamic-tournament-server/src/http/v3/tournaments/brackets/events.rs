@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::matches::Response as Event;
+use dynamic_tournament_macros::{method, path};
+use futures::StreamExt;
+use hyper::body::{Bytes, Sender};
+use hyper::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use hyper::Body;
+use tokio::time::MissedTickBehavior;
+use tokio::{select, task, time};
+
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::websocket::live_bracket::{ChangeEvent, LiveBracket};
+
+/// The interval in which a comment is sent to keep idle connections open.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+pub async fn route(mut ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => serve(ctx, tournament_id, bracket_id).await,
+        }),
+    })
+}
+
+/// Streams all changes of the bracket as server-sent events.
+async fn serve(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    // An invalid `Last-Event-ID` is treated like a new connection.
+    let last_event_id = ctx
+        .req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+
+    let (sender, body) = Body::channel();
+
+    let state = ctx.state.clone();
+    task::spawn(async move {
+        let shutdown = state.shutdown.listen();
+
+        select! {
+            _ = stream(bracket, last_event_id, sender) => {}
+            _ = shutdown => {
+                log::debug!("Server shutdown, closing event stream");
+            }
+        }
+    });
+
+    Ok(Response::ok()
+        .header(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"))
+        .header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+        .body(body))
+}
+
+/// Writes the changes of the `bracket` to `sender` until the client disconnects.
+///
+/// If `last_event_id` is given and all changes after it are still available only these changes
+/// are sent. Otherwise the stream starts with a snapshot of the bracket.
+async fn stream(
+    bracket: LiveBracket,
+    last_event_id: Option<u64>,
+    mut sender: Sender,
+) -> std::result::Result<(), hyper::Error> {
+    // Subscribe before reading the history or snapshot so that no change is missed. Changes
+    // that are already included are skipped using their ids.
+    let mut changed = bracket.changed();
+
+    let resume = last_event_id.and_then(|id| bracket.changes_since(id).map(|events| (id, events)));
+
+    let mut last_id = match resume {
+        Some((id, events)) => {
+            let mut last_id = id;
+            for event in events {
+                last_id = event.id;
+                send_change(&mut sender, event).await?;
+            }
+
+            last_id
+        }
+        None => send_snapshot(&mut sender, &bracket).await?,
+    };
+
+    let mut keep_alive = time::interval(KEEP_ALIVE_INTERVAL);
+    keep_alive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        select! {
+            res = changed.next() => {
+                match res {
+                    Some(Ok(event)) => {
+                        if event.id <= last_id {
+                            continue;
+                        }
+
+                        last_id = event.id;
+                        send_change(&mut sender, event).await?;
+                    }
+                    // The stream lagged behind, start again using a new snapshot.
+                    Some(Err(_)) => {
+                        last_id = send_snapshot(&mut sender, &bracket).await?;
+                    }
+                    None => return Ok(()),
+                }
+            }
+            _ = keep_alive.tick() => {
+                sender.send_data(Bytes::from_static(b":\n\n")).await?;
+            }
+        }
+    }
+}
+
+/// Sends the complete state of the bracket as a [`Event::SyncState`] event. Returns the id of
+/// the event.
+async fn send_snapshot(
+    sender: &mut Sender,
+    bracket: &LiveBracket,
+) -> std::result::Result<u64, hyper::Error> {
+    let (id, matches, revisions) = bracket.snapshot();
+
+    send_event(sender, id, &Event::SyncState(matches, revisions)).await?;
+    Ok(id)
+}
+
+async fn send_change(
+    sender: &mut Sender,
+    event: ChangeEvent,
+) -> std::result::Result<(), hyper::Error> {
+    send_event(sender, event.id, &Event::from(event.change)).await
+}
+
+async fn send_event(
+    sender: &mut Sender,
+    id: u64,
+    event: &Event,
+) -> std::result::Result<(), hyper::Error> {
+    let data = serde_json::to_string(event).unwrap();
+
+    sender
+        .send_data(Bytes::from(format!("id: {}\ndata: {}\n\n", id, data)))
+        .await
+}
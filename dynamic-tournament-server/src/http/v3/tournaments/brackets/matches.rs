@@ -1,7 +1,15 @@
-use crate::http::{Context, Response, Result};
-use crate::StatusCodeError;
+use std::hash::{Hash, Hasher};
 
+use crate::http::etag::{Etag, HashEtag};
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::{compare_etag, StatusCodeError};
+
+use dynamic_tournament_api::auth::Flags;
 use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::matches::{
+    ErrorResponse, MatchMetadata, MatchState,
+};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
 use dynamic_tournament_macros::{method, path};
 use hyper::header::{
     HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION,
@@ -15,10 +23,223 @@ pub async fn route(mut ctx: Context, id: TournamentId, bracket_id: BracketId) ->
     path!(ctx, {
         @ => method!(ctx, {
             GET => serve(ctx, id, bracket_id).await,
-        })
+        }),
+        "metadata" => path!(ctx, {
+            @ => method!(ctx, {
+                GET => list_metadata(ctx, id, bracket_id).await,
+            }),
+        }),
+        index => path!(ctx, {
+            @ => method!(ctx, {
+                GET => get_metadata(ctx, id, bracket_id, index).await,
+                PUT => update_metadata(ctx, id, bracket_id, index).await,
+                DELETE => delete_metadata(ctx, id, bracket_id, index).await,
+            }),
+            "state" => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => get_state(ctx, id, bracket_id, index).await,
+                    PUT => update_state(ctx, id, bracket_id, index).await,
+                }),
+            }),
+        }),
     })
 }
 
+async fn list_metadata(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let metadata = ctx
+        .state
+        .store
+        .match_metadata(tournament_id)
+        .list(bracket_id)
+        .await?;
+
+    Ok(Response::ok().json(&metadata))
+}
+
+async fn get_metadata(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let metadata = ctx
+        .state
+        .store
+        .match_metadata(tournament_id)
+        .get(bracket_id, index)
+        .await
+        .map_404()?;
+
+    Ok(Response::ok().json(&metadata))
+}
+
+async fn update_metadata(
+    mut ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::EDIT_SCORES)?;
+
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let mut metadata: MatchMetadata = ctx.req.json().await?;
+    metadata.index = index;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    if index >= bracket.matches().len() {
+        return Err(StatusCodeError::not_found().into());
+    }
+
+    let before = ctx
+        .state
+        .store
+        .match_metadata(tournament_id)
+        .get(bracket_id, index)
+        .await?;
+
+    bracket.set_metadata(index, Some(metadata.clone())).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateMatchMetadata {
+                bracket_id,
+                index: index as u64,
+                before,
+                after: Some(metadata.clone()),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&metadata))
+}
+
+async fn delete_metadata(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::EDIT_SCORES)?;
+
+    let metadata = ctx
+        .state
+        .store
+        .match_metadata(tournament_id)
+        .get(bracket_id, index)
+        .await
+        .map_404()?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    bracket.set_metadata(index, None).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateMatchMetadata {
+                bracket_id,
+                index: index as u64,
+                before: Some(metadata),
+                after: None,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn get_state(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let state = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?
+        .match_state(index)
+        .map_404()?;
+
+    let etag = Etag::new(&state);
+    compare_etag!(ctx, etag);
+
+    Ok(Response::ok().etag(etag).json(&state))
+}
+
+async fn update_state(
+    mut ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::EDIT_SCORES)?;
+
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let mut bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    bracket.set_user_id(Some(author));
+
+    let state = bracket.match_state(index).map_404()?;
+
+    let etag = Etag::new(&state);
+    compare_etag!(ctx, etag);
+
+    let body: MatchState = ctx.req.json().await?;
+
+    // The match may have changed since the etag was compared. The revision guarantees that
+    // we never overwrite a concurrent update.
+    let revision = match bracket.update(index as u64, state.revision, body.nodes) {
+        Ok(revision) => revision,
+        Err(ErrorResponse::Conflict(_)) => {
+            return Err(StatusCodeError::precondition_failed().into())
+        }
+        Err(_) => return Err(StatusCodeError::not_found().into()),
+    };
+
+    let state = MatchState {
+        index: index as u64,
+        revision,
+        nodes: body.nodes,
+    };
+
+    let etag = Etag::new(&state);
+    Ok(Response::ok().etag(etag).json(&state))
+}
+
 async fn serve(ctx: Context, id: TournamentId, bracket_id: BracketId) -> Result {
     // Check that the tournament and bracket exist.
     if ctx.state.store.get_tournament(id).await?.is_none()
@@ -69,3 +290,23 @@ async fn serve(ctx: Context, id: TournamentId, bracket_id: BracketId) -> Result
 
     Ok(resp)
 }
+
+impl HashEtag for MatchState {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        let Self {
+            index,
+            revision,
+            nodes,
+        } = self;
+
+        index.hash(state);
+        revision.hash(state);
+        for node in nodes {
+            node.score.hash(state);
+            node.winner.hash(state);
+        }
+    }
+}
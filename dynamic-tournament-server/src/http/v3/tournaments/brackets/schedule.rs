@@ -0,0 +1,279 @@
+use chrono::Duration;
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::schedule::{
+    Schedule, ScheduleConfig, ScheduleOverride, ScheduleStatus, ScheduledMatch,
+};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_macros::{method, path};
+
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::StatusCodeError;
+
+/// The largest accepted match duration and rest time in seconds (one week).
+const MAX_DURATION: u64 = 7 * 24 * 60 * 60;
+
+pub async fn route(mut ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => get(ctx, tournament_id, bracket_id).await,
+            PUT => update(ctx, tournament_id, bracket_id).await,
+            DELETE => delete(ctx, tournament_id, bracket_id).await,
+        }),
+        index => path!(ctx, {
+            @ => method!(ctx, {
+                PUT => set_override(ctx, tournament_id, bracket_id, index).await,
+                DELETE => delete_override(ctx, tournament_id, bracket_id, index).await,
+            }),
+        }),
+    })
+}
+
+async fn get(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let schedule = ctx
+        .state
+        .store
+        .schedules(tournament_id)
+        .get(bracket_id)
+        .await
+        .map_404()?;
+
+    Ok(Response::ok().json(&schedule))
+}
+
+async fn update(mut ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let config: ScheduleConfig = ctx.req.json().await?;
+
+    if config.stations.is_empty() {
+        return Err(StatusCodeError::bad_request()
+            .message("schedule requires at least one station")
+            .into());
+    }
+
+    if config.match_duration == 0 || config.match_duration > MAX_DURATION {
+        return Err(StatusCodeError::bad_request()
+            .message(format!(
+                "match duration must be between 1 and {} seconds",
+                MAX_DURATION
+            ))
+            .into());
+    }
+
+    if config.rest > MAX_DURATION {
+        return Err(StatusCodeError::bad_request()
+            .message(format!("rest must be at most {} seconds", MAX_DURATION))
+            .into());
+    }
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    let _guard = bracket.lock_schedule().await;
+
+    let schedules = ctx.state.store.schedules(tournament_id);
+
+    // Keep finished matches and overrides of an existing schedule.
+    let before = schedules.get(bracket_id).await?;
+    let mut schedule = match before.clone() {
+        Some(mut schedule) => {
+            schedule.config = config;
+            schedule
+        }
+        None => Schedule {
+            config,
+            matches: Vec::new(),
+        },
+    };
+
+    // Overrides on a removed station are dropped.
+    for slot in schedule.matches.iter_mut() {
+        if let Some(s) = slot {
+            if s.station >= schedule.config.stations.len() {
+                *slot = None;
+            }
+        }
+    }
+
+    bracket.plan(&mut schedule);
+
+    schedules.insert(bracket_id, &schedule).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateSchedule {
+                bracket_id,
+                before,
+                after: Some(schedule.clone()),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&schedule))
+}
+
+async fn delete(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    let _guard = bracket.lock_schedule().await;
+
+    let schedules = ctx.state.store.schedules(tournament_id);
+    let schedule = schedules.get(bracket_id).await.map_404()?;
+
+    schedules.delete(bracket_id).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateSchedule {
+                bracket_id,
+                before: Some(schedule),
+                after: None,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn set_override(
+    mut ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    let _guard = bracket.lock_schedule().await;
+
+    let schedules = ctx.state.store.schedules(tournament_id);
+    let mut schedule = schedules.get(bracket_id).await.map_404()?;
+    let before = Some(schedule.clone());
+
+    let len = bracket.matches().len();
+    if index >= len {
+        return Err(StatusCodeError::not_found().into());
+    }
+
+    let slot: ScheduleOverride = ctx.req.json().await?;
+
+    if slot.station >= schedule.config.stations.len() {
+        return Err(StatusCodeError::bad_request()
+            .message(format!("invalid station {}", slot.station))
+            .into());
+    }
+
+    let end = match slot.end {
+        Some(end) => end,
+        None => slot
+            .start
+            .checked_add_signed(Duration::seconds(
+                schedule.config.match_duration.min(MAX_DURATION) as i64,
+            ))
+            .ok_or_else(|| StatusCodeError::bad_request().message("slot start out of range"))?,
+    };
+
+    if end < slot.start {
+        return Err(StatusCodeError::bad_request()
+            .message("slot cannot end before it starts")
+            .into());
+    }
+
+    schedule.matches.resize(len, None);
+    schedule.matches[index] = Some(ScheduledMatch {
+        start: slot.start,
+        end,
+        station: slot.station,
+        status: ScheduleStatus::Override,
+    });
+
+    bracket.plan(&mut schedule);
+    schedules.insert(bracket_id, &schedule).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateSchedule {
+                bracket_id,
+                before,
+                after: Some(schedule.clone()),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&schedule))
+}
+
+async fn delete_override(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+    let _guard = bracket.lock_schedule().await;
+
+    let schedules = ctx.state.store.schedules(tournament_id);
+    let mut schedule = schedules.get(bracket_id).await.map_404()?;
+    let before = Some(schedule.clone());
+
+    // Remove the slot completely so the match is planned again from scratch.
+    match schedule.matches.get_mut(index) {
+        Some(slot) => match slot {
+            Some(s) if s.status == ScheduleStatus::Override => *slot = None,
+            _ => return Err(StatusCodeError::not_found().into()),
+        },
+        None => return Err(StatusCodeError::not_found().into()),
+    }
+
+    bracket.plan(&mut schedule);
+
+    schedules.insert(bracket_id, &schedule).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateSchedule {
+                bracket_id,
+                before,
+                after: Some(schedule.clone()),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&schedule))
+}
@@ -0,0 +1,272 @@
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::veto::{MatchVeto, Veto, VetoConfig};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_core::sides::Side;
+use dynamic_tournament_core::System;
+use dynamic_tournament_macros::{method, path};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::StatusCodeError;
+
+pub async fn route(mut ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => get_config(ctx, tournament_id, bracket_id).await,
+            PUT => update_config(ctx, tournament_id, bracket_id).await,
+            DELETE => delete_config(ctx, tournament_id, bracket_id).await,
+        }),
+        "matches" => path!(ctx, {
+            @ => method!(ctx, {
+                GET => list(ctx, tournament_id, bracket_id).await,
+            }),
+            index => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => get(ctx, tournament_id, bracket_id, index).await,
+                    POST => start(ctx, tournament_id, bracket_id, index).await,
+                    DELETE => delete(ctx, tournament_id, bracket_id, index).await,
+                }),
+            }),
+        }),
+    })
+}
+
+async fn get_config(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let config = ctx
+        .state
+        .store
+        .vetoes(tournament_id)
+        .config(bracket_id)
+        .await
+        .map_404()?;
+
+    Ok(Response::ok().json(&config))
+}
+
+async fn update_config(
+    mut ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    ctx.state
+        .store
+        .get_bracket(tournament_id, bracket_id)
+        .await
+        .map_404()?;
+
+    let config: VetoConfig = ctx.req.json().await?;
+
+    if let Err(err) = config.validate() {
+        return Err(StatusCodeError::bad_request()
+            .message(err.to_string())
+            .into());
+    }
+
+    let vetoes = ctx.state.store.vetoes(tournament_id);
+    let before = vetoes.config(bracket_id).await?;
+
+    // Vetoes that were already started keep the config they were started with.
+    vetoes.set_config(bracket_id, &config).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateVetoConfig {
+                bracket_id,
+                before,
+                after: Some(config.clone()),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&config))
+}
+
+async fn delete_config(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let vetoes = ctx.state.store.vetoes(tournament_id);
+    let config = vetoes.config(bracket_id).await.map_404()?;
+
+    vetoes.delete_config(bracket_id).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateVetoConfig {
+                bracket_id,
+                before: Some(config),
+                after: None,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn list(ctx: Context, tournament_id: TournamentId, bracket_id: BracketId) -> Result {
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    // Never expose the keys of the entrants.
+    let vetoes: Vec<MatchVeto> = bracket
+        .vetoes()
+        .into_iter()
+        .map(|veto| MatchVeto { keys: None, ..veto })
+        .collect();
+
+    Ok(Response::ok().json(&vetoes))
+}
+
+async fn get(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    let Some(veto) = bracket.veto(index) else {
+        return Err(StatusCodeError::not_found().into());
+    };
+
+    Ok(Response::ok().json(&MatchVeto { keys: None, ..veto }))
+}
+
+async fn start(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let Some(config) = ctx
+        .state
+        .store
+        .vetoes(tournament_id)
+        .config(bracket_id)
+        .await?
+    else {
+        return Err(StatusCodeError::bad_request()
+            .message("bracket has no veto config")
+            .into());
+    };
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    if index >= bracket.matches().len() {
+        return Err(StatusCodeError::not_found().into());
+    }
+
+    // The entrant playing on the home side starts the veto.
+    let Some(sides) = bracket.tournament().sides().get(index) else {
+        return Err(StatusCodeError::bad_request()
+            .message("match is not ready")
+            .into());
+    };
+    let first = sides.iter().position(|side| *side == Side::Home).unwrap();
+
+    let veto = match Veto::new(config, first) {
+        Ok(veto) => veto,
+        Err(err) => {
+            return Err(StatusCodeError::bad_request()
+                .message(err.to_string())
+                .into())
+        }
+    };
+
+    let veto = MatchVeto {
+        index,
+        veto,
+        keys: Some([generate_key(), generate_key()]),
+    };
+
+    let before = bracket.veto(index);
+    bracket.set_veto(index, Some(veto.clone())).await?;
+
+    // The keys of the entrants are never written to the log.
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateMatchVeto {
+                bracket_id,
+                index: index as u64,
+                before: before.map(|veto| MatchVeto { keys: None, ..veto }),
+                after: Some(MatchVeto {
+                    keys: None,
+                    ..veto.clone()
+                }),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok().json(&veto))
+}
+
+async fn delete(
+    ctx: Context,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let bracket = ctx
+        .state
+        .live_brackets
+        .get(tournament_id, bracket_id)
+        .await?;
+
+    let Some(veto) = bracket.veto(index) else {
+        return Err(StatusCodeError::not_found().into());
+    };
+
+    bracket.set_veto(index, None).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::UpdateMatchVeto {
+                bracket_id,
+                index: index as u64,
+                before: Some(MatchVeto { keys: None, ..veto }),
+                after: None,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+fn generate_key() -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect()
+}
@@ -3,6 +3,7 @@ use std::hash::{Hash, Hasher};
 use dynamic_tournament_api::auth::Flags;
 use dynamic_tournament_api::v3::id::{EntrantId, TournamentId};
 use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant, Player, Team};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
 use dynamic_tournament_api::Payload;
 use dynamic_tournament_macros::{method, path};
 
@@ -48,7 +49,7 @@ async fn get(ctx: Context, tournament_id: TournamentId, id: EntrantId) -> Result
 }
 
 async fn create(mut ctx: Context, tournament_id: TournamentId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let tournament = ctx
         .state
@@ -100,13 +101,28 @@ async fn create(mut ctx: Context, tournament_id: TournamentId) -> Result {
             .entrants(tournament_id)
             .insert(entrant)
             .await?;
+
+        let body = LogEventBody::CreateEntrant {
+            id: entrant.id,
+            entrant: entrant.clone(),
+        };
+
+        ctx.state.webhooks.dispatch(tournament_id, author, &body);
+        ctx.state
+            .store
+            .event_log(tournament_id)
+            .push(author, body)
+            .await?;
     }
 
     Ok(Response::created().json(&entrants))
 }
 
 async fn delete(ctx: Context, tournament_id: TournamentId, id: EntrantId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    // Ratings are written to the entrant concurrently.
+    let _guard = ctx.state.store.lock_entrants().await;
 
     let entrant = ctx
         .state
@@ -120,11 +136,23 @@ async fn delete(ctx: Context, tournament_id: TournamentId, id: EntrantId) -> Res
 
     ctx.state.store.entrants(tournament_id).delete(id).await?;
 
+    let body = LogEventBody::DeleteEntrant { id, entrant };
+
+    ctx.state.webhooks.dispatch(tournament_id, author, &body);
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(author, body)
+        .await?;
+
     Ok(Response::ok())
 }
 
 async fn patch(mut ctx: Context, tournament_id: TournamentId, id: EntrantId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    // Ratings are written to the entrant concurrently.
+    let _guard = ctx.state.store.lock_entrants().await;
 
     let entrant = ctx
         .state
@@ -136,7 +164,9 @@ async fn patch(mut ctx: Context, tournament_id: TournamentId, id: EntrantId) ->
     let etag = Etag::new(&entrant);
     compare_etag!(ctx, etag);
 
-    let mut entrant = ctx.req.json().await?;
+    let before = entrant;
+
+    let mut entrant: Entrant = ctx.req.json().await?;
     ctx.state
         .store
         .entrants(tournament_id)
@@ -145,6 +175,19 @@ async fn patch(mut ctx: Context, tournament_id: TournamentId, id: EntrantId) ->
 
     entrant.id = id;
 
+    let body = LogEventBody::UpdateEntrant {
+        id,
+        before,
+        after: entrant.clone(),
+    };
+
+    ctx.state.webhooks.dispatch(tournament_id, author, &body);
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(author, body)
+        .await?;
+
     Ok(Response::ok().json(&entrant))
 }
 
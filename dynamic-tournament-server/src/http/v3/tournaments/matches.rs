@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use dynamic_tournament_api::v3::id::TournamentId;
+use dynamic_tournament_api::v3::tournaments::matches::{
+    EntrantNextMatch, PlayableMatch, UpcomingMatches,
+};
+use dynamic_tournament_core::{EntrantSpot, System};
+use dynamic_tournament_macros::{method, path};
+
+use crate::http::{Context, Response, Result};
+
+pub async fn route(mut ctx: Context, id: TournamentId) -> Result {
+    path!(ctx, {
+        "next" => path!(ctx, {
+            @ => method!(ctx, {
+                GET => next(ctx, id).await,
+            }),
+        }),
+    })
+}
+
+async fn next(ctx: Context, id: TournamentId) -> Result {
+    let brackets = ctx.state.store.list_brackets(id).await?;
+
+    let mut upcoming_matches = Vec::with_capacity(brackets.len());
+    for bracket in brackets {
+        let tournament = ctx
+            .state
+            .live_brackets
+            .get(id, bracket.id)
+            .await?
+            .tournament();
+
+        let mut metadata: HashMap<usize, _> = ctx
+            .state
+            .store
+            .match_metadata(id)
+            .list(bracket.id)
+            .await?
+            .into_iter()
+            .map(|metadata| (metadata.index, metadata))
+            .collect();
+
+        let upcoming = tournament.upcoming();
+        let rounds = tournament.rounds();
+
+        let playable = upcoming
+            .playable()
+            .iter()
+            .map(|index| {
+                let entrants = tournament.matches()[*index].map(|spot| match spot {
+                    EntrantSpot::Entrant(node) => tournament.entrants()[node.index],
+                    _ => unreachable!(),
+                });
+
+                PlayableMatch {
+                    index: *index,
+                    code: rounds.code(*index).unwrap_or_default(),
+                    entrants,
+                    metadata: metadata.remove(index),
+                }
+            })
+            .collect();
+
+        let entrants = tournament
+            .entrants()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entrant)| {
+                upcoming.next_match(index).map(|index| EntrantNextMatch {
+                    entrant: *entrant,
+                    index,
+                })
+            })
+            .collect();
+
+        upcoming_matches.push(UpcomingMatches {
+            bracket_id: bracket.id,
+            playable,
+            blocked: upcoming.blocked().to_vec(),
+            entrants,
+        });
+    }
+
+    Ok(Response::ok().json(&upcoming_matches))
+}
@@ -0,0 +1,96 @@
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::id::TournamentId;
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_api::v3::tournaments::ratings::RatingSystem;
+use dynamic_tournament_macros::{method, path};
+
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::StatusCodeError;
+
+pub async fn route(mut ctx: Context, id: TournamentId) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => get(ctx, id).await,
+            PUT => update(ctx, id).await,
+            DELETE => delete(ctx, id).await,
+        }),
+        "history" => path!(ctx, {
+            @ => method!(ctx, {
+                GET => history(ctx, id).await,
+            }),
+        }),
+    })
+}
+
+async fn get(ctx: Context, id: TournamentId) -> Result {
+    let system = ctx.state.store.ratings(id).system().await.map_404()?;
+
+    Ok(Response::ok().json(&system))
+}
+
+async fn update(mut ctx: Context, id: TournamentId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    ctx.state.store.get_tournament(id).await.map_404()?;
+
+    let system: RatingSystem = ctx.req.json().await?;
+
+    let valid = match system {
+        RatingSystem::Elo { k } => k.is_finite() && k > 0.0,
+        RatingSystem::Glicko2 { tau } => tau.is_finite() && tau > 0.0,
+    };
+
+    if !valid {
+        return Err(StatusCodeError::bad_request()
+            .message("rating system parameters must be positive")
+            .into());
+    }
+
+    let ratings = ctx.state.store.ratings(id);
+    let before = ratings.system().await?;
+
+    ratings.set_system(&system).await?;
+
+    ctx.state
+        .store
+        .event_log(id)
+        .push(
+            author,
+            LogEventBody::UpdateRatingSystem {
+                before,
+                after: Some(system),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn delete(ctx: Context, id: TournamentId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let ratings = ctx.state.store.ratings(id);
+    let system = ratings.system().await.map_404()?;
+
+    ratings.delete_system().await?;
+
+    ctx.state
+        .store
+        .event_log(id)
+        .push(
+            author,
+            LogEventBody::UpdateRatingSystem {
+                before: Some(system),
+                after: None,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn history(ctx: Context, id: TournamentId) -> Result {
+    let history = ctx.state.store.ratings(id).history().await?;
+
+    Ok(Response::ok().json(&history))
+}
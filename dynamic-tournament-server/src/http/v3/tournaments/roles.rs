@@ -1,10 +1,11 @@
 use dynamic_tournament_api::auth::Flags;
 use dynamic_tournament_api::v3::id::{RoleId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
 use dynamic_tournament_api::v3::tournaments::roles::Role;
 use dynamic_tournament_api::Payload;
 use dynamic_tournament_macros::{method, path};
 
-use crate::http::{Context, Response, Result};
+use crate::http::{Context, HttpResult, Response, Result};
 use crate::StatusCodeError;
 
 pub async fn route(mut ctx: Context, tournament_id: TournamentId) -> Result {
@@ -36,21 +37,47 @@ async fn get(ctx: Context, tournament_id: TournamentId, id: RoleId) -> Result {
 }
 
 async fn create(mut ctx: Context, tournament_id: TournamentId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
 
     let mut roles: Payload<Role> = ctx.req.json().await?;
 
     for role in roles.iter_mut() {
         role.id = ctx.state.store.roles(tournament_id).insert(role).await?;
+
+        ctx.state
+            .store
+            .event_log(tournament_id)
+            .push(
+                author,
+                LogEventBody::CreateRole {
+                    id: role.id,
+                    role: role.clone(),
+                },
+            )
+            .await?;
     }
 
     Ok(Response::created().json(&roles))
 }
 
 async fn delete(ctx: Context, tournament_id: TournamentId, id: RoleId) -> Result {
-    ctx.require_authentication(Flags::ADMIN)?;
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let role = ctx
+        .state
+        .store
+        .roles(tournament_id)
+        .get(id)
+        .await
+        .map_404()?;
 
     ctx.state.store.roles(tournament_id).delete(id).await?;
 
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(author, LogEventBody::DeleteRole { id, role })
+        .await?;
+
     Ok(Response::ok())
 }
@@ -0,0 +1,170 @@
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::id::{TournamentId, WebhookId};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_api::v3::tournaments::webhooks::Webhook;
+use dynamic_tournament_api::Payload;
+use dynamic_tournament_macros::{method, path};
+use hyper::Uri;
+
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::{Error, StatusCodeError};
+
+pub async fn route(mut ctx: Context, tournament_id: TournamentId) -> Result {
+    path!(ctx, {
+        @ => method!(ctx, {
+            GET => list(ctx, tournament_id).await,
+            POST => create(ctx, tournament_id).await,
+        }),
+        id => path!(ctx, {
+            @ => method!(ctx, {
+                GET => get(ctx, tournament_id, id).await,
+                DELETE => delete(ctx, tournament_id, id).await,
+            }),
+            "deliveries" => path!(ctx, {
+                @ => method!(ctx, {
+                    GET => deliveries(ctx, tournament_id, id).await,
+                }),
+            }),
+        }),
+    })
+}
+
+async fn list(ctx: Context, tournament_id: TournamentId) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let webhooks: Vec<Webhook> = ctx
+        .state
+        .store
+        .webhooks(tournament_id)
+        .list()
+        .await?
+        .into_iter()
+        .map(without_secret)
+        .collect();
+
+    Ok(Response::ok().json(&webhooks))
+}
+
+async fn get(ctx: Context, tournament_id: TournamentId, id: WebhookId) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let webhook = ctx
+        .state
+        .store
+        .webhooks(tournament_id)
+        .get(id)
+        .await
+        .map_404()?;
+
+    Ok(Response::ok().json(&without_secret(webhook)))
+}
+
+async fn create(mut ctx: Context, tournament_id: TournamentId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let mut webhooks: Payload<Webhook> = ctx.req.json().await?;
+
+    for webhook in webhooks.iter() {
+        validate(webhook)?;
+    }
+
+    for webhook in webhooks.iter_mut() {
+        webhook.id = ctx
+            .state
+            .store
+            .webhooks(tournament_id)
+            .insert(webhook)
+            .await?;
+
+        *webhook = without_secret(webhook.clone());
+
+        ctx.state
+            .store
+            .event_log(tournament_id)
+            .push(
+                author,
+                LogEventBody::CreateWebhook {
+                    id: webhook.id,
+                    webhook: webhook.clone(),
+                },
+            )
+            .await?;
+    }
+
+    Ok(Response::created().json(&webhooks))
+}
+
+async fn delete(ctx: Context, tournament_id: TournamentId, id: WebhookId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let webhook = ctx
+        .state
+        .store
+        .webhooks(tournament_id)
+        .get(id)
+        .await
+        .map_404()?;
+
+    ctx.state.store.webhooks(tournament_id).delete(id).await?;
+
+    ctx.state
+        .store
+        .event_log(tournament_id)
+        .push(
+            author,
+            LogEventBody::DeleteWebhook {
+                id,
+                webhook: without_secret(webhook),
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn deliveries(ctx: Context, tournament_id: TournamentId, id: WebhookId) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let webhooks = ctx.state.store.webhooks(tournament_id);
+
+    webhooks.get(id).await.map_404()?;
+    let deliveries = webhooks.deliveries(id).await?;
+
+    Ok(Response::ok().json(&deliveries))
+}
+
+/// Removes the signing secret of the `webhook`. The secret is never returned or written to the
+/// log.
+fn without_secret(webhook: Webhook) -> Webhook {
+    Webhook {
+        secret: String::new(),
+        ..webhook
+    }
+}
+
+fn validate(webhook: &Webhook) -> std::result::Result<(), Error> {
+    let valid_url = match webhook.url.parse::<Uri>() {
+        Ok(uri) => matches!(uri.scheme_str(), Some("http" | "https")) && uri.host().is_some(),
+        Err(_) => false,
+    };
+
+    if !valid_url {
+        return Err(StatusCodeError::bad_request()
+            .message("url must be a http or https url")
+            .into());
+    }
+
+    if webhook.secret.is_empty() {
+        return Err(StatusCodeError::bad_request()
+            .message("secret must not be empty")
+            .into());
+    }
+
+    if webhook.events.is_empty() {
+        return Err(StatusCodeError::bad_request()
+            .message("events must not be empty")
+            .into());
+    }
+
+    Ok(())
+}
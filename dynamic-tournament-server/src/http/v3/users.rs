@@ -1,10 +1,13 @@
 use dynamic_tournament_api::auth::Flags;
-use dynamic_tournament_api::v3::users::User;
+use dynamic_tournament_api::v3::id::UserId;
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_api::v3::users::{ChangePassword, PartialUser, User};
 use dynamic_tournament_macros::{method, path};
 use snowflaked::sync::Generator;
 
 use crate::auth::password_hash;
-use crate::http::{Context, Response, Result};
+use crate::http::{Context, HttpResult, Response, Result};
+use crate::{Error, StatusCodeError};
 
 pub static USER_ID_GENERATOR: Generator = Generator::new_unchecked(0);
 
@@ -12,27 +15,230 @@ pub async fn route(mut ctx: Context) -> Result {
     path!(ctx, {
         @ => {
             method!(ctx, {
+                GET => list(ctx).await,
                 POST => create(ctx).await,
             })
-        }
+        },
+        "me" => path!(ctx, {
+            "password" => method!(ctx, {
+                POST => change_password(ctx).await,
+            }),
+        }),
+        id => method!(ctx, {
+            GET => get(ctx, id).await,
+            PATCH => patch(ctx, id).await,
+            DELETE => delete(ctx, id).await,
+        }),
     })
 }
 
-async fn create(mut ctx: Context) -> Result {
+async fn list(ctx: Context) -> Result {
+    ctx.require_authentication(Flags::ADMIN)?;
+
+    let mut users = ctx.state.store.users().list().await?;
+    for user in &mut users {
+        user.password.clear();
+    }
+
+    Ok(Response::ok().json(&users))
+}
+
+async fn get(ctx: Context, id: UserId) -> Result {
     ctx.require_authentication(Flags::ADMIN)?;
 
+    let mut user = ctx.state.store.users().get_by_id(id).await.map_404()?;
+    user.password.clear();
+
+    Ok(Response::ok().json(&user))
+}
+
+async fn create(mut ctx: Context) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
     let mut user: User = ctx.req.json().await?;
 
+    validate_username(&ctx, &user.username, None).await?;
+    validate_password(&user.password)?;
+
     user.id.0 = USER_ID_GENERATOR.generate();
     apply_hash(&mut user);
 
     ctx.state.store.users().insert(&user).await?;
+    ctx.state.auth.insert_user(user.id.0);
+
+    ctx.state
+        .store
+        .system_log()
+        .push(
+            author,
+            LogEventBody::CreateUser {
+                id: user.id,
+                username: user.username,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+async fn patch(mut ctx: Context, id: UserId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    let mut user = ctx.state.store.users().get_by_id(id).await.map_404()?;
+
+    let partial: PartialUser = ctx.req.json().await?;
+
+    let before = user.username.clone();
+
+    if let Some(username) = partial.username {
+        validate_username(&ctx, &username, Some(id)).await?;
+        user.username = username;
+    }
+
+    if let Some(password) = partial.password {
+        validate_password(&password)?;
+        user.password = password;
+        apply_hash(&mut user);
+    }
+
+    ctx.state.store.users().update(&user).await?;
+    revoke_tokens(&ctx, id).await?;
+
+    ctx.state
+        .store
+        .system_log()
+        .push(
+            author,
+            LogEventBody::UpdateUser {
+                id,
+                before,
+                after: user.username.clone(),
+            },
+        )
+        .await?;
+
+    user.password.clear();
+    Ok(Response::ok().json(&user))
+}
+
+async fn delete(ctx: Context, id: UserId) -> Result {
+    let author = ctx.require_authentication(Flags::ADMIN)?;
+
+    if author == id.0 {
+        return Err(StatusCodeError::bad_request()
+            .message("cannot delete the authenticated user")
+            .into());
+    }
+
+    let user = ctx.state.store.users().get_by_id(id).await.map_404()?;
+
+    ctx.state.store.users().delete(id).await?;
+    ctx.state.auth.remove_user(id.0);
+
+    ctx.state
+        .store
+        .system_log()
+        .push(
+            author,
+            LogEventBody::DeleteUser {
+                id,
+                username: user.username,
+            },
+        )
+        .await?;
+
+    Ok(Response::ok())
+}
+
+/// Changes the password of the authenticated user. The current password is required.
+async fn change_password(mut ctx: Context) -> Result {
+    let id = UserId(ctx.require_authentication(Flags::EMPTY)?);
+
+    let mut user = match ctx.state.store.users().get_by_id(id).await? {
+        Some(user) => user,
+        None => return Err(StatusCodeError::unauthorized().into()),
+    };
+
+    let body: ChangePassword = ctx.req.json().await?;
+
+    if password_hash(body.password, id.0.to_le_bytes()) != user.password {
+        return Err(StatusCodeError::forbidden()
+            .message("invalid password")
+            .into());
+    }
+
+    validate_password(&body.new_password)?;
+
+    let username = user.username.clone();
+
+    user.password = body.new_password;
+    apply_hash(&mut user);
+
+    ctx.state.store.users().update(&user).await?;
+    revoke_tokens(&ctx, id).await?;
+
+    ctx.state
+        .store
+        .system_log()
+        .push(
+            id.0,
+            LogEventBody::UpdateUser {
+                id,
+                before: username.clone(),
+                after: username,
+            },
+        )
+        .await?;
 
     Ok(Response::ok())
 }
 
+/// Revokes all tokens of the user with the given `id` and persists the revocation.
+async fn revoke_tokens(ctx: &Context, id: UserId) -> std::result::Result<(), Error> {
+    let valid_after = ctx.state.auth.revoke_tokens(id.0);
+
+    ctx.state
+        .store
+        .users()
+        .set_tokens_valid_after(id, valid_after)
+        .await
+}
+
 /// Apply the password hash to the user.
 /// See v2/auth.rs for hashing details.
 fn apply_hash(user: &mut User) {
     user.password = password_hash(&user.password, user.id.0.to_le_bytes());
 }
+
+/// Checks that the `username` is not empty and not used by any user other than `id`.
+async fn validate_username(
+    ctx: &Context,
+    username: &str,
+    id: Option<UserId>,
+) -> std::result::Result<(), Error> {
+    if username.is_empty() {
+        return Err(StatusCodeError::bad_request()
+            .message("username must not be empty")
+            .into());
+    }
+
+    if let Some(user) = ctx.state.store.users().get(username).await? {
+        if Some(user.id) != id {
+            return Err(StatusCodeError::bad_request()
+                .message("username is already taken")
+                .into());
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_password(password: &str) -> std::result::Result<(), Error> {
+    if password.is_empty() {
+        return Err(StatusCodeError::bad_request()
+            .message("password must not be empty")
+            .into());
+    }
+
+    Ok(())
+}
@@ -15,9 +15,12 @@ mod config;
 mod http;
 mod limits;
 mod logger;
+mod ratings;
+mod replay;
 mod signal;
 mod state;
 mod store;
+mod webhooks;
 mod websocket;
 
 #[cfg(feature = "metrics")]
@@ -61,76 +64,29 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     log::info!("Using config: {:?}", config);
 
-    let prefix = config.database.prefix.clone();
-    let state = State::new(config);
+    let state = match State::new(config) {
+        Ok(state) => state,
+        Err(err) => {
+            log::error!("Failed to connect to database: {}", err);
+            return Err(err.into());
+        }
+    };
+
+    // Bring the database schema up to date before accepting any requests.
+    if let Err(err) = state.store.migrate().await {
+        log::error!("Failed to migrate database schema: {}", err);
+        return Err(err.into());
+    }
+
+    if let Err(err) = state.auth.load(&state.store).await {
+        log::error!("Failed to load users: {}", err);
+        return Err(err.into());
+    }
 
     {
         let state = state.clone();
 
         tokio::task::spawn(async move {
-            let tables = [
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}tournaments (
-                        id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
-                        name TEXT NOT NULL,
-                        description TEXT NOT NULL,
-                        date TIMESTAMP NOT NULL,
-                        kind TINYINT UNSIGNED NOT NULL
-                    )",
-                    prefix
-                ),
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}entrants (
-                        id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
-                        tournament_id BIGINT UNSIGNED NOT NULL,
-                        data BLOB NOT NULL
-                    )",
-                    prefix
-                ),
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}brackets (
-                        id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
-                        tournament_id BIGINT UNSIGNED NOT NULL,
-                        data BLOB NOT NULL,
-                        state BLOB NOT NULL
-                    )",
-                    prefix
-                ),
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}roles (
-                        id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
-                        tournament_id BIGINT UNSIGNED NOT NULL,
-                        name TEXT NOT NULL
-                    )",
-                    prefix
-                ),
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}users (
-                        id BIGINT UNSIGNED PRIMARY KEY,
-                        name TEXT NOT NULL,
-                        password TEXT NOT NULL
-                    )",
-                    prefix
-                ),
-                format!(
-                    "CREATE TABLE IF NOT EXISTS {}log (
-                        id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
-                        tournament_id BIGINT UNSIGNED NOT NULL,
-                        date TIMESTAMP NOT NULL,
-                        author BIGINT UNSIGNED NOT NULL,
-                        data BLOB NOT NULL
-                    )",
-                    prefix
-                ),
-            ];
-
-            for t in tables {
-                if let Err(err) = sqlx::query(&t).execute(&state.store.pool).await {
-                    log::error!("Failed to create tables: {}", err);
-                    break;
-                }
-            }
-
             match state.store.users().get("admin").await {
                 Ok(Some(_)) => log::debug!("Admin user already exists"),
                 Ok(None) => {
@@ -139,7 +95,10 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
                     // Generate admin user if it doesn't exist.
                     let user = auth::generate_admin_user();
                     match state.store.users().insert(&user).await {
-                        Ok(()) => log::debug!("Admin user created"),
+                        Ok(()) => {
+                            state.auth.insert_user(user.id.0);
+                            log::debug!("Admin user created");
+                        }
                         Err(err) => {
                             log::error!("Failed to create admin user: {}", err);
                         }
@@ -150,6 +109,8 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    let store = state.store.clone();
+
     // Spawn the http server.
     tokio::task::spawn(async move {
         loop {
@@ -171,6 +132,11 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     });
 
     signal::await_shutdown().await;
+
+    if let Err(err) = store.persist().await {
+        log::error!("Failed to persist store: {}", err);
+    }
+
     Ok(())
 }
 
@@ -194,6 +160,8 @@ pub enum Error {
     BodyConsumed,
     #[error("invalid token")]
     InvalidToken,
+    #[error("database schema version {version} is newer than the latest known version {latest}")]
+    SchemaVersion { version: u64, latest: u64 },
 }
 
 #[derive(Debug, Error)]
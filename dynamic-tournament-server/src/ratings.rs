@@ -0,0 +1,175 @@
+//! Rating updates from concluded matches.
+//!
+//! Every player has a rating stored in [`Player::rating`]. When the result of a match changes and
+//! the tournament has a [`RatingSystem`] configured, the ratings of all players in the match are
+//! updated. A team is rated using the combined rating of its players against the combined rating
+//! of the opposing team. The resulting change is applied to every player of the team. Every
+//! change is stored in the rating history, which also keeps the deviation and volatility used by
+//! [`RatingSystem::Glicko2`].
+use std::collections::hash_map::{Entry, HashMap};
+
+use chrono::Utc;
+use dynamic_tournament_api::v3::id::{BracketId, EntrantId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant, Player};
+use dynamic_tournament_api::v3::tournaments::ratings::{RatingChange, RatingSystem};
+use dynamic_tournament_core::prediction::DEFAULT_RATING;
+use dynamic_tournament_core::rating::{Elo, Glicko2, Outcome, Rating};
+
+use crate::store::Store;
+use crate::Error;
+
+/// Updates the ratings after the result of the match with the given `index` changed. `result`
+/// contains the entrants of the match and the position of the winning entrant, or `None` if the
+/// match is no longer concluded.
+///
+/// All changes previously caused by the match are reverted first, so a corrected result replaces
+/// the old one. Nothing is updated if the tournament has no [`RatingSystem`].
+pub async fn rate_match(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+    result: Option<([EntrantId; 2], usize)>,
+) -> Result<(), Error> {
+    let ratings = store.ratings(tournament_id);
+
+    let Some(system) = ratings.system().await? else {
+        return Ok(());
+    };
+
+    let _guard = store.lock_entrants().await;
+
+    revert_match(store, tournament_id, bracket_id, index).await?;
+
+    let Some((entrants, winner)) = result else {
+        return Ok(());
+    };
+
+    let mut sides = Vec::with_capacity(2);
+    for id in entrants {
+        let Some(mut entrant) = store.get_entrant(tournament_id, id).await? else {
+            return Ok(());
+        };
+
+        let history = ratings.entrant_history(id).await?;
+        let current: Vec<Rating> = players_mut(&mut entrant)
+            .iter()
+            .enumerate()
+            .map(|(player, data)| current_rating(&history, player, data))
+            .collect();
+
+        sides.push((entrant, current));
+    }
+
+    let combined: Vec<Rating> = sides
+        .iter()
+        .map(|(_, current)| Rating::combine(current.iter().copied()))
+        .collect();
+
+    let date = Utc::now();
+    for (side, (mut entrant, current)) in sides.into_iter().enumerate() {
+        let opponent = combined[1 - side];
+        let outcome = if side == winner {
+            Outcome::win(opponent)
+        } else {
+            Outcome::loss(opponent)
+        };
+
+        let team = match system {
+            RatingSystem::Elo { k } => Elo::new(k).rate(combined[side], &[outcome]),
+            RatingSystem::Glicko2 { tau } => Glicko2::new(tau).rate(combined[side], &[outcome]),
+        };
+        let delta = team.rating - combined[side].rating;
+
+        let id = entrant.id;
+        for (player, (data, rating)) in players_mut(&mut entrant)
+            .iter_mut()
+            .zip(current)
+            .enumerate()
+        {
+            let new = (rating.rating + delta).round().max(0.0);
+            data.rating = Some(new as u64);
+
+            ratings
+                .insert(&RatingChange {
+                    date,
+                    entrant: id,
+                    player,
+                    bracket: bracket_id,
+                    index,
+                    rating: new,
+                    // The rounded difference is stored to revert the change exactly.
+                    delta: new - rating.rating,
+                    deviation: team.deviation,
+                    volatility: team.volatility,
+                })
+                .await?;
+        }
+
+        store.entrants(tournament_id).update(id, &entrant).await?;
+    }
+
+    Ok(())
+}
+
+/// Reverts all [`RatingChange`]s caused by the match with the given `index` and removes them
+/// from the history.
+async fn revert_match(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    index: usize,
+) -> Result<(), Error> {
+    let ratings = store.ratings(tournament_id);
+
+    let changes = ratings.match_history(bracket_id, index).await?;
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut entrants: HashMap<EntrantId, Entrant> = HashMap::new();
+    for change in changes {
+        let entrant = match entrants.entry(change.entrant) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            // The entrant may have been deleted since.
+            Entry::Vacant(entry) => match store.get_entrant(tournament_id, change.entrant).await? {
+                Some(entrant) => entry.insert(entrant),
+                None => continue,
+            },
+        };
+
+        if let Some(data) = players_mut(entrant).get_mut(change.player) {
+            let rating = data.rating.map(|r| r as f64).unwrap_or(DEFAULT_RATING);
+            data.rating = Some((rating - change.delta).round().max(0.0) as u64);
+        }
+    }
+
+    for (id, entrant) in entrants {
+        store.entrants(tournament_id).update(id, &entrant).await?;
+    }
+
+    ratings.delete_match(bracket_id, index).await
+}
+
+/// Returns the current [`Rating`] of a player. The rating is always taken from the player itself
+/// since it may have been changed manually. Deviation and volatility are taken from the latest
+/// [`RatingChange`] of the player in the `history` of the entrant.
+fn current_rating(history: &[RatingChange], player: usize, data: &Player) -> Rating {
+    let rating = data.rating.map(|r| r as f64).unwrap_or(DEFAULT_RATING);
+
+    match history.iter().rev().find(|change| change.player == player) {
+        Some(change) => Rating {
+            rating,
+            deviation: change.deviation,
+            volatility: change.volatility,
+        },
+        None => Rating::new(rating),
+    }
+}
+
+fn players_mut(entrant: &mut Entrant) -> &mut [Player] {
+    match &mut entrant.inner {
+        EntrantVariant::Player(player) => std::slice::from_mut(player),
+        EntrantVariant::Team(team) => &mut team.players,
+    }
+}
@@ -0,0 +1,264 @@
+//! Replaying the event log of a bracket.
+//!
+//! Every update and reset of a match is written to the event log of the tournament. Replaying
+//! these events on the initial state of a bracket recreates the state of the bracket at any
+//! point of the log. This is used to preview or roll back to an earlier state and to recover a
+//! bracket whose stored state can no longer be loaded.
+//!
+//! Changing the entrants or options of a bracket recreates all matches. Replaying starts with the
+//! bracket as it was before the first such change and recreates the bracket from every change.
+use dynamic_tournament_api::v3::id::{BracketId, EntrantId, EventId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
+use dynamic_tournament_api::v3::tournaments::log::{LogEvent, LogEventBody};
+use dynamic_tournament_core::tournament::Tournament;
+use dynamic_tournament_core::{EntrantScore, System};
+
+use crate::store::Store;
+use crate::websocket::live_bracket::{self, requires_rebuild};
+use crate::Error;
+
+/// Recreates the state of the bracket with the given id after the event with the id `until`.
+/// An `until` of `EventId(0)` returns the initial state of the bracket. The [`Bracket`] that was
+/// in effect after the event is returned together with the state.
+///
+/// Returns `None` if the bracket or the event does not exist.
+pub async fn replay(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    until: EventId,
+) -> Result<Option<(Bracket, Tournament<EntrantId, EntrantScore<u64>>)>, Error> {
+    let Some(bracket) = store.get_bracket(tournament_id, bracket_id).await? else {
+        return Ok(None);
+    };
+
+    let mut events = store.event_log(tournament_id).list().await?;
+    if until != EventId(0) && !events.iter().any(|event| event.id == until) {
+        return Ok(None);
+    }
+
+    events.sort_by_key(|event| event.id);
+
+    let initial = initial_bracket(bracket, &events, bracket_id);
+    Ok(Some(replay_events(&initial, &events, bracket_id, until)?))
+}
+
+/// Returns the version of the bracket that was in effect before the first event. This is the
+/// bracket before its entrants or options were first changed, or the `current` bracket if they
+/// were never changed.
+fn initial_bracket(current: Bracket, events: &[LogEvent], bracket_id: BracketId) -> Bracket {
+    events
+        .iter()
+        .find_map(|event| match &event.body {
+            LogEventBody::UpdateBracket { id, before, after }
+                if *id == bracket_id && requires_rebuild(before, after) =>
+            {
+                Some(before.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or(current)
+}
+
+/// Applies all `events` of the bracket with the given id up to and including the event `until`
+/// on the initial state of the `initial` bracket. `events` must be sorted by their ids.
+fn replay_events(
+    initial: &Bracket,
+    events: &[LogEvent],
+    bracket_id: BracketId,
+    until: EventId,
+) -> dynamic_tournament_core::Result<(Bracket, Tournament<EntrantId, EntrantScore<u64>>)> {
+    let mut bracket = initial.clone();
+    let mut tournament = live_bracket::build(bracket.clone(), None)?;
+
+    for event in events.iter().take_while(|event| event.id <= until) {
+        match &event.body {
+            LogEventBody::UpdateMatch {
+                bracket_id: id,
+                index,
+                nodes,
+            } if *id == bracket_id && (*index as usize) < tournament.matches().len() => {
+                live_bracket::update_match(&mut tournament, *index as usize, *nodes);
+            }
+            LogEventBody::ResetMatch {
+                bracket_id: id,
+                index,
+            } if *id == bracket_id && (*index as usize) < tournament.matches().len() => {
+                tournament.update_match(*index as usize, |_, res| {
+                    res.reset_default();
+                });
+            }
+            // The entrants or options changed and all matches were recreated.
+            LogEventBody::UpdateBracket { id, before, after }
+                if *id == bracket_id && requires_rebuild(before, after) =>
+            {
+                bracket = after.clone();
+                tournament = live_bracket::build(bracket.clone(), None)?;
+            }
+            // A rollback always refers to an earlier event. The state at that event is
+            // replayed again, including all rollbacks before it.
+            LogEventBody::Rollback {
+                bracket_id: id,
+                event_id,
+            } if *id == bracket_id && *event_id < event.id => {
+                (bracket, tournament) = replay_events(initial, events, bracket_id, *event_id)?;
+            }
+            _ => (),
+        }
+    }
+
+    Ok((bracket, tournament))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use dynamic_tournament_api::v3::id::{BracketId, EntrantId, EventId, SystemId};
+    use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
+    use dynamic_tournament_api::v3::tournaments::log::{LogEvent, LogEventBody};
+    use dynamic_tournament_core::tournament::Tournament;
+    use dynamic_tournament_core::{EntrantScore, EntrantSpot, System};
+
+    use super::{initial_bracket, replay_events};
+
+    fn event(id: u64, body: LogEventBody) -> LogEvent {
+        LogEvent {
+            id: EventId(id),
+            date: Utc::now(),
+            author: 0,
+            body,
+        }
+    }
+
+    fn update(id: u64, bracket_id: BracketId, index: u64) -> LogEvent {
+        event(
+            id,
+            LogEventBody::UpdateMatch {
+                bracket_id,
+                index,
+                nodes: [
+                    EntrantScore {
+                        score: 2,
+                        winner: true,
+                    },
+                    EntrantScore {
+                        score: 1,
+                        winner: false,
+                    },
+                ],
+            },
+        )
+    }
+
+    fn bracket(id: BracketId, entrants: u64) -> Bracket {
+        Bracket {
+            id,
+            name: String::from("Bracket"),
+            system: SystemId(1),
+            options: Default::default(),
+            entrants: (0..entrants).map(EntrantId).collect(),
+            seeding: Default::default(),
+        }
+    }
+
+    fn score(tournament: &Tournament<EntrantId, EntrantScore<u64>>, index: usize) -> u64 {
+        match &tournament.matches()[index].entrants[0] {
+            EntrantSpot::Entrant(node) => node.data.score,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_replay_events() {
+        let bracket_id = BracketId(1);
+        let initial = bracket(bracket_id, 4);
+
+        let events = [
+            update(1, bracket_id, 0),
+            update(2, bracket_id, 1),
+            // Events of other brackets are ignored.
+            update(3, BracketId(2), 2),
+            event(
+                4,
+                LogEventBody::ResetMatch {
+                    bracket_id,
+                    index: 0,
+                },
+            ),
+            event(
+                5,
+                LogEventBody::Rollback {
+                    bracket_id,
+                    event_id: EventId(2),
+                },
+            ),
+        ];
+
+        let (_, tournament) = replay_events(&initial, &events, bracket_id, EventId(0)).unwrap();
+        assert_eq!(score(&tournament, 0), 0);
+
+        let (_, tournament) = replay_events(&initial, &events, bracket_id, EventId(3)).unwrap();
+        assert_eq!(score(&tournament, 0), 2);
+        assert_eq!(score(&tournament, 1), 2);
+        assert_eq!(score(&tournament, 2), 0);
+
+        let (_, tournament) = replay_events(&initial, &events, bracket_id, EventId(4)).unwrap();
+        assert_eq!(score(&tournament, 0), 0);
+        assert_eq!(score(&tournament, 1), 2);
+
+        let (_, tournament) = replay_events(&initial, &events, bracket_id, EventId(5)).unwrap();
+        assert_eq!(score(&tournament, 0), 2);
+        assert_eq!(score(&tournament, 1), 2);
+    }
+
+    #[test]
+    fn test_replay_events_update_bracket() {
+        let bracket_id = BracketId(1);
+        let before = bracket(bracket_id, 4);
+        let after = bracket(bracket_id, 8);
+
+        let events = [
+            update(1, bracket_id, 0),
+            event(
+                2,
+                LogEventBody::ResetMatch {
+                    bracket_id,
+                    index: 0,
+                },
+            ),
+            // The bracket was edited using PATCH.
+            event(
+                3,
+                LogEventBody::UpdateBracket {
+                    id: bracket_id,
+                    before: before.clone(),
+                    after: after.clone(),
+                },
+            ),
+            update(4, bracket_id, 3),
+        ];
+
+        // The current bracket is replaced with the bracket before the first change.
+        let initial = initial_bracket(after.clone(), &events, bracket_id);
+        assert_eq!(initial.entrants, before.entrants);
+
+        let (bracket, tournament) =
+            replay_events(&initial, &events, bracket_id, EventId(1)).unwrap();
+        assert_eq!(bracket.entrants, before.entrants);
+        assert_eq!(tournament.matches().len(), 3);
+        assert_eq!(score(&tournament, 0), 2);
+
+        let (bracket, tournament) =
+            replay_events(&initial, &events, bracket_id, EventId(3)).unwrap();
+        assert_eq!(bracket.entrants, after.entrants);
+        assert_eq!(tournament.matches().len(), 7);
+        assert_eq!(score(&tournament, 3), 0);
+
+        let (_, tournament) = replay_events(&initial, &events, bracket_id, EventId(4)).unwrap();
+        assert_eq!(score(&tournament, 3), 2);
+
+        // Without any changes the current bracket is used.
+        let initial = initial_bracket(after.clone(), &events[..2], bracket_id);
+        assert_eq!(initial.entrants, after.entrants);
+    }
+}
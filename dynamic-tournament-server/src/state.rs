@@ -1,16 +1,13 @@
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
 
 use crate::auth::Authorization;
 use crate::limits::Limits;
 use crate::signal::ShutdownListener;
 use crate::store::Store;
+use crate::webhooks::Webhooks;
 use crate::websocket::live_bracket::LiveBrackets;
-use crate::Config;
-
-use sqlx::pool::PoolOptions;
-use sqlx::MySqlPool;
+use crate::{Config, Error};
 
 #[cfg(feature = "metrics")]
 use crate::metrics::Metrics;
@@ -19,7 +16,7 @@ use crate::metrics::Metrics;
 pub struct State(Arc<StateInner>);
 
 impl State {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config) -> Result<Self, Error> {
         let limits = Limits::new();
 
         // Acquire STDIN, STDOUT and STDERR. They are acquired forever.
@@ -30,27 +27,18 @@ impl State {
         // Database connections
         limits.try_acquire_files(8).map(|fd| fd.forget()).unwrap();
 
-        let pool: MySqlPool = PoolOptions::new()
-            .max_connections(0)
-            .max_connections(8)
-            .max_lifetime(Duration::new(3600, 0))
-            .idle_timeout(Duration::new(60, 0))
-            .connect_lazy(&config.database.connect_string())
-            .unwrap();
-
-        let store = Store {
-            pool,
-            table_prefix: config.database.prefix.clone(),
-        };
+        let store = Store::connect_lazy(&config.database)?;
 
         let auth = Authorization::new(config.authorization.alg);
 
-        let live_brackets = LiveBrackets::new(store.clone());
+        let webhooks = Webhooks::new(store.clone());
+        let live_brackets = LiveBrackets::new(store.clone(), webhooks.clone());
 
-        Self(Arc::new(StateInner {
+        Ok(Self(Arc::new(StateInner {
             store,
             config,
             live_brackets,
+            webhooks,
             shutdown: Shutdown,
             auth,
 
@@ -59,7 +47,7 @@ impl State {
 
             #[cfg(feature = "limits")]
             limits,
-        }))
+        })))
     }
 }
 
@@ -77,6 +65,7 @@ pub struct StateInner {
     pub store: Store,
     pub config: Config,
     pub live_brackets: LiveBrackets,
+    pub webhooks: Webhooks,
     pub shutdown: Shutdown,
     pub auth: Authorization,
 
@@ -0,0 +1,816 @@
+//! An in-memory storage backend.
+//!
+//! All data is lost when the server exits, unless a path is given. In that case the data is
+//! loaded from the JSON file at the path on startup and written back on shutdown.
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use dynamic_tournament_api::v3::id::{
+    BracketId, DeliveryId, EntrantId, EventId, RoleId, TournamentId, UserId, WebhookId,
+};
+use dynamic_tournament_api::v3::tournaments::brackets::matches::MatchMetadata;
+use dynamic_tournament_api::v3::tournaments::brackets::schedule::Schedule;
+use dynamic_tournament_api::v3::tournaments::brackets::veto::{MatchVeto, VetoConfig};
+use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
+use dynamic_tournament_api::v3::tournaments::entrants::Entrant;
+use dynamic_tournament_api::v3::tournaments::log::LogEvent;
+use dynamic_tournament_api::v3::tournaments::ratings::{RatingChange, RatingSystem};
+use dynamic_tournament_api::v3::tournaments::roles::Role;
+use dynamic_tournament_api::v3::tournaments::webhooks::{Delivery, Webhook};
+use dynamic_tournament_api::v3::tournaments::{PartialTournament, Tournament, TournamentOverview};
+use dynamic_tournament_api::v3::users::User;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use super::BracketState;
+use crate::Error;
+
+#[derive(Debug)]
+pub struct Memory {
+    data: RwLock<Data>,
+    /// The path of the file the data is persisted to.
+    path: Option<PathBuf>,
+}
+
+/// A row belonging to a tournament.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Row<T> {
+    tournament_id: TournamentId,
+    value: T,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BracketRow {
+    tournament_id: TournamentId,
+    bracket: Bracket,
+    state: Option<BracketState>,
+}
+
+/// All tables. Ids are unique across all tables.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Data {
+    next_id: u64,
+    tournaments: BTreeMap<u64, Tournament>,
+    entrants: BTreeMap<u64, Row<Entrant>>,
+    brackets: BTreeMap<u64, BracketRow>,
+    roles: BTreeMap<u64, Row<Role>>,
+    users: BTreeMap<u64, User>,
+    /// Keyed by user id.
+    user_tokens: BTreeMap<u64, u64>,
+    log: BTreeMap<u64, Row<LogEvent>>,
+    /// Keyed by bracket id.
+    schedules: BTreeMap<u64, Row<Schedule>>,
+    /// Keyed by bracket id.
+    veto_configs: BTreeMap<u64, Row<VetoConfig>>,
+    /// Keyed by bracket id, then by match index.
+    vetoes: BTreeMap<u64, Row<BTreeMap<usize, MatchVeto>>>,
+    /// Keyed by bracket id, then by match index.
+    match_metadata: BTreeMap<u64, Row<BTreeMap<usize, MatchMetadata>>>,
+    /// Keyed by tournament id.
+    rating_systems: BTreeMap<u64, RatingSystem>,
+    rating_history: BTreeMap<u64, Row<RatingChange>>,
+    webhooks: BTreeMap<u64, Row<Webhook>>,
+    webhook_deliveries: BTreeMap<u64, Row<Delivery>>,
+}
+
+impl Data {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+impl Memory {
+    /// Creates a new `Memory` store. If a `path` is given the data is loaded from the file at
+    /// `path`, if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if the file exists, but cannot be read or contains invalid data.
+    pub fn new(path: Option<PathBuf>) -> Result<Self, Error> {
+        let data = match &path {
+            Some(path) => match std::fs::read(path) {
+                Ok(buf) => serde_json::from_slice(&buf)?,
+                Err(err) if err.kind() == ErrorKind::NotFound => Data::default(),
+                Err(err) => return Err(err.into()),
+            },
+            None => Data::default(),
+        };
+
+        Ok(Self {
+            data: RwLock::new(data),
+            path,
+        })
+    }
+
+    /// Writes all data to the file at the configured path. Does nothing if no path is
+    /// configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if the file cannot be written.
+    pub async fn persist(&self) -> Result<(), Error> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let buf = serde_json::to_vec(&*self.data.read())?;
+        tokio::fs::write(path, buf).await?;
+        Ok(())
+    }
+
+    pub fn list_tournaments(&self) -> Vec<TournamentOverview> {
+        let data = self.data.read();
+
+        let mut tournaments: Vec<_> = data
+            .tournaments
+            .iter()
+            .map(|(id, tournament)| TournamentOverview {
+                id: TournamentId(*id),
+                name: tournament.name.clone(),
+                date: tournament.date,
+                kind: tournament.kind,
+            })
+            .collect();
+
+        tournaments.sort_by_key(|tournament| Reverse(tournament.date));
+        tournaments
+    }
+
+    pub fn get_tournament(&self, id: TournamentId) -> Option<Tournament> {
+        let data = self.data.read();
+
+        data.tournaments.get(&id.0).map(|tournament| Tournament {
+            id,
+            ..tournament.clone()
+        })
+    }
+
+    pub fn insert_tournament(&self, tournament: &Tournament) -> TournamentId {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.tournaments.insert(id, tournament.clone());
+        TournamentId(id)
+    }
+
+    pub fn update_tournament(&self, id: TournamentId, tournament: &PartialTournament) {
+        let mut data = self.data.write();
+
+        if let Some(value) = data.tournaments.get_mut(&id.0) {
+            value.update(tournament.clone());
+        }
+    }
+
+    /// Deletes the tournament with the given `id` and all rows belonging to it.
+    pub fn delete_tournament(&self, id: TournamentId) {
+        let mut data = self.data.write();
+
+        data.tournaments.remove(&id.0);
+        data.entrants.retain(|_, row| row.tournament_id != id);
+        data.brackets.retain(|_, row| row.tournament_id != id);
+        data.roles.retain(|_, row| row.tournament_id != id);
+        data.schedules.retain(|_, row| row.tournament_id != id);
+        data.veto_configs.retain(|_, row| row.tournament_id != id);
+        data.vetoes.retain(|_, row| row.tournament_id != id);
+        data.match_metadata.retain(|_, row| row.tournament_id != id);
+        data.rating_systems.remove(&id.0);
+        data.rating_history.retain(|_, row| row.tournament_id != id);
+        data.webhooks.retain(|_, row| row.tournament_id != id);
+        data.webhook_deliveries
+            .retain(|_, row| row.tournament_id != id);
+    }
+
+    pub fn list_entrants(&self, tournament_id: TournamentId) -> Vec<Entrant> {
+        let data = self.data.read();
+
+        data.entrants
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id)
+            .map(|(id, row)| Entrant {
+                id: EntrantId(*id),
+                ..row.value.clone()
+            })
+            .collect()
+    }
+
+    pub fn get_entrant(&self, tournament_id: TournamentId, id: EntrantId) -> Option<Entrant> {
+        let data = self.data.read();
+
+        data.entrants
+            .get(&id.0)
+            .filter(|row| row.tournament_id == tournament_id)
+            .map(|row| Entrant {
+                id,
+                ..row.value.clone()
+            })
+    }
+
+    pub fn insert_entrant(&self, tournament_id: TournamentId, entrant: &Entrant) -> EntrantId {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.entrants.insert(
+            id,
+            Row {
+                tournament_id,
+                value: entrant.clone(),
+            },
+        );
+        EntrantId(id)
+    }
+
+    pub fn update_entrant(&self, id: EntrantId, entrant: &Entrant) {
+        let mut data = self.data.write();
+
+        if let Some(row) = data.entrants.get_mut(&id.0) {
+            row.value = entrant.clone();
+        }
+    }
+
+    pub fn delete_entrant(&self, tournament_id: TournamentId, id: EntrantId) {
+        let mut data = self.data.write();
+
+        if data.entrants.get(&id.0).map(|row| row.tournament_id) == Some(tournament_id) {
+            data.entrants.remove(&id.0);
+        }
+    }
+
+    pub fn list_brackets(&self, tournament_id: TournamentId) -> Vec<Bracket> {
+        let data = self.data.read();
+
+        data.brackets
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id)
+            .map(|(id, row)| Bracket {
+                id: BracketId(*id),
+                ..row.bracket.clone()
+            })
+            .collect()
+    }
+
+    pub fn get_bracket(&self, tournament_id: TournamentId, id: BracketId) -> Option<Bracket> {
+        self.bracket_row(tournament_id, id)
+            .map(|row| Bracket { id, ..row.bracket })
+    }
+
+    /// Returns the state of the bracket. Returns `None` if the bracket doesn't exist.
+    pub fn get_bracket_state(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+    ) -> Option<Option<BracketState>> {
+        self.bracket_row(tournament_id, id).map(|row| row.state)
+    }
+
+    fn bracket_row(&self, tournament_id: TournamentId, id: BracketId) -> Option<BracketRow> {
+        let data = self.data.read();
+
+        data.brackets
+            .get(&id.0)
+            .filter(|row| row.tournament_id == tournament_id)
+            .cloned()
+    }
+
+    pub fn insert_bracket(&self, tournament_id: TournamentId, bracket: &Bracket) -> BracketId {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.brackets.insert(
+            id,
+            BracketRow {
+                tournament_id,
+                bracket: bracket.clone(),
+                state: None,
+            },
+        );
+        BracketId(id)
+    }
+
+    pub fn update_bracket(&self, tournament_id: TournamentId, id: BracketId, bracket: &Bracket) {
+        let mut data = self.data.write();
+
+        if let Some(row) = data.brackets.get_mut(&id.0) {
+            if row.tournament_id == tournament_id {
+                row.bracket = bracket.clone();
+            }
+        }
+    }
+
+    pub fn update_bracket_state(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        state: &Option<BracketState>,
+    ) {
+        let mut data = self.data.write();
+
+        if let Some(row) = data.brackets.get_mut(&id.0) {
+            if row.tournament_id == tournament_id {
+                row.state = state.clone();
+            }
+        }
+    }
+
+    pub fn update_bracket_with_state(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        bracket: &Bracket,
+        state: &Option<BracketState>,
+    ) {
+        let mut data = self.data.write();
+
+        if let Some(row) = data.brackets.get_mut(&id.0) {
+            if row.tournament_id == tournament_id {
+                row.bracket = bracket.clone();
+                row.state = state.clone();
+            }
+        }
+    }
+
+    /// Deletes the bracket with the given `id` and all rows belonging to it.
+    pub fn delete_bracket(&self, id: BracketId) {
+        let mut data = self.data.write();
+
+        data.brackets.remove(&id.0);
+        data.schedules.remove(&id.0);
+        data.veto_configs.remove(&id.0);
+        data.vetoes.remove(&id.0);
+        data.match_metadata.remove(&id.0);
+    }
+
+    pub fn list_roles(&self, tournament_id: TournamentId) -> Vec<Role> {
+        let data = self.data.read();
+
+        data.roles
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id)
+            .map(|(id, row)| Role {
+                id: RoleId(*id),
+                name: row.value.name.clone(),
+            })
+            .collect()
+    }
+
+    pub fn get_role(&self, tournament_id: TournamentId, id: RoleId) -> Option<Role> {
+        let data = self.data.read();
+
+        data.roles
+            .get(&id.0)
+            .filter(|row| row.tournament_id == tournament_id)
+            .map(|row| Role {
+                id,
+                name: row.value.name.clone(),
+            })
+    }
+
+    pub fn insert_role(&self, tournament_id: TournamentId, role: &Role) -> RoleId {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.roles.insert(
+            id,
+            Row {
+                tournament_id,
+                value: role.clone(),
+            },
+        );
+        RoleId(id)
+    }
+
+    pub fn delete_role(&self, id: RoleId) {
+        self.data.write().roles.remove(&id.0);
+    }
+
+    pub fn get_user(&self, username: &str) -> Option<User> {
+        let data = self.data.read();
+
+        data.users
+            .values()
+            .find(|user| user.username == username)
+            .cloned()
+    }
+
+    pub fn list_users(&self) -> Vec<User> {
+        let data = self.data.read();
+
+        let mut users: Vec<_> = data.users.values().cloned().collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        users
+    }
+
+    pub fn get_user_by_id(&self, id: UserId) -> Option<User> {
+        self.data.read().users.get(&id.0).cloned()
+    }
+
+    pub fn insert_user(&self, user: &User) {
+        self.data.write().users.insert(user.id.0, user.clone());
+    }
+
+    pub fn delete_user(&self, id: UserId) {
+        let mut data = self.data.write();
+        data.users.remove(&id.0);
+        data.user_tokens.remove(&id.0);
+    }
+
+    pub fn list_tokens_valid_after(&self) -> Vec<(UserId, u64)> {
+        let data = self.data.read();
+
+        data.users
+            .keys()
+            .map(|id| (UserId(*id), data.user_tokens.get(id).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    pub fn set_tokens_valid_after(&self, id: UserId, valid_after: u64) {
+        self.data.write().user_tokens.insert(id.0, valid_after);
+    }
+
+    pub fn list_events(&self, tournament_id: TournamentId) -> Vec<LogEvent> {
+        let data = self.data.read();
+
+        data.log
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id)
+            .map(|(id, row)| LogEvent {
+                id: EventId(*id),
+                ..row.value.clone()
+            })
+            .collect()
+    }
+
+    pub fn insert_event(&self, tournament_id: TournamentId, event: &LogEvent) {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.log.insert(
+            id,
+            Row {
+                tournament_id,
+                value: event.clone(),
+            },
+        );
+    }
+
+    pub fn get_schedule(&self, tournament_id: TournamentId, id: BracketId) -> Option<Schedule> {
+        let data = self.data.read();
+        get(&data.schedules, tournament_id, id).cloned()
+    }
+
+    pub fn insert_schedule(&self, tournament_id: TournamentId, id: BracketId, schedule: &Schedule) {
+        let mut data = self.data.write();
+        insert(&mut data.schedules, tournament_id, id, schedule.clone());
+    }
+
+    pub fn delete_schedule(&self, tournament_id: TournamentId, id: BracketId) {
+        let mut data = self.data.write();
+        remove(&mut data.schedules, tournament_id, id);
+    }
+
+    pub fn get_veto_config(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+    ) -> Option<VetoConfig> {
+        let data = self.data.read();
+        get(&data.veto_configs, tournament_id, id).cloned()
+    }
+
+    pub fn set_veto_config(&self, tournament_id: TournamentId, id: BracketId, config: &VetoConfig) {
+        let mut data = self.data.write();
+        insert(&mut data.veto_configs, tournament_id, id, config.clone());
+    }
+
+    pub fn delete_veto_config(&self, tournament_id: TournamentId, id: BracketId) {
+        let mut data = self.data.write();
+        remove(&mut data.veto_configs, tournament_id, id);
+    }
+
+    pub fn list_vetoes(&self, tournament_id: TournamentId, id: BracketId) -> Vec<MatchVeto> {
+        let data = self.data.read();
+
+        get(&data.vetoes, tournament_id, id)
+            .map(|vetoes| vetoes.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn insert_veto(&self, tournament_id: TournamentId, id: BracketId, veto: &MatchVeto) {
+        let mut data = self.data.write();
+
+        get_or_insert(&mut data.vetoes, tournament_id, id).insert(veto.index, veto.clone());
+    }
+
+    pub fn delete_veto(&self, tournament_id: TournamentId, id: BracketId, index: usize) {
+        let mut data = self.data.write();
+
+        if let Some(vetoes) = get_mut(&mut data.vetoes, tournament_id, id) {
+            vetoes.remove(&index);
+        }
+    }
+
+    pub fn clear_vetoes(&self, tournament_id: TournamentId, id: BracketId) {
+        let mut data = self.data.write();
+        remove(&mut data.vetoes, tournament_id, id);
+    }
+
+    pub fn get_match_metadata(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        index: usize,
+    ) -> Option<MatchMetadata> {
+        let data = self.data.read();
+
+        get(&data.match_metadata, tournament_id, id)
+            .and_then(|metadata| metadata.get(&index))
+            .cloned()
+    }
+
+    pub fn list_match_metadata(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+    ) -> Vec<MatchMetadata> {
+        let data = self.data.read();
+
+        get(&data.match_metadata, tournament_id, id)
+            .map(|metadata| metadata.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn insert_match_metadata(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        metadata: &MatchMetadata,
+    ) {
+        let mut data = self.data.write();
+
+        get_or_insert(&mut data.match_metadata, tournament_id, id)
+            .insert(metadata.index, metadata.clone());
+    }
+
+    pub fn delete_match_metadata(&self, tournament_id: TournamentId, id: BracketId, index: usize) {
+        let mut data = self.data.write();
+
+        if let Some(metadata) = get_mut(&mut data.match_metadata, tournament_id, id) {
+            metadata.remove(&index);
+        }
+    }
+
+    pub fn clear_match_metadata(&self, tournament_id: TournamentId, id: BracketId) {
+        let mut data = self.data.write();
+        remove(&mut data.match_metadata, tournament_id, id);
+    }
+
+    pub fn get_rating_system(&self, tournament_id: TournamentId) -> Option<RatingSystem> {
+        self.data
+            .read()
+            .rating_systems
+            .get(&tournament_id.0)
+            .cloned()
+    }
+
+    pub fn set_rating_system(&self, tournament_id: TournamentId, system: &RatingSystem) {
+        self.data
+            .write()
+            .rating_systems
+            .insert(tournament_id.0, *system);
+    }
+
+    pub fn delete_rating_system(&self, tournament_id: TournamentId) {
+        self.data.write().rating_systems.remove(&tournament_id.0);
+    }
+
+    pub fn list_rating_changes<F>(&self, tournament_id: TournamentId, f: F) -> Vec<RatingChange>
+    where
+        F: Fn(&RatingChange) -> bool,
+    {
+        let data = self.data.read();
+
+        data.rating_history
+            .values()
+            .filter(|row| row.tournament_id == tournament_id && f(&row.value))
+            .map(|row| row.value.clone())
+            .collect()
+    }
+
+    pub fn delete_rating_changes<F>(&self, tournament_id: TournamentId, f: F)
+    where
+        F: Fn(&RatingChange) -> bool,
+    {
+        self.data
+            .write()
+            .rating_history
+            .retain(|_, row| row.tournament_id != tournament_id || !f(&row.value));
+    }
+
+    pub fn insert_rating_change(&self, tournament_id: TournamentId, change: &RatingChange) {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.rating_history.insert(
+            id,
+            Row {
+                tournament_id,
+                value: change.clone(),
+            },
+        );
+    }
+
+    pub fn list_webhooks(&self, tournament_id: TournamentId) -> Vec<Webhook> {
+        let data = self.data.read();
+
+        data.webhooks
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id)
+            .map(|(id, row)| Webhook {
+                id: WebhookId(*id),
+                ..row.value.clone()
+            })
+            .collect()
+    }
+
+    pub fn get_webhook(&self, tournament_id: TournamentId, id: WebhookId) -> Option<Webhook> {
+        let data = self.data.read();
+
+        data.webhooks
+            .get(&id.0)
+            .filter(|row| row.tournament_id == tournament_id)
+            .map(|row| Webhook {
+                id,
+                ..row.value.clone()
+            })
+    }
+
+    pub fn insert_webhook(&self, tournament_id: TournamentId, webhook: &Webhook) -> WebhookId {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.webhooks.insert(
+            id,
+            Row {
+                tournament_id,
+                value: webhook.clone(),
+            },
+        );
+        WebhookId(id)
+    }
+
+    /// Deletes the webhook with the given `id` and all its deliveries.
+    pub fn delete_webhook(&self, tournament_id: TournamentId, id: WebhookId) {
+        let mut data = self.data.write();
+
+        match data.webhooks.get(&id.0) {
+            Some(row) if row.tournament_id == tournament_id => (),
+            _ => return,
+        }
+
+        data.webhooks.remove(&id.0);
+        data.webhook_deliveries
+            .retain(|_, row| row.value.webhook_id != id);
+    }
+
+    pub fn list_deliveries(&self, tournament_id: TournamentId, id: WebhookId) -> Vec<Delivery> {
+        let data = self.data.read();
+
+        data.webhook_deliveries
+            .iter()
+            .filter(|(_, row)| row.tournament_id == tournament_id && row.value.webhook_id == id)
+            .map(|(id, row)| Delivery {
+                id: DeliveryId(*id),
+                ..row.value.clone()
+            })
+            .collect()
+    }
+
+    pub fn insert_delivery(&self, tournament_id: TournamentId, delivery: &Delivery) {
+        let mut data = self.data.write();
+
+        let id = data.next_id();
+        data.webhook_deliveries.insert(
+            id,
+            Row {
+                tournament_id,
+                value: delivery.clone(),
+            },
+        );
+    }
+}
+
+/// Returns the value of the bracket with the given `id` from a table keyed by bracket id.
+fn get<T>(table: &BTreeMap<u64, Row<T>>, tournament_id: TournamentId, id: BracketId) -> Option<&T> {
+    table
+        .get(&id.0)
+        .filter(|row| row.tournament_id == tournament_id)
+        .map(|row| &row.value)
+}
+
+fn get_mut<T>(
+    table: &mut BTreeMap<u64, Row<T>>,
+    tournament_id: TournamentId,
+    id: BracketId,
+) -> Option<&mut T> {
+    table
+        .get_mut(&id.0)
+        .filter(|row| row.tournament_id == tournament_id)
+        .map(|row| &mut row.value)
+}
+
+/// Returns the value of the bracket with the given `id`, inserting the default value if it
+/// doesn't exist.
+fn get_or_insert<T>(
+    table: &mut BTreeMap<u64, Row<T>>,
+    tournament_id: TournamentId,
+    id: BracketId,
+) -> &mut T
+where
+    T: Default,
+{
+    &mut table
+        .entry(id.0)
+        .or_insert_with(|| Row {
+            tournament_id,
+            value: T::default(),
+        })
+        .value
+}
+
+/// Inserts the value of the bracket with the given `id`, overwriting the existing value.
+fn insert<T>(
+    table: &mut BTreeMap<u64, Row<T>>,
+    tournament_id: TournamentId,
+    id: BracketId,
+    value: T,
+) {
+    table.insert(
+        id.0,
+        Row {
+            tournament_id,
+            value,
+        },
+    );
+}
+
+fn remove<T>(table: &mut BTreeMap<u64, Row<T>>, tournament_id: TournamentId, id: BracketId) {
+    if get(table, tournament_id, id).is_some() {
+        table.remove(&id.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+    use dynamic_tournament_api::v3::tournaments::{EntrantKind, Tournament};
+
+    use super::{Data, Memory};
+
+    fn tournament() -> Tournament {
+        Tournament {
+            id: TournamentId(0),
+            name: String::from("test"),
+            description: String::new(),
+            date: Utc::now(),
+            kind: EntrantKind::Player,
+        }
+    }
+
+    #[test]
+    fn test_memory_tournaments() {
+        let memory = Memory::new(None).unwrap();
+
+        let id = memory.insert_tournament(&tournament());
+        assert_eq!(memory.get_tournament(id).unwrap().id, id);
+        assert_eq!(memory.list_tournaments().len(), 1);
+
+        memory.delete_tournament(id);
+        assert!(memory.get_tournament(id).is_none());
+        assert!(memory.list_tournaments().is_empty());
+    }
+
+    #[test]
+    fn test_memory_bracket_state() {
+        let memory = Memory::new(None).unwrap();
+
+        let id = memory.insert_tournament(&tournament());
+        assert!(memory.get_bracket_state(id, BracketId(1)).is_none());
+    }
+
+    #[test]
+    fn test_memory_serialize() {
+        let memory = Memory::new(None).unwrap();
+        let id = memory.insert_tournament(&tournament());
+
+        let buf = serde_json::to_vec(&*memory.data.read()).unwrap();
+        let data: Data = serde_json::from_slice(&buf).unwrap();
+
+        assert_eq!(data.next_id, id.0);
+        assert!(data.tournaments.contains_key(&id.0));
+    }
+}
@@ -0,0 +1,316 @@
+use super::Driver;
+
+/// Column types that differ between the backends.
+struct Types {
+    /// An auto incrementing primary key.
+    serial: &'static str,
+    /// A 64-bit integer.
+    int: &'static str,
+    /// An 8-bit integer.
+    tinyint: &'static str,
+    timestamp: &'static str,
+    blob: &'static str,
+}
+
+impl Types {
+    fn new(driver: Driver) -> Self {
+        match driver {
+            Driver::MySql => Self {
+                serial: "BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY",
+                int: "BIGINT UNSIGNED",
+                tinyint: "TINYINT UNSIGNED",
+                timestamp: "TIMESTAMP",
+                blob: "BLOB",
+            },
+            Driver::Postgres => Self {
+                serial: "BIGSERIAL PRIMARY KEY",
+                int: "BIGINT",
+                tinyint: "SMALLINT",
+                timestamp: "TIMESTAMPTZ",
+                blob: "BYTEA",
+            },
+            Driver::Sqlite => Self {
+                serial: "INTEGER PRIMARY KEY AUTOINCREMENT",
+                int: "INTEGER",
+                tinyint: "INTEGER",
+                timestamp: "DATETIME",
+                blob: "BLOB",
+            },
+            Driver::Memory => unreachable!("the memory backend has no schema"),
+        }
+    }
+}
+
+/// A schema migration.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct Migration {
+    /// The schema version after the migration was applied. Versions start at `1` and are
+    /// strictly increasing.
+    pub version: u64,
+    pub name: &'static str,
+    /// Returns the statements of the migration for the given driver and table prefix.
+    pub up: fn(Driver, &str) -> Vec<String>,
+}
+
+/// All migrations in the order they are applied. Applied migrations must never be changed, new
+/// migrations are appended with the next version.
+///
+/// Every migration is applied in a transaction where the backend supports transactional schema
+/// changes. MySQL does not, so all statements must be safe to repeat.
+pub(super) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial schema",
+        up: initial,
+    },
+    Migration {
+        version: 2,
+        name: "webhooks",
+        up: webhooks,
+    },
+    Migration {
+        version: 3,
+        name: "rating changes",
+        up: rating_changes,
+    },
+    Migration {
+        version: 4,
+        name: "user tokens",
+        up: user_tokens,
+    },
+];
+
+/// Returns the latest known schema version.
+pub(super) fn latest() -> u64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Returns the statement creating the table that stores the applied schema versions.
+pub(super) fn schema_version(driver: Driver, prefix: &str) -> String {
+    let Types { int, timestamp, .. } = Types::new(driver);
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {prefix}schema_version (
+            version {int} PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied {timestamp} NOT NULL
+        )"
+    )
+}
+
+/// Creates all tables. Databases created before versioned migrations were introduced already
+/// contain these tables, hence `IF NOT EXISTS`.
+fn initial(driver: Driver, prefix: &str) -> Vec<String> {
+    let Types {
+        serial,
+        int,
+        tinyint,
+        timestamp,
+        blob,
+    } = Types::new(driver);
+
+    vec![
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}tournaments (
+                id {serial},
+                name TEXT NOT NULL,
+                description TEXT NOT NULL,
+                date {timestamp} NOT NULL,
+                kind {tinyint} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}entrants (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}brackets (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                data {blob} NOT NULL,
+                state {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}roles (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                name TEXT NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}users (
+                id {int} PRIMARY KEY,
+                name TEXT NOT NULL,
+                password TEXT NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}log (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                date {timestamp} NOT NULL,
+                author {int} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}schedules (
+                tournament_id {int} NOT NULL,
+                bracket_id {int} NOT NULL,
+                data {blob} NOT NULL,
+                PRIMARY KEY (tournament_id, bracket_id)
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}veto_configs (
+                tournament_id {int} NOT NULL,
+                bracket_id {int} NOT NULL,
+                data {blob} NOT NULL,
+                PRIMARY KEY (tournament_id, bracket_id)
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}vetoes (
+                tournament_id {int} NOT NULL,
+                bracket_id {int} NOT NULL,
+                match_index {int} NOT NULL,
+                data {blob} NOT NULL,
+                PRIMARY KEY (tournament_id, bracket_id, match_index)
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}match_metadata (
+                tournament_id {int} NOT NULL,
+                bracket_id {int} NOT NULL,
+                match_index {int} NOT NULL,
+                data {blob} NOT NULL,
+                PRIMARY KEY (tournament_id, bracket_id, match_index)
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}rating_systems (
+                tournament_id {int} PRIMARY KEY,
+                data {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}rating_history (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                date {timestamp} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+    ]
+}
+
+/// Creates the webhook tables. MySQL commits every `CREATE TABLE` immediately, hence
+/// `IF NOT EXISTS` to safely repeat a partially applied migration.
+fn webhooks(driver: Driver, prefix: &str) -> Vec<String> {
+    let Types {
+        serial,
+        int,
+        timestamp,
+        blob,
+        ..
+    } = Types::new(driver);
+
+    vec![
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}webhooks (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}webhook_deliveries (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                webhook_id {int} NOT NULL,
+                date {timestamp} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+    ]
+}
+
+/// Moves the rating history into a table that can be queried by entrant and match. The old
+/// `rating_history` table is kept so that the statements can safely be repeated.
+fn rating_changes(driver: Driver, prefix: &str) -> Vec<String> {
+    let Types {
+        serial,
+        int,
+        timestamp,
+        blob,
+        ..
+    } = Types::new(driver);
+
+    // Extracts an integer field of the JSON encoded `RatingChange` in the `data` column.
+    let field = |name: &str| match driver {
+        Driver::MySql => {
+            format!("CAST(JSON_EXTRACT(CONVERT(data USING utf8mb4), '$.{name}') AS UNSIGNED)")
+        }
+        Driver::Postgres => format!("(convert_from(data, 'UTF8')::json->>'{name}')::BIGINT"),
+        Driver::Sqlite => format!("json_extract(CAST(data AS TEXT), '$.{name}')"),
+        Driver::Memory => unreachable!("the memory backend has no schema"),
+    };
+
+    vec![
+        format!(
+            "CREATE TABLE IF NOT EXISTS {prefix}rating_changes (
+                id {serial},
+                tournament_id {int} NOT NULL,
+                entrant_id {int} NOT NULL,
+                bracket_id {int} NOT NULL,
+                match_index {int} NOT NULL,
+                date {timestamp} NOT NULL,
+                data {blob} NOT NULL
+            )"
+        ),
+        format!(
+            "INSERT INTO {prefix}rating_changes
+                (tournament_id, entrant_id, bracket_id, match_index, date, data)
+            SELECT tournament_id, {entrant}, {bracket}, {index}, date, data
+            FROM {prefix}rating_history
+            WHERE NOT EXISTS (SELECT 1 FROM {prefix}rating_changes)
+            ORDER BY id",
+            entrant = field("entrant"),
+            bracket = field("bracket"),
+            index = field("index"),
+        ),
+    ]
+}
+
+/// Creates the table storing the time after which tokens of a user are valid. Users without a
+/// row have never revoked their tokens.
+fn user_tokens(driver: Driver, prefix: &str) -> Vec<String> {
+    let Types { int, .. } = Types::new(driver);
+
+    vec![format!(
+        "CREATE TABLE IF NOT EXISTS {prefix}user_tokens (
+            user_id {int} PRIMARY KEY,
+            valid_after {int} NOT NULL
+        )"
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{latest, MIGRATIONS};
+
+    #[test]
+    fn test_migrations_ordered() {
+        let mut version = 0;
+        for migration in MIGRATIONS {
+            assert_eq!(migration.version, version + 1);
+            version = migration.version;
+        }
+
+        assert_eq!(latest(), version);
+    }
+}
@@ -1,27 +1,406 @@
+mod memory;
+mod migrations;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config;
 use crate::Error;
-use dynamic_tournament_api::v3::id::{EventId, RoleId};
+use chrono::Utc;
+use dynamic_tournament_api::v3::id::{DeliveryId, EventId, RoleId, WebhookId};
+use dynamic_tournament_api::v3::tournaments::brackets::matches::MatchMetadata;
+use dynamic_tournament_api::v3::tournaments::brackets::schedule::Schedule;
+use dynamic_tournament_api::v3::tournaments::brackets::veto::{MatchVeto, VetoConfig};
 use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
-use dynamic_tournament_api::v3::tournaments::log::LogEvent;
+use dynamic_tournament_api::v3::tournaments::log::{LogEvent, LogEventBody};
+use dynamic_tournament_api::v3::tournaments::ratings::{RatingChange, RatingSystem};
 use dynamic_tournament_api::v3::tournaments::roles::Role;
+use dynamic_tournament_api::v3::tournaments::webhooks::{Delivery, Webhook};
 use dynamic_tournament_api::v3::tournaments::PartialTournament;
 use dynamic_tournament_api::v3::users::User;
 use dynamic_tournament_api::v3::{
     id::{BracketId, EntrantId, TournamentId, UserId},
     tournaments::{entrants::Entrant, EntrantKind, Tournament, TournamentOverview},
 };
+use dynamic_tournament_core::snapshot::Snapshot;
 use dynamic_tournament_core::{EntrantScore, Matches};
-use sqlx::mysql::MySqlPool;
+use sqlx::mysql::{MySqlPool, MySqlRow};
+use sqlx::pool::PoolOptions;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::sqlite::{SqlitePool, SqliteRow};
 use sqlx::Row;
 
 use futures::TryStreamExt;
+use memory::Memory;
+use migrations::Migration;
+use serde::{Deserialize, Serialize};
+
+// All macros below run a query on the backend of `$store`. The query is given using `?`
+// placeholders and converted to the syntax of the backend. Database errors are propagated using
+// `?`. The memory backend is handled separately by every method and never runs a query.
+
+/// Executes a query.
+macro_rules! execute {
+    ($store:expr, $sql:expr $(, $bind:expr)* $(,)?) => {{
+        let sql = $store.sql($sql);
+        match &$store.pool {
+            Pool::MySql(pool) => {
+                sqlx::query(&sql)$(.bind($bind))*.execute(pool).await?;
+            }
+            Pool::Postgres(pool) => {
+                sqlx::query(&sql)$(.bind($bind))*.execute(pool).await?;
+            }
+            Pool::Sqlite(pool) => {
+                sqlx::query(&sql)$(.bind($bind))*.execute(pool).await?;
+            }
+            Pool::Memory(_) => unreachable!("query on memory store"),
+        }
+    }};
+}
+
+/// Executes an `INSERT` query and returns the id of the inserted row.
+macro_rules! insert {
+    ($store:expr, $sql:expr $(, $bind:expr)* $(,)?) => {{
+        let sql = $store.sql($sql);
+        match &$store.pool {
+            Pool::MySql(pool) => sqlx::query(&sql)$(.bind($bind))*
+                .execute(pool)
+                .await?
+                .last_insert_id(),
+            // PostgreSQL has no equivalent of `LAST_INSERT_ID()`.
+            Pool::Postgres(pool) => {
+                let sql = format!("{} RETURNING id", sql);
+                let row = sqlx::query(&sql)$(.bind($bind))*.fetch_one(pool).await?;
+                row.try_get_u64("id")?
+            }
+            Pool::Sqlite(pool) => sqlx::query(&sql)$(.bind($bind))*
+                .execute(pool)
+                .await?
+                .last_insert_rowid() as u64,
+            Pool::Memory(_) => unreachable!("query on memory store"),
+        }
+    }};
+}
+
+/// Fetches a single row and maps it using `$body`. Evaluates to `None` if no row was returned.
+macro_rules! fetch_optional {
+    ($store:expr, $sql:expr $(, $bind:expr)* ; |$row:ident| $body:expr) => {{
+        let sql = $store.sql($sql);
+        match &$store.pool {
+            Pool::MySql(pool) => {
+                match sqlx::query(&sql)$(.bind($bind))*.fetch_optional(pool).await? {
+                    Some($row) => Some($body),
+                    None => None,
+                }
+            }
+            Pool::Postgres(pool) => {
+                match sqlx::query(&sql)$(.bind($bind))*.fetch_optional(pool).await? {
+                    Some($row) => Some($body),
+                    None => None,
+                }
+            }
+            Pool::Sqlite(pool) => {
+                match sqlx::query(&sql)$(.bind($bind))*.fetch_optional(pool).await? {
+                    Some($row) => Some($body),
+                    None => None,
+                }
+            }
+            Pool::Memory(_) => unreachable!("query on memory store"),
+        }
+    }};
+}
+
+/// Fetches all rows and maps every row using `$body`. Evaluates to a `Vec` of all mapped rows.
+macro_rules! fetch_all {
+    ($store:expr, $sql:expr $(, $bind:expr)* ; |$row:ident| $body:expr) => {{
+        let sql = $store.sql($sql);
+        let mut items = Vec::new();
+        match &$store.pool {
+            Pool::MySql(pool) => {
+                let mut rows = sqlx::query(&sql)$(.bind($bind))*.fetch(pool);
+                while let Some($row) = rows.try_next().await? {
+                    items.push($body);
+                }
+            }
+            Pool::Postgres(pool) => {
+                let mut rows = sqlx::query(&sql)$(.bind($bind))*.fetch(pool);
+                while let Some($row) = rows.try_next().await? {
+                    items.push($body);
+                }
+            }
+            Pool::Sqlite(pool) => {
+                let mut rows = sqlx::query(&sql)$(.bind($bind))*.fetch(pool);
+                while let Some($row) = rows.try_next().await? {
+                    items.push($body);
+                }
+            }
+            Pool::Memory(_) => unreachable!("query on memory store"),
+        }
+        items
+    }};
+}
+
+/// The database backend used by a [`Store`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Driver {
+    MySql,
+    Postgres,
+    Sqlite,
+    Memory,
+}
+
+impl FromStr for Driver {
+    type Err = sqlx::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "memory" => Ok(Self::Memory),
+            _ => Err(sqlx::Error::Configuration(
+                format!("unsupported database driver: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// A connection pool to one of the supported database backends.
+#[derive(Clone, Debug)]
+pub enum Pool {
+    MySql(MySqlPool),
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+    Memory(Arc<Memory>),
+}
 
 #[derive(Clone, Debug)]
 pub struct Store {
-    pub pool: MySqlPool,
+    pub pool: Pool,
     pub table_prefix: String,
+    /// Held while an entrant is read, modified and written back.
+    entrants_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl Store {
+    /// Creates a new `Store` using the backend selected by the `driver` of the given `config`.
+    /// No connection is established until the store is first used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if the driver is not supported or the connection options are
+    /// invalid.
+    pub fn connect_lazy(config: &config::Database) -> Result<Self, Error> {
+        let driver: Driver = config.driver.parse()?;
+        let uri = config.connect_string();
+
+        let pool = match driver {
+            Driver::MySql => Pool::MySql(pool_options().connect_lazy(&uri)?),
+            Driver::Postgres => Pool::Postgres(pool_options().connect_lazy(&uri)?),
+            Driver::Sqlite => Pool::Sqlite(pool_options().connect_lazy(&uri)?),
+            // The database is the path of the file the data is persisted to.
+            Driver::Memory => {
+                let path = match config.database.as_str() {
+                    "" => None,
+                    path => Some(PathBuf::from(path)),
+                };
+
+                Pool::Memory(Arc::new(Memory::new(path)?))
+            }
+        };
+
+        Ok(Self {
+            pool,
+            table_prefix: config.prefix.clone(),
+            entrants_lock: Arc::default(),
+        })
+    }
+
+    /// Creates a new `Store` that keeps all data in memory without persisting it.
+    #[cfg(test)]
+    pub fn memory() -> Self {
+        Self {
+            pool: Pool::Memory(Arc::new(Memory::new(None).unwrap())),
+            table_prefix: String::new(),
+            entrants_lock: Arc::default(),
+        }
+    }
+
+    /// Locks the entrants of all tournaments. Changes that read, modify and write back an
+    /// entrant must be made while holding the returned guard.
+    pub async fn lock_entrants(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.entrants_lock.lock().await
+    }
+
+    /// Returns the [`Driver`] of the backend.
+    pub fn driver(&self) -> Driver {
+        match self.pool {
+            Pool::MySql(_) => Driver::MySql,
+            Pool::Postgres(_) => Driver::Postgres,
+            Pool::Sqlite(_) => Driver::Sqlite,
+            Pool::Memory(_) => Driver::Memory,
+        }
+    }
+
+    /// Writes all data of the memory backend to its file. Does nothing for other backends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if the data cannot be written.
+    pub async fn persist(&self) -> Result<(), Error> {
+        match &self.pool {
+            Pool::Memory(memory) => memory.persist().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the current schema version of the database. Returns `0` if no migrations were
+    /// applied yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn schema_version(&self) -> Result<u64, Error> {
+        // The memory backend has no schema.
+        if let Pool::Memory(_) = self.pool {
+            return Ok(migrations::latest());
+        }
+
+        execute!(
+            self,
+            migrations::schema_version(self.driver(), &self.table_prefix)
+        );
+
+        let version = fetch_optional!(
+            self,
+            format!(
+                "SELECT version FROM {}schema_version ORDER BY version DESC LIMIT 1",
+                self.table_prefix
+            );
+            |row| row.try_get_u64("version")?
+        );
+
+        Ok(version.unwrap_or(0))
+    }
+
+    /// Applies all migrations newer than the current schema version of the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured or the schema version of the
+    /// database is newer than the latest known version.
+    pub async fn migrate(&self) -> Result<(), Error> {
+        let version = self.schema_version().await?;
+        let latest = migrations::latest();
+
+        if version > latest {
+            return Err(Error::SchemaVersion { version, latest });
+        }
+
+        for migration in migrations::MIGRATIONS {
+            if migration.version <= version {
+                continue;
+            }
+
+            log::info!(
+                "Applying migration {} ({})",
+                migration.version,
+                migration.name
+            );
+
+            self.apply(migration).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies a single migration and records its version. PostgreSQL and SQLite apply the
+    /// migration in a transaction. MySQL implicitly commits schema changes, so the version is
+    /// only recorded once all statements succeeded.
+    async fn apply(&self, migration: &Migration) -> Result<(), Error> {
+        let statements = (migration.up)(self.driver(), &self.table_prefix);
+        let version = self.sql(format!(
+            "INSERT INTO {}schema_version (version, name, applied) VALUES (?, ?, ?)",
+            self.table_prefix
+        ));
+
+        macro_rules! apply {
+            ($executor:expr) => {{
+                for sql in &statements {
+                    sqlx::query(sql).execute($executor).await?;
+                }
+
+                sqlx::query(&version)
+                    .bind(migration.version as i64)
+                    .bind(migration.name)
+                    .bind(Utc::now())
+                    .execute($executor)
+                    .await?;
+            }};
+        }
+
+        match &self.pool {
+            Pool::MySql(pool) => apply!(pool),
+            Pool::Postgres(pool) => {
+                let mut tx = pool.begin().await?;
+                apply!(&mut tx);
+                tx.commit().await?;
+            }
+            Pool::Sqlite(pool) => {
+                let mut tx = pool.begin().await?;
+                apply!(&mut tx);
+                tx.commit().await?;
+            }
+            Pool::Memory(_) => unreachable!("query on memory store"),
+        }
+
+        Ok(())
+    }
+
+    /// Converts the `?` placeholders in `sql` into the syntax of the backend.
+    fn sql(&self, sql: String) -> String {
+        match self.driver() {
+            Driver::MySql | Driver::Sqlite | Driver::Memory => sql,
+            Driver::Postgres => numbered_placeholders(&sql),
+        }
+    }
+
+    /// Returns a query that inserts a row into `table`, replacing the existing row with the same
+    /// `keys`. The first `keys` entries of `columns` are the primary key of `table`.
+    fn replace(&self, table: &str, columns: &[&str], keys: usize) -> String {
+        let placeholders = vec!["?"; columns.len()].join(", ");
+
+        match self.driver() {
+            Driver::MySql | Driver::Sqlite | Driver::Memory => format!(
+                "REPLACE INTO {}{} ({}) VALUES ({})",
+                self.table_prefix,
+                table,
+                columns.join(", "),
+                placeholders
+            ),
+            Driver::Postgres => {
+                let updates = columns[keys..]
+                    .iter()
+                    .map(|column| format!("{0} = EXCLUDED.{0}", column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!(
+                    "INSERT INTO {}{} ({}) VALUES ({}) ON CONFLICT ({}) DO UPDATE SET {}",
+                    self.table_prefix,
+                    table,
+                    columns.join(", "),
+                    placeholders,
+                    columns[..keys].join(", "),
+                    updates
+                )
+            }
+        }
+    }
+
     #[inline]
     pub fn tournaments(&self) -> TournamentsClient<'_> {
         TournamentsClient { store: self }
@@ -52,70 +431,105 @@ impl Store {
         EventLogClient { store: self, id }
     }
 
-    pub async fn insert_tournament(&self, tournament: &Tournament) -> Result<TournamentId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}tournaments (name, description, date, kind) VALUES (?, ?, ?, ?)",
-            self.table_prefix
-        ))
-        .bind(&tournament.name)
-        .bind(&tournament.description)
-        .bind(tournament.date)
-        .bind(tournament.kind.to_u8())
-        .execute(&self.pool)
-        .await?;
+    /// Returns the log for all events that don't belong to a tournament.
+    #[inline]
+    pub fn system_log(&self) -> EventLogClient<'_> {
+        // Tournament ids start at 1, the id 0 is never assigned to a tournament.
+        EventLogClient {
+            store: self,
+            id: TournamentId(0),
+        }
+    }
 
-        let id = res.last_insert_id();
+    #[inline]
+    pub fn schedules(&self, id: TournamentId) -> SchedulesClient<'_> {
+        SchedulesClient { store: self, id }
+    }
 
-        Ok(TournamentId(id))
+    #[inline]
+    pub fn vetoes(&self, id: TournamentId) -> VetoesClient<'_> {
+        VetoesClient { store: self, id }
     }
 
-    pub async fn list_tournaments(&self) -> Result<Vec<TournamentOverview>, Error> {
-        let sql = format!(
-            "SELECT id, name, date, kind FROM {}tournaments",
-            self.table_prefix
-        );
+    #[inline]
+    pub fn match_metadata(&self, id: TournamentId) -> MatchMetadataClient<'_> {
+        MatchMetadataClient { store: self, id }
+    }
+
+    #[inline]
+    pub fn ratings(&self, id: TournamentId) -> RatingsClient<'_> {
+        RatingsClient { store: self, id }
+    }
 
-        let mut rows = sqlx::query(&sql).fetch(&self.pool);
+    #[inline]
+    pub fn webhooks(&self, id: TournamentId) -> WebhooksClient<'_> {
+        WebhooksClient { store: self, id }
+    }
 
-        let mut tournaments = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let name = row.try_get("name")?;
-            let date = row.try_get("date")?;
-            let kind = row.try_get("kind")?;
+    pub async fn insert_tournament(&self, tournament: &Tournament) -> Result<TournamentId, Error> {
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.insert_tournament(tournament));
+        }
 
-            tournaments.push(TournamentOverview {
-                id: TournamentId(id),
-                name,
-                date,
-                kind: EntrantKind::from_u8(kind).unwrap(),
-            });
+        let id = insert!(
+            self,
+            format!(
+                "INSERT INTO {}tournaments (name, description, date, kind) VALUES (?, ?, ?, ?)",
+                self.table_prefix
+            ),
+            &tournament.name,
+            &tournament.description,
+            tournament.date,
+            i16::from(tournament.kind.to_u8()),
+        );
+
+        Ok(TournamentId(id))
+    }
+
+    pub async fn list_tournaments(&self) -> Result<Vec<TournamentOverview>, Error> {
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.list_tournaments());
         }
 
+        let tournaments = fetch_all!(
+            self,
+            format!(
+                "SELECT id, name, date, kind FROM {}tournaments",
+                self.table_prefix
+            );
+            |row| TournamentOverview {
+                id: TournamentId(row.try_get_u64("id")?),
+                name: row.try_get("name")?,
+                date: row.try_get("date")?,
+                kind: EntrantKind::from_u8(row.try_get_u8("kind")?).unwrap(),
+            }
+        );
+
         Ok(tournaments)
     }
 
     pub async fn get_tournament(&self, id: TournamentId) -> Result<Option<Tournament>, Error> {
-        let row = match sqlx::query(&format!(
-            "SELECT name, date, description, kind FROM {}tournaments WHERE id = ?",
-            self.table_prefix
-        ))
-        .bind(id.as_ref())
-        .fetch_one(&self.pool)
-        .await
-        {
-            Ok(v) => v,
-            Err(sqlx::Error::RowNotFound) => return Ok(None),
-            Err(err) => return Err(err.into()),
-        };
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.get_tournament(id));
+        }
+
+        let tournament = fetch_optional!(
+            self,
+            format!(
+                "SELECT name, date, description, kind FROM {}tournaments WHERE id = ?",
+                self.table_prefix
+            ),
+            id.0 as i64;
+            |row| Tournament {
+                id,
+                name: row.try_get("name")?,
+                description: row.try_get("description")?,
+                date: row.try_get("date")?,
+                kind: EntrantKind::from_u8(row.try_get_u8("kind")?).unwrap(),
+            }
+        );
 
-        Ok(Some(Tournament {
-            id,
-            name: row.try_get("name")?,
-            description: row.try_get("description")?,
-            date: row.try_get("date")?,
-            kind: EntrantKind::from_u8(row.try_get("kind")?).unwrap(),
-        }))
+        Ok(tournament)
     }
 
     pub async fn insert_entrant(
@@ -123,16 +537,19 @@ impl Store {
         tournament_id: TournamentId,
         entrant: Entrant,
     ) -> Result<EntrantId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}entrants (tournament_id, data) VALUES (?, ?)",
-            self.table_prefix
-        ))
-        .bind(tournament_id.0)
-        .bind(serde_json::to_vec(&entrant)?)
-        .execute(&self.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.insert_entrant(tournament_id, &entrant));
+        }
 
-        let id = res.last_insert_id();
+        let id = insert!(
+            self,
+            format!(
+                "INSERT INTO {}entrants (tournament_id, data) VALUES (?, ?)",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64,
+            serde_json::to_vec(&entrant)?,
+        );
 
         Ok(EntrantId(id))
     }
@@ -142,67 +559,77 @@ impl Store {
         tournament_id: TournamentId,
         id: EntrantId,
     ) -> Result<Option<Entrant>, Error> {
-        let row = match sqlx::query(&format!(
-            "SELECT data FROM {}entrants WHERE tournament_id = ? AND id = ?",
-            self.table_prefix
-        ))
-        .bind(tournament_id.0)
-        .bind(id.0)
-        .fetch_one(&self.pool)
-        .await
-        {
-            Ok(v) => v,
-            Err(sqlx::Error::RowNotFound) => return Ok(None),
-            Err(err) => return Err(err.into()),
-        };
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.get_entrant(tournament_id, id));
+        }
 
-        let mut entrant: Entrant = serde_json::from_slice(row.try_get("data")?)?;
-        entrant.id = id;
+        let entrant = fetch_optional!(
+            self,
+            format!(
+                "SELECT data FROM {}entrants WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut entrant: Entrant = serde_json::from_slice(&data)?;
+                entrant.id = id;
+                entrant
+            }
+        );
 
-        Ok(Some(entrant))
+        Ok(entrant)
     }
 
     pub async fn get_entrants(&self, tournament_id: TournamentId) -> Result<Vec<Entrant>, Error> {
-        let sql = format!(
-            "SELECT id, data FROM {}entrants WHERE tournament_id = ?",
-            self.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).bind(tournament_id.0).fetch(&self.pool);
-
-        let mut entrants = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let data: Vec<u8> = row.try_get("data")?;
-
-            let mut inner: Entrant = serde_json::from_slice(&data)?;
-            inner.id = EntrantId(id);
-
-            entrants.push(inner);
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.list_entrants(tournament_id));
         }
 
+        let entrants = fetch_all!(
+            self,
+            format!(
+                "SELECT id, data FROM {}entrants WHERE tournament_id = ?",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut inner: Entrant = serde_json::from_slice(&data)?;
+                inner.id = EntrantId(id);
+                inner
+            }
+        );
+
         Ok(entrants)
     }
 
     pub async fn list_brackets(&self, tournament_id: TournamentId) -> Result<Vec<Bracket>, Error> {
-        let sql = format!(
-            "SELECT id, data FROM {}brackets WHERE tournament_id = ?",
-            self.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).bind(tournament_id.0).fetch(&self.pool);
-
-        let mut brackets = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let data: Vec<u8> = row.try_get("data")?;
-
-            let mut bracket: Bracket = serde_json::from_slice(&data)?;
-            bracket.id = BracketId(id);
-
-            brackets.push(bracket);
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.list_brackets(tournament_id));
         }
 
+        let brackets = fetch_all!(
+            self,
+            format!(
+                "SELECT id, data FROM {}brackets WHERE tournament_id = ?",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut bracket: Bracket = serde_json::from_slice(&data)?;
+                bracket.id = BracketId(id);
+                bracket
+            }
+        );
+
         Ok(brackets)
     }
 
@@ -211,97 +638,239 @@ impl Store {
         tournament_id: TournamentId,
         bracket: &Bracket,
     ) -> Result<BracketId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}brackets (tournament_id, data, state) VALUES (?, ?, ?)",
-            self.table_prefix
-        ))
-        .bind(tournament_id.0)
-        .bind(serde_json::to_vec(bracket)?)
-        .bind(serde_json::to_vec::<Option<u8>>(&None)?)
-        .execute(&self.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.insert_bracket(tournament_id, bracket));
+        }
 
-        let id = res.last_insert_id();
+        let id = insert!(
+            self,
+            format!(
+                "INSERT INTO {}brackets (tournament_id, data, state) VALUES (?, ?, ?)",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64,
+            serde_json::to_vec(bracket)?,
+            serde_json::to_vec::<Option<u8>>(&None)?,
+        );
 
         Ok(BracketId(id))
     }
 
+    /// Updates the [`Bracket`] with the given `id`. The state of the bracket is not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn update_bracket(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        bracket: &Bracket,
+    ) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.pool {
+            memory.update_bracket(tournament_id, id, bracket);
+            return Ok(());
+        }
+
+        execute!(
+            self,
+            format!(
+                "UPDATE {}brackets SET data = ? WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            serde_json::to_vec(bracket)?,
+            tournament_id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+
     pub async fn get_bracket(
         &self,
         tournament_id: TournamentId,
         id: BracketId,
     ) -> Result<Option<Bracket>, Error> {
-        let row = match sqlx::query(&format!(
-            "SELECT data FROM {}brackets WHERE tournament_id = ? AND id = ?",
-            self.table_prefix
-        ))
-        .bind(tournament_id.0)
-        .bind(id.0)
-        .fetch_one(&self.pool)
-        .await
-        {
-            Ok(v) => v,
-            Err(sqlx::Error::RowNotFound) => return Ok(None),
-            Err(err) => return Err(err.into()),
-        };
-
-        let data: Vec<u8> = row.try_get("data")?;
+        if let Pool::Memory(memory) = &self.pool {
+            return Ok(memory.get_bracket(tournament_id, id));
+        }
 
-        let mut bracket: Bracket = serde_json::from_slice(&data)?;
-        bracket.id = id;
+        let bracket = fetch_optional!(
+            self,
+            format!(
+                "SELECT data FROM {}brackets WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut bracket: Bracket = serde_json::from_slice(&data)?;
+                bracket.id = id;
+                bracket
+            }
+        );
 
-        Ok(Some(bracket))
+        Ok(bracket)
     }
 
     pub async fn get_bracket_state(
         &self,
         tournament_id: TournamentId,
         id: BracketId,
-    ) -> Result<Option<Matches<EntrantScore<u64>>>, Error> {
-        let row = sqlx::query(&format!(
-            "SELECT state FROM {}brackets WHERE tournament_id = ? AND id = ?",
-            self.table_prefix
-        ))
-        .bind(tournament_id.0)
-        .bind(id.0)
-        .fetch_one(&self.pool)
-        .await?;
+    ) -> Result<Option<BracketState>, Error> {
+        if let Pool::Memory(memory) = &self.pool {
+            return memory
+                .get_bracket_state(tournament_id, id)
+                .ok_or_else(|| sqlx::Error::RowNotFound.into());
+        }
+
+        let state = fetch_optional!(
+            self,
+            format!(
+                "SELECT state FROM {}brackets WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            tournament_id.0 as i64,
+            id.0 as i64;
+            |row| row.try_get::<Vec<u8>, _>("state")?
+        );
 
-        let state: Vec<u8> = row.try_get("state")?;
+        let state = match state {
+            Some(state) => state,
+            None => return Err(sqlx::Error::RowNotFound.into()),
+        };
 
-        let matches = serde_json::from_slice(&state)?;
+        let state = serde_json::from_slice(&state)?;
 
-        Ok(matches)
+        Ok(state)
     }
 
     pub async fn update_bracket_state(
         &self,
         tournament_id: TournamentId,
         id: BracketId,
-        state: &Option<Matches<EntrantScore<u64>>>,
+        state: &Option<BracketState>,
     ) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "UPDATE {}brackets SET state = ? WHERE tournament_id = ? AND id = ?",
-            self.table_prefix
-        ))
-        .bind(serde_json::to_vec(state)?)
-        .bind(tournament_id.0)
-        .bind(id.0)
-        .execute(&self.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.pool {
+            memory.update_bracket_state(tournament_id, id, state);
+            return Ok(());
+        }
+
+        execute!(
+            self,
+            format!(
+                "UPDATE {}brackets SET state = ? WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            serde_json::to_vec(state)?,
+            tournament_id.0 as i64,
+            id.0 as i64,
+        );
 
         Ok(())
     }
-}
 
-macro_rules! get_one {
-    ($query:expr) => {
-        match $query {
-            Ok(v) => v,
-            Err(sqlx::Error::RowNotFound) => return Ok(None),
-            Err(err) => return Err(err.into()),
+    /// Updates the [`Bracket`] with the given `id` together with its state. Both are written in
+    /// a single statement, so the stored state always belongs to the stored bracket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn update_bracket_with_state(
+        &self,
+        tournament_id: TournamentId,
+        id: BracketId,
+        bracket: &Bracket,
+        state: &Option<BracketState>,
+    ) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.pool {
+            memory.update_bracket_with_state(tournament_id, id, bracket, state);
+            return Ok(());
         }
-    };
+
+        execute!(
+            self,
+            format!(
+                "UPDATE {}brackets SET data = ?, state = ? WHERE tournament_id = ? AND id = ?",
+                self.table_prefix
+            ),
+            serde_json::to_vec(bracket)?,
+            serde_json::to_vec(state)?,
+            tournament_id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+}
+
+/// The stored state of a bracket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BracketState {
+    /// A [`Snapshot`] together with the revisions of all matches and the times at which matches
+    /// were concluded.
+    Live {
+        snapshot: Snapshot<EntrantId, EntrantScore<u64>>,
+        revisions: Vec<u64>,
+        /// Pairs of the match index and the time in seconds since the unix epoch.
+        finished: Vec<(usize, u64)>,
+    },
+    /// A [`Snapshot`] stored before the revisions of matches were stored.
+    Snapshot(Snapshot<EntrantId, EntrantScore<u64>>),
+    /// The matches of a bracket stored before brackets were stored as [`Snapshot`]s.
+    Legacy(Matches<EntrantScore<u64>>),
+}
+
+/// Returns the [`PoolOptions`] shared by all backends.
+fn pool_options<DB>() -> PoolOptions<DB>
+where
+    DB: sqlx::Database,
+{
+    PoolOptions::new()
+        .max_connections(8)
+        .max_lifetime(Duration::new(3600, 0))
+        .idle_timeout(Duration::new(60, 0))
+}
+
+/// Decoding of integer columns. MySQL uses unsigned columns, which are not available in
+/// PostgreSQL and SQLite.
+trait RowExt {
+    fn try_get_u64(&self, column: &str) -> Result<u64, sqlx::Error>;
+
+    fn try_get_u8(&self, column: &str) -> Result<u8, sqlx::Error>;
+}
+
+impl RowExt for MySqlRow {
+    fn try_get_u64(&self, column: &str) -> Result<u64, sqlx::Error> {
+        self.try_get(column)
+    }
+
+    fn try_get_u8(&self, column: &str) -> Result<u8, sqlx::Error> {
+        self.try_get(column)
+    }
+}
+
+impl RowExt for PgRow {
+    fn try_get_u64(&self, column: &str) -> Result<u64, sqlx::Error> {
+        self.try_get::<i64, _>(column).map(|v| v as u64)
+    }
+
+    fn try_get_u8(&self, column: &str) -> Result<u8, sqlx::Error> {
+        self.try_get::<i16, _>(column).map(|v| v as u8)
+    }
+}
+
+impl RowExt for SqliteRow {
+    fn try_get_u64(&self, column: &str) -> Result<u64, sqlx::Error> {
+        self.try_get::<i64, _>(column).map(|v| v as u64)
+    }
+
+    fn try_get_u8(&self, column: &str) -> Result<u8, sqlx::Error> {
+        self.try_get::<i64, _>(column).map(|v| v as u8)
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -316,31 +885,34 @@ impl<'a> TournamentsClient<'a> {
     ///
     /// Returns an [`enum@Error`] if an database error occured.
     pub async fn list(&self) -> Result<Vec<TournamentOverview>, Error> {
-        let sql = format!(
-            "SELECT id, name, date, kind FROM {}tournaments ORDER BY date DESC",
-            self.store.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).fetch(&self.store.pool);
-
-        let mut tournaments = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let name = row.try_get("name")?;
-            let date = row.try_get("date")?;
-            let kind = row.try_get("kind")?;
-
-            let id = TournamentId(id);
-            let kind = EntrantKind::from_u8(kind).unwrap();
-
-            tournaments.push(TournamentOverview {
-                id,
-                name,
-                date,
-                kind,
-            });
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_tournaments());
         }
 
+        let tournaments = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, name, date, kind FROM {}tournaments ORDER BY date DESC",
+                self.store.table_prefix
+            );
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let name = row.try_get("name")?;
+                let date = row.try_get("date")?;
+                let kind = row.try_get_u8("kind")?;
+
+                let id = TournamentId(id);
+                let kind = EntrantKind::from_u8(kind).unwrap();
+
+                TournamentOverview {
+                    id,
+                    name,
+                    date,
+                    kind,
+                }
+            }
+        );
+
         Ok(tournaments)
     }
 
@@ -351,28 +923,34 @@ impl<'a> TournamentsClient<'a> {
     ///
     /// Returns an [`enum@Error`] if an database error occured.
     pub async fn get(&self, id: TournamentId) -> Result<Option<Tournament>, Error> {
-        let row = get_one!(
-            sqlx::query(&format!(
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_tournament(id));
+        }
+
+        let tournament = fetch_optional!(
+            self.store,
+            format!(
                 "SELECT name, date, description, kind FROM {}tournaments WHERE id = ?",
                 self.store.table_prefix
-            ))
-            .bind(id.0)
-            .fetch_one(&self.store.pool)
-            .await
+            ),
+            id.0 as i64;
+            |row| {
+                let name = row.try_get("name")?;
+                let description = row.try_get("description")?;
+                let date = row.try_get("date")?;
+                let kind = EntrantKind::from_u8(row.try_get_u8("kind")?).unwrap();
+
+                Tournament {
+                    id,
+                    name,
+                    description,
+                    date,
+                    kind,
+                }
+            }
         );
 
-        let name = row.try_get("name")?;
-        let description = row.try_get("description")?;
-        let date = row.try_get("date")?;
-        let kind = EntrantKind::from_u8(row.try_get("kind")?).unwrap();
-
-        Ok(Some(Tournament {
-            id,
-            name,
-            description,
-            date,
-            kind,
-        }))
+        Ok(tournament)
     }
 
     /// Inserts a new [`Tournament`] and returns the [`TournamentId`] for the newly created value.
@@ -381,18 +959,23 @@ impl<'a> TournamentsClient<'a> {
     ///
     /// Returns an [`enum@Error`] if an database error occured.
     pub async fn insert(&self, tournament: &Tournament) -> Result<TournamentId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}tournaments (name, description, date, kind) VALUES (?, ?, ?, ?)",
-            self.store.table_prefix
-        ))
-        .bind(&tournament.name)
-        .bind(&tournament.description)
-        .bind(tournament.date)
-        .bind(tournament.kind.to_u8())
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.insert_tournament(tournament));
+        }
+
+        let id = insert!(
+            self.store,
+            format!(
+                "INSERT INTO {}tournaments (name, description, date, kind) VALUES (?, ?, ?, ?)",
+                self.store.table_prefix
+            ),
+            &tournament.name,
+            &tournament.description,
+            tournament.date,
+            i16::from(tournament.kind.to_u8()),
+        );
 
-        Ok(TournamentId(res.last_insert_id()))
+        Ok(TournamentId(id))
     }
 
     /// Deletes the [`Tournament`] with the given `id`.
@@ -401,38 +984,44 @@ impl<'a> TournamentsClient<'a> {
     ///
     /// Returns an [`enum@Error`] if an database error occured.
     pub async fn delete(&self, id: TournamentId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_tournament(id);
+            return Ok(());
+        }
+
         // FIXME: Join all futures for better speeeed.
-        sqlx::query(&format!(
-            "DELETE FROM {}tournaments WHERE id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
-
-        sqlx::query(&format!(
-            "DELETE FROM {}entrants WHERE tournament_id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
-
-        sqlx::query(&format!(
-            "DELETE FROM {}brackets WHERE tournament_id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
-
-        sqlx::query(&format!(
-            "DELETE FROM {}roles WHERE tournament_id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}tournaments WHERE id = ?",
+                self.store.table_prefix
+            ),
+            id.0 as i64,
+        );
+
+        for table in [
+            "entrants",
+            "brackets",
+            "roles",
+            "schedules",
+            "veto_configs",
+            "vetoes",
+            "match_metadata",
+            "rating_systems",
+            "rating_history",
+            "rating_changes",
+            "webhooks",
+            "webhook_deliveries",
+        ] {
+            execute!(
+                self.store,
+                format!(
+                    "DELETE FROM {}{} WHERE tournament_id = ?",
+                    self.store.table_prefix, table
+                ),
+                id.0 as i64,
+            );
+        }
 
         Ok(())
     }
@@ -452,37 +1041,45 @@ impl<'a> TournamentsClient<'a> {
         id: TournamentId,
         tournament: &PartialTournament,
     ) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.update_tournament(id, tournament);
+            return Ok(());
+        }
+
         if let Some(name) = &tournament.name {
-            sqlx::query(&format!(
-                "UPDATE {}tournaments SET name = ? WHERE id = ?",
-                self.store.table_prefix
-            ))
-            .bind(name)
-            .bind(id.0)
-            .execute(&self.store.pool)
-            .await?;
+            execute!(
+                self.store,
+                format!(
+                    "UPDATE {}tournaments SET name = ? WHERE id = ?",
+                    self.store.table_prefix
+                ),
+                name,
+                id.0 as i64,
+            );
         }
 
         if let Some(description) = &tournament.description {
-            sqlx::query(&format!(
-                "UPDATE {}tournaments SET description = ? WHERE id = ?",
-                self.store.table_prefix
-            ))
-            .bind(description)
-            .bind(id.0)
-            .execute(&self.store.pool)
-            .await?;
+            execute!(
+                self.store,
+                format!(
+                    "UPDATE {}tournaments SET description = ? WHERE id = ?",
+                    self.store.table_prefix
+                ),
+                description,
+                id.0 as i64,
+            );
         }
 
         if let Some(date) = tournament.date {
-            sqlx::query(&format!(
-                "UPDATE {}tournaments SET date = ? WHERE id = ?",
-                self.store.table_prefix
-            ))
-            .bind(date)
-            .bind(id.0)
-            .execute(&self.store.pool)
-            .await?;
+            execute!(
+                self.store,
+                format!(
+                    "UPDATE {}tournaments SET date = ? WHERE id = ?",
+                    self.store.table_prefix
+                ),
+                date,
+                id.0 as i64,
+            );
         }
 
         if let Some(kind) = tournament.kind {
@@ -492,14 +1089,15 @@ impl<'a> TournamentsClient<'a> {
                 assert!(entrants.is_empty());
             }
 
-            sqlx::query(&format!(
-                "UPDATE {}tournaments SET kind = ? WHERE id = ?",
-                self.store.table_prefix
-            ))
-            .bind(kind.to_u8())
-            .bind(id.0)
-            .execute(&self.store.pool)
-            .await?;
+            execute!(
+                self.store,
+                format!(
+                    "UPDATE {}tournaments SET kind = ? WHERE id = ?",
+                    self.store.table_prefix
+                ),
+                i16::from(kind.to_u8()),
+                id.0 as i64,
+            );
         }
 
         Ok(())
@@ -514,62 +1112,82 @@ pub struct EntrantsClient<'a> {
 
 impl<'a> EntrantsClient<'a> {
     pub async fn list(&self) -> Result<Vec<Entrant>, Error> {
-        let sql = format!(
-            "SELECT id, data FROM {}entrants WHERE tournament_id = ?",
-            self.store.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).bind(self.id.0).fetch(&self.store.pool);
-
-        let mut entrants = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let data: Vec<u8> = row.try_get("data")?;
-
-            let mut inner: Entrant = serde_json::from_slice(&data)?;
-            inner.id = EntrantId(id);
-
-            entrants.push(inner);
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_entrants(self.id));
         }
 
+        let entrants = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, data FROM {}entrants WHERE tournament_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut inner: Entrant = serde_json::from_slice(&data)?;
+                inner.id = EntrantId(id);
+                inner
+            }
+        );
+
         Ok(entrants)
     }
 
     pub async fn insert(&self, entrant: &Entrant) -> Result<EntrantId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}entrants (tournament_id, data) VALUES (?, ?)",
-            self.store.table_prefix
-        ))
-        .bind(self.id.0)
-        .bind(serde_json::to_vec(entrant)?)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.insert_entrant(self.id, entrant));
+        }
+
+        let id = insert!(
+            self.store,
+            format!(
+                "INSERT INTO {}entrants (tournament_id, data) VALUES (?, ?)",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            serde_json::to_vec(entrant)?,
+        );
 
-        Ok(EntrantId(res.last_insert_id()))
+        Ok(EntrantId(id))
     }
 
     pub async fn delete(&self, id: EntrantId) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "DELETE FROM {}entrants WHERE tournament_id = ? AND id = ?",
-            self.store.table_prefix
-        ))
-        .bind(self.id.0)
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_entrant(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}entrants WHERE tournament_id = ? AND id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
 
         Ok(())
     }
 
     pub async fn update(&self, id: EntrantId, entrant: &Entrant) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "UPDATE {}entrants SET data = ? WHERE id = ?",
-            self.store.table_prefix,
-        ))
-        .bind(serde_json::to_vec(entrant)?)
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.update_entrant(id, entrant);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "UPDATE {}entrants SET data = ? WHERE id = ?",
+                self.store.table_prefix,
+            ),
+            serde_json::to_vec(entrant)?,
+            id.0 as i64,
+        );
 
         Ok(())
     }
@@ -583,65 +1201,77 @@ pub struct RolesClient<'a> {
 
 impl<'a> RolesClient<'a> {
     pub async fn list(&self) -> Result<Vec<Role>, Error> {
-        let sql = format!(
-            "SELECT id, name FROM {}roles WHERE tournament_id = ?",
-            self.store.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).bind(self.id.0).fetch(&self.store.pool);
-
-        let mut roles = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let name = row.try_get("name")?;
-
-            roles.push(Role {
-                id: RoleId(id),
-                name,
-            });
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_roles(self.id));
         }
 
+        let roles = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, name FROM {}roles WHERE tournament_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| Role {
+                id: RoleId(row.try_get_u64("id")?),
+                name: row.try_get("name")?,
+            }
+        );
+
         Ok(roles)
     }
 
     pub async fn get(&self, id: RoleId) -> Result<Option<Role>, Error> {
-        let row = get_one!(
-            sqlx::query(&format!(
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_role(self.id, id));
+        }
+
+        let role = fetch_optional!(
+            self.store,
+            format!(
                 "SELECT name FROM {}roles WHERE tournament_id = ? AND id = ?",
                 self.store.table_prefix
-            ))
-            .bind(self.id.0)
-            .bind(id.0)
-            .fetch_one(&self.store.pool)
-            .await
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| Role {
+                id,
+                name: row.try_get("name")?,
+            }
         );
 
-        let name = row.try_get("name")?;
-
-        Ok(Some(Role { id, name }))
+        Ok(role)
     }
 
     pub async fn insert(&self, role: &Role) -> Result<RoleId, Error> {
-        let res = sqlx::query(&format!(
-            "INSERT INTO {}roles (name, tournament_id) VALUES (?, ?)",
-            self.store.table_prefix
-        ))
-        .bind(&role.name)
-        .bind(self.id.0)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.insert_role(self.id, role));
+        }
+
+        let id = insert!(
+            self.store,
+            format!(
+                "INSERT INTO {}roles (name, tournament_id) VALUES (?, ?)",
+                self.store.table_prefix
+            ),
+            &role.name,
+            self.id.0 as i64,
+        );
 
-        Ok(RoleId(res.last_insert_id()))
+        Ok(RoleId(id))
     }
 
     pub async fn delete(&self, id: RoleId) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "DELETE FROM {}roles WHERE id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_role(id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!("DELETE FROM {}roles WHERE id = ?", self.store.table_prefix),
+            id.0 as i64,
+        );
 
         Ok(())
     }
@@ -654,36 +1284,183 @@ pub struct UsersClient<'a> {
 
 impl<'a> UsersClient<'a> {
     pub async fn get(&self, username: &str) -> Result<Option<User>, Error> {
-        let row = get_one!(
-            sqlx::query(&format!(
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_user(username));
+        }
+
+        let user = fetch_optional!(
+            self.store,
+            format!(
                 "SELECT id, password FROM {}users WHERE name = ?",
                 self.store.table_prefix
-            ))
-            .bind(username)
-            .fetch_one(&self.store.pool)
-            .await
+            ),
+            username;
+            |row| User {
+                id: UserId(row.try_get_u64("id")?),
+                username: username.to_string(),
+                password: row.try_get("password")?,
+            }
+        );
+
+        Ok(user)
+    }
+
+    /// Returns all users. Note that the returned users include the password hashes.
+    pub async fn list(&self) -> Result<Vec<User>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_users());
+        }
+
+        let users = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, name, password FROM {}users ORDER BY name",
+                self.store.table_prefix
+            );
+            |row| User {
+                id: UserId(row.try_get_u64("id")?),
+                username: row.try_get("name")?,
+                password: row.try_get("password")?,
+            }
         );
 
-        let id = row.try_get("id")?;
-        let password = row.try_get("password")?;
+        Ok(users)
+    }
+
+    pub async fn get_by_id(&self, id: UserId) -> Result<Option<User>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_user_by_id(id));
+        }
+
+        let user = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT name, password FROM {}users WHERE id = ?",
+                self.store.table_prefix
+            ),
+            id.0 as i64;
+            |row| User {
+                id,
+                username: row.try_get("name")?,
+                password: row.try_get("password")?,
+            }
+        );
 
-        Ok(Some(User {
-            id: UserId(id),
-            username: username.to_string(),
-            password,
-        }))
+        Ok(user)
     }
 
     pub async fn insert(&self, user: &User) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "INSERT INTO {}users (id, name, password) VALUES (?, ?, ?)",
-            self.store.table_prefix
-        ))
-        .bind(user.id.0)
-        .bind(&user.username)
-        .bind(&user.password)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_user(user);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "INSERT INTO {}users (id, name, password) VALUES (?, ?, ?)",
+                self.store.table_prefix
+            ),
+            user.id.0 as i64,
+            &user.username,
+            &user.password,
+        );
+
+        Ok(())
+    }
+
+    /// Updates the username and password hash of the user with the id of `user`.
+    pub async fn update(&self, user: &User) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_user(user);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "UPDATE {}users SET name = ?, password = ? WHERE id = ?",
+                self.store.table_prefix
+            ),
+            &user.username,
+            &user.password,
+            user.id.0 as i64,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: UserId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_user(id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!("DELETE FROM {}users WHERE id = ?", self.store.table_prefix),
+            id.0 as i64,
+        );
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}user_tokens WHERE user_id = ?",
+                self.store.table_prefix
+            ),
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the time in seconds after which tokens are valid for every user. This is `0` for
+    /// users that never revoked their tokens.
+    pub async fn list_tokens_valid_after(&self) -> Result<Vec<(UserId, u64)>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_tokens_valid_after());
+        }
+
+        let ids = fetch_all!(
+            self.store,
+            format!("SELECT id FROM {}users", self.store.table_prefix);
+            |row| UserId(row.try_get_u64("id")?)
+        );
+
+        let valid_after: HashMap<UserId, u64> = fetch_all!(
+            self.store,
+            format!(
+                "SELECT user_id, valid_after FROM {}user_tokens",
+                self.store.table_prefix
+            );
+            |row| (
+                UserId(row.try_get_u64("user_id")?),
+                row.try_get_u64("valid_after")?,
+            )
+        )
+        .into_iter()
+        .collect();
+
+        Ok(ids
+            .into_iter()
+            .map(|id| (id, valid_after.get(&id).copied().unwrap_or(0)))
+            .collect())
+    }
+
+    /// Sets the time in seconds after which tokens of the user with the given `id` are valid.
+    pub async fn set_tokens_valid_after(&self, id: UserId, valid_after: u64) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.set_tokens_valid_after(id, valid_after);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store
+                .replace("user_tokens", &["user_id", "valid_after"], 1),
+            id.0 as i64,
+            valid_after as i64,
+        );
 
         Ok(())
     }
@@ -697,49 +1474,70 @@ pub struct EventLogClient<'a> {
 
 impl<'a> EventLogClient<'a> {
     pub async fn list(&self) -> Result<Vec<LogEvent>, Error> {
-        let sql = format!(
-            "SELECT id, date, author, data FROM {}log WHERE tournament_id = ?",
-            self.store.table_prefix
-        );
-
-        let mut rows = sqlx::query(&sql).bind(self.id.0).fetch(&self.store.pool);
-
-        let mut events = Vec::new();
-        while let Some(row) = rows.try_next().await? {
-            let id = row.try_get("id")?;
-            let date = row.try_get("date")?;
-            let author = row.try_get("author")?;
-            let data: Vec<u8> = row.try_get("data")?;
-
-            let body = serde_json::from_slice(&data)?;
-
-            events.push(LogEvent {
-                id: EventId(id),
-                date,
-                author,
-                body,
-            });
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_events(self.id));
         }
 
+        let events = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, date, author, data FROM {}log WHERE tournament_id = ? ORDER BY id",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let date = row.try_get("date")?;
+                let author = row.try_get_u64("author")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let body = serde_json::from_slice(&data)?;
+
+                LogEvent {
+                    id: EventId(id),
+                    date,
+                    author,
+                    body,
+                }
+            }
+        );
+
         Ok(events)
     }
 
     pub async fn insert(&self, event: &LogEvent) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_event(self.id, event);
+            return Ok(());
+        }
+
         let body = serde_json::to_vec(&event.body)?;
 
-        sqlx::query(&format!(
-            "INSERT INTO {}log (tournament_id, date, author, data) VALUES (?, ?, ?, ?)",
-            self.store.table_prefix
-        ))
-        .bind(self.id.0)
-        .bind(event.date)
-        .bind(event.author)
-        .bind(body)
-        .execute(&self.store.pool)
-        .await?;
+        execute!(
+            self.store,
+            format!(
+                "INSERT INTO {}log (tournament_id, date, author, data) VALUES (?, ?, ?, ?)",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            event.date,
+            event.author as i64,
+            body,
+        );
 
         Ok(())
     }
+
+    /// Inserts a new event with the given `author` and `body` at the current time.
+    pub async fn push(&self, author: u64, body: LogEventBody) -> Result<(), Error> {
+        self.insert(&LogEvent {
+            id: EventId(0),
+            date: Utc::now(),
+            author,
+            body,
+        })
+        .await
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -749,14 +1547,1203 @@ pub struct BracketsClient<'a> {
 
 impl<'a> BracketsClient<'a> {
     pub async fn delete(&self, id: BracketId) -> Result<(), Error> {
-        sqlx::query(&format!(
-            "DELETE FROM {}brackets WHERE id = ?",
-            self.store.table_prefix
-        ))
-        .bind(id.0)
-        .execute(&self.store.pool)
-        .await?;
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_bracket(id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}brackets WHERE id = ?",
+                self.store.table_prefix
+            ),
+            id.0 as i64,
+        );
+
+        for table in ["schedules", "veto_configs", "vetoes", "match_metadata"] {
+            execute!(
+                self.store,
+                format!(
+                    "DELETE FROM {}{} WHERE bracket_id = ?",
+                    self.store.table_prefix, table
+                ),
+                id.0 as i64,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SchedulesClient<'a> {
+    store: &'a Store,
+    id: TournamentId,
+}
+
+impl<'a> SchedulesClient<'a> {
+    /// Returns the [`Schedule`] of the bracket with the given `id`. Returns `None` if the bracket
+    /// has no schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn get(&self, id: BracketId) -> Result<Option<Schedule>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_schedule(self.id, id));
+        }
+
+        let schedule = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT data FROM {}schedules WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(schedule)
+    }
+
+    /// Inserts the [`Schedule`] for the bracket with the given `id`, overwriting the existing
+    /// schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn insert(&self, id: BracketId, schedule: &Schedule) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_schedule(self.id, id, schedule);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store
+                .replace("schedules", &["tournament_id", "bracket_id", "data"], 2),
+            self.id.0 as i64,
+            id.0 as i64,
+            serde_json::to_vec(schedule)?,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: BracketId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_schedule(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}schedules WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct VetoesClient<'a> {
+    store: &'a Store,
+    id: TournamentId,
+}
+
+impl<'a> VetoesClient<'a> {
+    /// Returns the [`VetoConfig`] of the bracket with the given `id`. Returns `None` if the
+    /// bracket has no veto configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn config(&self, id: BracketId) -> Result<Option<VetoConfig>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_veto_config(self.id, id));
+        }
+
+        let config = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT data FROM {}veto_configs WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(config)
+    }
+
+    pub async fn set_config(&self, id: BracketId, config: &VetoConfig) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.set_veto_config(self.id, id, config);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store
+                .replace("veto_configs", &["tournament_id", "bracket_id", "data"], 2),
+            self.id.0 as i64,
+            id.0 as i64,
+            serde_json::to_vec(config)?,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete_config(&self, id: BracketId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_veto_config(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}veto_configs WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the [`MatchVeto`]s of all matches in the bracket with the given `id`, ordered by
+    /// the match index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn list(&self, id: BracketId) -> Result<Vec<MatchVeto>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_vetoes(self.id, id));
+        }
+
+        let vetoes = fetch_all!(
+            self.store,
+            format!(
+                "SELECT data FROM {}vetoes WHERE tournament_id = ? AND bracket_id = ? ORDER BY match_index ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(vetoes)
+    }
+
+    /// Inserts the [`MatchVeto`] for the bracket with the given `id`, overwriting the existing
+    /// veto of the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn insert(&self, id: BracketId, veto: &MatchVeto) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_veto(self.id, id, veto);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store.replace(
+                "vetoes",
+                &["tournament_id", "bracket_id", "match_index", "data"],
+                3
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+            veto.index as i64,
+            serde_json::to_vec(veto)?,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: BracketId, index: usize) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_veto(self.id, id, index);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}vetoes WHERE tournament_id = ? AND bracket_id = ? AND match_index = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+            index as i64,
+        );
 
         Ok(())
     }
+
+    /// Deletes the [`MatchVeto`]s of all matches in the bracket with the given `id`. The
+    /// [`VetoConfig`] of the bracket is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn clear(&self, id: BracketId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.clear_vetoes(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}vetoes WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatchMetadataClient<'a> {
+    store: &'a Store,
+    id: TournamentId,
+}
+
+impl<'a> MatchMetadataClient<'a> {
+    /// Returns the [`MatchMetadata`] of the match with the given `index` in the bracket with the
+    /// given `id`. Returns `None` if the match has no metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn get(&self, id: BracketId, index: usize) -> Result<Option<MatchMetadata>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_match_metadata(self.id, id, index));
+        }
+
+        let metadata = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT data FROM {}match_metadata WHERE tournament_id = ? AND bracket_id = ? AND match_index = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+            index as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(metadata)
+    }
+
+    /// Returns the [`MatchMetadata`] of all matches in the bracket with the given `id`, ordered
+    /// by the match index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn list(&self, id: BracketId) -> Result<Vec<MatchMetadata>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_match_metadata(self.id, id));
+        }
+
+        let metadata = fetch_all!(
+            self.store,
+            format!(
+                "SELECT data FROM {}match_metadata WHERE tournament_id = ? AND bracket_id = ? ORDER BY match_index ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(metadata)
+    }
+
+    /// Inserts the [`MatchMetadata`] for the bracket with the given `id`, overwriting the
+    /// existing metadata of the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn insert(&self, id: BracketId, metadata: &MatchMetadata) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_match_metadata(self.id, id, metadata);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store.replace(
+                "match_metadata",
+                &["tournament_id", "bracket_id", "match_index", "data"],
+                3
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+            metadata.index as i64,
+            serde_json::to_vec(metadata)?,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: BracketId, index: usize) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_match_metadata(self.id, id, index);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}match_metadata WHERE tournament_id = ? AND bracket_id = ? AND match_index = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+            index as i64,
+        );
+
+        Ok(())
+    }
+
+    /// Deletes the [`MatchMetadata`] of all matches in the bracket with the given `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn clear(&self, id: BracketId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.clear_match_metadata(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}match_metadata WHERE tournament_id = ? AND bracket_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct RatingsClient<'a> {
+    store: &'a Store,
+    id: TournamentId,
+}
+
+impl<'a> RatingsClient<'a> {
+    /// Returns the [`RatingSystem`] of the tournament. Returns `None` if rating updates are
+    /// disabled for the tournament.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn system(&self) -> Result<Option<RatingSystem>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_rating_system(self.id));
+        }
+
+        let system = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT data FROM {}rating_systems WHERE tournament_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(system)
+    }
+
+    /// Sets the [`RatingSystem`] of the tournament, overwriting the existing system.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn set_system(&self, system: &RatingSystem) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.set_rating_system(self.id, system);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            self.store
+                .replace("rating_systems", &["tournament_id", "data"], 1),
+            self.id.0 as i64,
+            serde_json::to_vec(system)?,
+        );
+
+        Ok(())
+    }
+
+    pub async fn delete_system(&self) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_rating_system(self.id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}rating_systems WHERE tournament_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+        );
+
+        Ok(())
+    }
+
+    /// Returns all [`RatingChange`]s of the tournament in the order they were inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn history(&self) -> Result<Vec<RatingChange>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_rating_changes(self.id, |_| true));
+        }
+
+        let changes = fetch_all!(
+            self.store,
+            format!(
+                "SELECT data FROM {}rating_changes WHERE tournament_id = ? ORDER BY id ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(changes)
+    }
+
+    /// Returns all [`RatingChange`]s of the entrant with the given `id` in the order they were
+    /// inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn entrant_history(&self, id: EntrantId) -> Result<Vec<RatingChange>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_rating_changes(self.id, |change| change.entrant == id));
+        }
+
+        let changes = fetch_all!(
+            self.store,
+            format!(
+                "SELECT data FROM {}rating_changes WHERE tournament_id = ? AND entrant_id = ? ORDER BY id ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(changes)
+    }
+
+    /// Returns all [`RatingChange`]s caused by the match with the given `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn match_history(
+        &self,
+        bracket_id: BracketId,
+        index: usize,
+    ) -> Result<Vec<RatingChange>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_rating_changes(self.id, |change| {
+                change.bracket == bracket_id && change.index == index
+            }));
+        }
+
+        let changes = fetch_all!(
+            self.store,
+            format!(
+                "SELECT data FROM {}rating_changes WHERE tournament_id = ? AND bracket_id = ? AND match_index = ? ORDER BY id ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            bracket_id.0 as i64,
+            index as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+                serde_json::from_slice(&data)?
+            }
+        );
+
+        Ok(changes)
+    }
+
+    pub async fn insert(&self, change: &RatingChange) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_rating_change(self.id, change);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "INSERT INTO {}rating_changes (tournament_id, entrant_id, bracket_id, match_index, date, data) VALUES (?, ?, ?, ?, ?, ?)",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            change.entrant.0 as i64,
+            change.bracket.0 as i64,
+            change.index as i64,
+            change.date,
+            serde_json::to_vec(change)?,
+        );
+
+        Ok(())
+    }
+
+    /// Removes all [`RatingChange`]s caused by the match with the given `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn delete_match(&self, bracket_id: BracketId, index: usize) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_rating_changes(self.id, |change| {
+                change.bracket == bracket_id && change.index == index
+            });
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}rating_changes WHERE tournament_id = ? AND bracket_id = ? AND match_index = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            bracket_id.0 as i64,
+            index as i64,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WebhooksClient<'a> {
+    store: &'a Store,
+    id: TournamentId,
+}
+
+impl<'a> WebhooksClient<'a> {
+    pub async fn list(&self) -> Result<Vec<Webhook>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_webhooks(self.id));
+        }
+
+        let webhooks = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, data FROM {}webhooks WHERE tournament_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut webhook: Webhook = serde_json::from_slice(&data)?;
+                webhook.id = WebhookId(id);
+                webhook
+            }
+        );
+
+        Ok(webhooks)
+    }
+
+    pub async fn get(&self, id: WebhookId) -> Result<Option<Webhook>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.get_webhook(self.id, id));
+        }
+
+        let webhook = fetch_optional!(
+            self.store,
+            format!(
+                "SELECT data FROM {}webhooks WHERE tournament_id = ? AND id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut webhook: Webhook = serde_json::from_slice(&data)?;
+                webhook.id = id;
+                webhook
+            }
+        );
+
+        Ok(webhook)
+    }
+
+    pub async fn insert(&self, webhook: &Webhook) -> Result<WebhookId, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.insert_webhook(self.id, webhook));
+        }
+
+        let id = insert!(
+            self.store,
+            format!(
+                "INSERT INTO {}webhooks (tournament_id, data) VALUES (?, ?)",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            serde_json::to_vec(webhook)?,
+        );
+
+        Ok(WebhookId(id))
+    }
+
+    /// Deletes the [`Webhook`] with the given `id` together with all its deliveries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn delete(&self, id: WebhookId) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.delete_webhook(self.id, id);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}webhooks WHERE tournament_id = ? AND id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        execute!(
+            self.store,
+            format!(
+                "DELETE FROM {}webhook_deliveries WHERE tournament_id = ? AND webhook_id = ?",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64,
+        );
+
+        Ok(())
+    }
+
+    /// Returns all [`Delivery`] attempts of the webhook with the given `id` in the order they
+    /// were inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`enum@Error`] if an database error occured.
+    pub async fn deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            return Ok(memory.list_deliveries(self.id, id));
+        }
+
+        let deliveries = fetch_all!(
+            self.store,
+            format!(
+                "SELECT id, data FROM {}webhook_deliveries WHERE tournament_id = ? AND webhook_id = ? ORDER BY id ASC",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            id.0 as i64;
+            |row| {
+                let id = row.try_get_u64("id")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                let mut delivery: Delivery = serde_json::from_slice(&data)?;
+                delivery.id = DeliveryId(id);
+                delivery
+            }
+        );
+
+        Ok(deliveries)
+    }
+
+    pub async fn insert_delivery(&self, delivery: &Delivery) -> Result<(), Error> {
+        if let Pool::Memory(memory) = &self.store.pool {
+            memory.insert_delivery(self.id, delivery);
+            return Ok(());
+        }
+
+        execute!(
+            self.store,
+            format!(
+                "INSERT INTO {}webhook_deliveries (tournament_id, webhook_id, date, data) VALUES (?, ?, ?, ?)",
+                self.store.table_prefix
+            ),
+            self.id.0 as i64,
+            delivery.webhook_id.0 as i64,
+            delivery.date,
+            serde_json::to_vec(delivery)?,
+        );
+
+        Ok(())
+    }
+}
+
+/// Replaces every `?` placeholder with a numbered placeholder (`$1`, `$2`, ...) as used by
+/// PostgreSQL. A `?` inside of a single quoted string literal is not a placeholder and is left
+/// as is.
+fn numbered_placeholders(sql: &str) -> String {
+    let mut buf = String::with_capacity(sql.len());
+    let mut index = 0;
+    // An escaped quote (`''`) leaves and immediately reenters the literal.
+    let mut literal = false;
+
+    for c in sql.chars() {
+        if c == '\'' {
+            literal = !literal;
+            buf.push(c);
+        } else if c == '?' && !literal {
+            index += 1;
+            buf.push_str(&format!("${}", index));
+        } else {
+            buf.push(c);
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use dynamic_tournament_api::v3::id::{
+        BracketId, DeliveryId, EntrantId, RoleId, SystemId, UserId, WebhookId,
+    };
+    use dynamic_tournament_api::v3::tournaments::brackets::matches::MatchMetadata;
+    use dynamic_tournament_api::v3::tournaments::brackets::schedule::{Schedule, ScheduleConfig};
+    use dynamic_tournament_api::v3::tournaments::brackets::veto::{
+        MatchVeto, Veto, VetoConfig, VetoStep,
+    };
+    use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
+    use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant, Player};
+    use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+    use dynamic_tournament_api::v3::tournaments::ratings::{RatingChange, RatingSystem};
+    use dynamic_tournament_api::v3::tournaments::webhooks::{Delivery, Webhook, WebhookEvent};
+    use dynamic_tournament_api::v3::tournaments::{EntrantKind, Tournament};
+    use dynamic_tournament_api::v3::users::User;
+    use sqlx::sqlite::SqlitePool;
+
+    use super::migrations::{self, MIGRATIONS};
+    use super::{numbered_placeholders, pool_options, BracketState, Pool, Store};
+    use crate::websocket::live_bracket;
+
+    /// Creates a [`Store`] using an in-memory SQLite database. Every connection opens a separate
+    /// database, so the pool keeps a single connection open.
+    async fn sqlite() -> Store {
+        let pool: SqlitePool = pool_options()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        Store {
+            pool: Pool::Sqlite(pool),
+            table_prefix: String::from("dt_"),
+            entrants_lock: Arc::default(),
+        }
+    }
+
+    fn rating_change(entrant: EntrantId, bracket: BracketId, index: usize) -> RatingChange {
+        RatingChange {
+            date: Utc::now(),
+            entrant,
+            player: 0,
+            bracket,
+            index,
+            rating: 1016.0,
+            delta: 16.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite() {
+        let store = sqlite().await;
+
+        // Stop before the rating history is moved so that migrating the old rows is covered.
+        assert_eq!(store.schema_version().await.unwrap(), 0);
+        for migration in &MIGRATIONS[..2] {
+            store.apply(migration).await.unwrap();
+        }
+        assert_eq!(store.schema_version().await.unwrap(), 2);
+
+        // Tournaments
+        let tournament_id = store
+            .insert_tournament(&Tournament {
+                id: Default::default(),
+                name: String::from("test"),
+                description: String::new(),
+                date: Utc::now(),
+                kind: EntrantKind::Player,
+            })
+            .await
+            .unwrap();
+
+        let change = rating_change(EntrantId(1), BracketId(2), 3);
+        let Pool::Sqlite(pool) = &store.pool else {
+            unreachable!();
+        };
+        sqlx::query("INSERT INTO dt_rating_history (tournament_id, date, data) VALUES (?, ?, ?)")
+            .bind(tournament_id.0 as i64)
+            .bind(change.date)
+            .bind(serde_json::to_vec(&change).unwrap())
+            .execute(pool)
+            .await
+            .unwrap();
+
+        store.migrate().await.unwrap();
+        assert_eq!(store.schema_version().await.unwrap(), migrations::latest());
+
+        // Migrating again does nothing.
+        store.migrate().await.unwrap();
+
+        let tournament = store.get_tournament(tournament_id).await.unwrap().unwrap();
+        assert_eq!(tournament.name, "test");
+        assert_eq!(store.list_tournaments().await.unwrap().len(), 1);
+
+        // Entrants
+        let entrant = Entrant::player(Player {
+            name: String::from("player"),
+            role: RoleId(0),
+            rating: None,
+        });
+        let entrant_id = store
+            .insert_entrant(tournament_id, entrant.clone())
+            .await
+            .unwrap();
+
+        let mut entrant = store
+            .get_entrant(tournament_id, entrant_id)
+            .await
+            .unwrap()
+            .unwrap();
+        if let EntrantVariant::Player(player) = &mut entrant.inner {
+            player.rating = Some(1200);
+        }
+        store
+            .entrants(tournament_id)
+            .update(entrant_id, &entrant)
+            .await
+            .unwrap();
+
+        let entrants = store.get_entrants(tournament_id).await.unwrap();
+        assert_eq!(entrants.len(), 1);
+        assert_eq!(entrants[0].rating(), Some(1200));
+
+        // Brackets and their state
+        let mut bracket = Bracket {
+            id: BracketId(0),
+            name: String::from("bracket"),
+            system: SystemId(1),
+            options: Default::default(),
+            entrants: (1..=4).map(EntrantId).collect(),
+            seeding: Default::default(),
+        };
+        let bracket_id = store.insert_bracket(tournament_id, &bracket).await.unwrap();
+
+        bracket.name = String::from("renamed");
+        store
+            .update_bracket(tournament_id, bracket_id, &bracket)
+            .await
+            .unwrap();
+
+        let stored = store
+            .get_bracket(tournament_id, bracket_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.id, bracket_id);
+        assert_eq!(stored.name, "renamed");
+        assert_eq!(store.list_brackets(tournament_id).await.unwrap().len(), 1);
+
+        let state = store
+            .get_bracket_state(tournament_id, bracket_id)
+            .await
+            .unwrap();
+        assert!(state.is_none());
+
+        let tournament = live_bracket::build(bracket, None).unwrap();
+        store
+            .update_bracket_state(
+                tournament_id,
+                bracket_id,
+                &Some(BracketState::Snapshot(tournament.snapshot())),
+            )
+            .await
+            .unwrap();
+
+        let state = store
+            .get_bracket_state(tournament_id, bracket_id)
+            .await
+            .unwrap();
+        assert!(live_bracket::build(stored, state).is_ok());
+
+        // Event log
+        store
+            .event_log(tournament_id)
+            .push(
+                1,
+                LogEventBody::ResetMatch {
+                    bracket_id,
+                    index: 0,
+                },
+            )
+            .await
+            .unwrap();
+
+        let events = store.event_log(tournament_id).list().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].author, 1);
+
+        // Schedules, stored twice to cover replacing an existing row.
+        let mut schedule = Schedule {
+            config: ScheduleConfig {
+                start: Utc::now(),
+                stations: vec![String::from("station")],
+                match_duration: 60,
+                rest: 0,
+            },
+            matches: Vec::new(),
+        };
+        let schedules = store.schedules(tournament_id);
+        schedules.insert(bracket_id, &schedule).await.unwrap();
+        schedule.config.match_duration = 120;
+        schedules.insert(bracket_id, &schedule).await.unwrap();
+
+        let stored = schedules.get(bracket_id).await.unwrap().unwrap();
+        assert_eq!(stored.config.match_duration, 120);
+
+        schedules.delete(bracket_id).await.unwrap();
+        assert!(schedules.get(bracket_id).await.unwrap().is_none());
+
+        // Vetoes
+        let config = VetoConfig {
+            pool: vec![String::from("a"), String::from("b")],
+            sequence: vec![VetoStep::Ban, VetoStep::Decider],
+        };
+        let vetoes = store.vetoes(tournament_id);
+        vetoes.set_config(bracket_id, &config).await.unwrap();
+        vetoes.set_config(bracket_id, &config).await.unwrap();
+        assert_eq!(
+            vetoes.config(bracket_id).await.unwrap(),
+            Some(config.clone())
+        );
+
+        let veto = MatchVeto {
+            index: 2,
+            veto: Veto::new(config, 0).unwrap(),
+            keys: None,
+        };
+        vetoes.insert(bracket_id, &veto).await.unwrap();
+        vetoes.insert(bracket_id, &veto).await.unwrap();
+        assert_eq!(vetoes.list(bracket_id).await.unwrap().len(), 1);
+
+        vetoes.clear(bracket_id).await.unwrap();
+        assert!(vetoes.list(bracket_id).await.unwrap().is_empty());
+
+        // Match metadata
+        let mut metadata = MatchMetadata {
+            index: 1,
+            ..Default::default()
+        };
+        let client = store.match_metadata(tournament_id);
+        client.insert(bracket_id, &metadata).await.unwrap();
+        metadata.notes = String::from("notes");
+        client.insert(bracket_id, &metadata).await.unwrap();
+
+        assert_eq!(
+            client.get(bracket_id, 1).await.unwrap(),
+            Some(metadata.clone())
+        );
+        assert_eq!(client.list(bracket_id).await.unwrap(), vec![metadata]);
+
+        client.delete(bracket_id, 1).await.unwrap();
+        assert!(client.get(bracket_id, 1).await.unwrap().is_none());
+
+        // Ratings, including the change migrated from the old rating history.
+        let ratings = store.ratings(tournament_id);
+        let migrated = ratings.match_history(BracketId(2), 3).await.unwrap();
+        assert_eq!(migrated.len(), 1);
+        assert_eq!(migrated[0].entrant, EntrantId(1));
+        assert_eq!(
+            ratings.entrant_history(EntrantId(1)).await.unwrap().len(),
+            1
+        );
+
+        let system = RatingSystem::Elo { k: 16.0 };
+        ratings.set_system(&system).await.unwrap();
+        ratings.set_system(&system).await.unwrap();
+        assert_eq!(ratings.system().await.unwrap(), Some(system));
+
+        ratings
+            .insert(&rating_change(entrant_id, bracket_id, 0))
+            .await
+            .unwrap();
+        assert_eq!(ratings.history().await.unwrap().len(), 2);
+
+        ratings.delete_match(bracket_id, 0).await.unwrap();
+        assert!(ratings
+            .match_history(bracket_id, 0)
+            .await
+            .unwrap()
+            .is_empty());
+
+        ratings.delete_system().await.unwrap();
+        assert!(ratings.system().await.unwrap().is_none());
+
+        // Webhooks and deliveries
+        let webhooks = store.webhooks(tournament_id);
+        let webhook_id = webhooks
+            .insert(&Webhook {
+                id: WebhookId(0),
+                url: String::from("http://localhost"),
+                secret: String::from("secret"),
+                events: vec![WebhookEvent::UpdateMatch],
+            })
+            .await
+            .unwrap();
+
+        let webhook = webhooks.get(webhook_id).await.unwrap().unwrap();
+        assert_eq!(webhook.id, webhook_id);
+        assert_eq!(webhook.secret, "secret");
+        assert_eq!(webhooks.list().await.unwrap().len(), 1);
+
+        webhooks
+            .insert_delivery(&Delivery {
+                id: DeliveryId(0),
+                webhook_id,
+                date: Utc::now(),
+                event: WebhookEvent::UpdateMatch,
+                attempt: 1,
+                status: Some(200),
+                error: None,
+            })
+            .await
+            .unwrap();
+
+        let deliveries = webhooks.deliveries(webhook_id).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, Some(200));
+
+        webhooks.delete(webhook_id).await.unwrap();
+        assert!(webhooks.get(webhook_id).await.unwrap().is_none());
+        assert!(webhooks.deliveries(webhook_id).await.unwrap().is_empty());
+
+        // Users and their token revocations
+        let users = store.users();
+        for id in [1, 2] {
+            users
+                .insert(&User {
+                    id: UserId(id),
+                    username: format!("user{}", id),
+                    password: String::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        users.set_tokens_valid_after(UserId(1), 10).await.unwrap();
+        users.set_tokens_valid_after(UserId(1), 20).await.unwrap();
+
+        let mut tokens = users.list_tokens_valid_after().await.unwrap();
+        tokens.sort();
+        assert_eq!(tokens, vec![(UserId(1), 20), (UserId(2), 0)]);
+
+        users.delete(UserId(1)).await.unwrap();
+        assert_eq!(
+            users.list_tokens_valid_after().await.unwrap(),
+            vec![(UserId(2), 0)]
+        );
+
+        // Deleting the tournament removes all of its rows.
+        store.tournaments().delete(tournament_id).await.unwrap();
+        assert!(store.get_tournament(tournament_id).await.unwrap().is_none());
+        assert!(store.get_entrants(tournament_id).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_numbered_placeholders() {
+        assert_eq!(
+            numbered_placeholders("SELECT a FROM b WHERE c = ? AND d = ?"),
+            "SELECT a FROM b WHERE c = $1 AND d = $2"
+        );
+
+        assert_eq!(
+            numbered_placeholders("SELECT a FROM b WHERE c = '?' AND d = ?"),
+            "SELECT a FROM b WHERE c = '?' AND d = $1"
+        );
+
+        assert_eq!(
+            numbered_placeholders("SELECT a FROM b WHERE c = 'it''s?' AND d = ?"),
+            "SELECT a FROM b WHERE c = 'it''s?' AND d = $1"
+        );
+    }
 }
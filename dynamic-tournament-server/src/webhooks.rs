@@ -0,0 +1,292 @@
+//! Outgoing webhooks.
+//!
+//! Events of a tournament are posted as a [`WebhookPayload`] to all webhooks of the tournament
+//! that subscribed to the event. Every payload is signed using the secret of the webhook. The
+//! hex encoded HMAC-SHA256 signature of the request body is sent in the `X-Signature-256`
+//! header in the form `sha256=<signature>`.
+//!
+//! A delivery fails if the webhook does not respond with a `2xx` status code. Failed deliveries
+//! are retried with an exponential backoff. Every attempt is written to the delivery log of the
+//! webhook.
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use dynamic_tournament_api::v3::id::{DeliveryId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::log::LogEventBody;
+use dynamic_tournament_api::v3::tournaments::webhooks::{
+    Delivery, Webhook, WebhookEvent, WebhookPayload,
+};
+use hyper::body::Bytes;
+use hyper::header::{HeaderValue, CONTENT_TYPE, HOST, USER_AGENT};
+use hyper::{Body, StatusCode, Uri};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::time;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+
+use crate::store::Store;
+
+/// The maximum number of attempts to deliver a single event.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// The delay before the first retry. The delay is doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+
+/// The maximum duration of a single attempt.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct Webhooks {
+    store: Store,
+    tls: Arc<ClientConfig>,
+}
+
+impl Webhooks {
+    pub fn new(store: Store) -> Self {
+        let mut roots = RootCertStore::empty();
+        roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+
+        let tls = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        Self {
+            store,
+            tls: Arc::new(tls),
+        }
+    }
+
+    /// Sends the event with the given `body` to all webhooks of the tournament that subscribed
+    /// to it. The event is delivered in the background.
+    pub fn dispatch(&self, tournament_id: TournamentId, author: u64, body: &LogEventBody) {
+        let Some(event) = WebhookEvent::from_body(body) else {
+            return;
+        };
+
+        let payload = WebhookPayload {
+            tournament_id,
+            date: Utc::now(),
+            author,
+            body: body.clone(),
+        };
+        let payload = Bytes::from(serde_json::to_vec(&payload).unwrap());
+
+        let webhooks = self.clone();
+        tokio::task::spawn(async move {
+            let list = match webhooks.store.webhooks(tournament_id).list().await {
+                Ok(list) => list,
+                Err(err) => {
+                    log::error!("Failed to load webhooks: {}", err);
+                    return;
+                }
+            };
+
+            for webhook in list {
+                if webhook.events.contains(&event) {
+                    tokio::task::spawn(webhooks.clone().deliver(
+                        tournament_id,
+                        webhook,
+                        event,
+                        payload.clone(),
+                    ));
+                }
+            }
+        });
+    }
+
+    async fn deliver(
+        self,
+        tournament_id: TournamentId,
+        webhook: Webhook,
+        event: WebhookEvent,
+        payload: Bytes,
+    ) {
+        let signature = sign(webhook.secret.as_bytes(), &payload);
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let res = time::timeout(
+                TIMEOUT,
+                self.post(&webhook.url, &signature, payload.clone()),
+            )
+            .await;
+
+            let (status, error) = match res {
+                Ok(Ok(status)) => (Some(status), None),
+                Ok(Err(err)) => (None, Some(err.to_string())),
+                Err(_) => (None, Some(String::from("timed out"))),
+            };
+
+            let delivery = Delivery {
+                id: DeliveryId(0),
+                webhook_id: webhook.id,
+                date: Utc::now(),
+                event,
+                attempt,
+                status: status.map(|status| status.as_u16()),
+                error,
+            };
+
+            if let Err(err) = self
+                .store
+                .webhooks(tournament_id)
+                .insert_delivery(&delivery)
+                .await
+            {
+                log::error!("Failed to log webhook delivery: {}", err);
+            }
+
+            if status.map(|status| status.is_success()).unwrap_or(false) {
+                return;
+            }
+
+            if attempt < MAX_ATTEMPTS {
+                time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        log::warn!(
+            "Failed to deliver event to webhook {} after {} attempts",
+            webhook.id,
+            MAX_ATTEMPTS
+        );
+    }
+
+    /// Posts the `payload` to the `url` and returns the status code of the response.
+    async fn post(
+        &self,
+        url: &str,
+        signature: &str,
+        payload: Bytes,
+    ) -> Result<StatusCode, DeliveryError> {
+        let uri: Uri = url.parse().map_err(|_| DeliveryError::InvalidUrl)?;
+
+        let https = match uri.scheme_str() {
+            Some("http") => false,
+            Some("https") => true,
+            _ => return Err(DeliveryError::InvalidUrl),
+        };
+
+        let (Some(host), Some(authority)) = (uri.host(), uri.authority()) else {
+            return Err(DeliveryError::InvalidUrl);
+        };
+        let port = uri.port_u16().unwrap_or(if https { 443 } else { 80 });
+
+        let path = uri
+            .path_and_query()
+            .map(|path| path.as_str())
+            .unwrap_or("/");
+
+        let req = hyper::Request::post(path)
+            .header(HOST, authority.as_str())
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(
+                USER_AGENT,
+                HeaderValue::from_static(concat!(
+                    "dynamic-tournament-server/",
+                    env!("CARGO_PKG_VERSION")
+                )),
+            )
+            .header("X-Signature-256", format!("sha256={}", signature))
+            .body(Body::from(payload))
+            .map_err(|_| DeliveryError::InvalidUrl)?;
+
+        let stream = TcpStream::connect((host, port)).await?;
+
+        if https {
+            let name = ServerName::try_from(host).map_err(|_| DeliveryError::InvalidUrl)?;
+            let stream = TlsConnector::from(self.tls.clone())
+                .connect(name, stream)
+                .await?;
+
+            send(stream, req).await
+        } else {
+            send(stream, req).await
+        }
+    }
+}
+
+/// Sends the request over a new HTTP/1 connection on `stream`.
+async fn send<S>(stream: S, req: hyper::Request<Body>) -> Result<StatusCode, DeliveryError>
+where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let (mut sender, conn) = hyper::client::conn::handshake(stream).await?;
+
+    tokio::task::spawn(async move {
+        if let Err(err) = conn.await {
+            log::debug!("Webhook connection failed: {}", err);
+        }
+    });
+
+    let resp = sender.send_request(req).await?;
+    Ok(resp.status())
+}
+
+/// Returns the hex encoded HMAC-SHA256 of `payload` using `key`.
+fn sign(key: &[u8], payload: &[u8]) -> String {
+    const BLOCK_SIZE: usize = 64;
+
+    // Keys longer than the block size are hashed first.
+    let mut block = [0; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let hash = Sha256::digest(key);
+        block[..hash.len()].copy_from_slice(&hash);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(payload);
+
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+
+    hex::encode(outer.finalize())
+}
+
+#[derive(Debug, Error)]
+enum DeliveryError {
+    #[error("invalid url")]
+    InvalidUrl,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Hyper(#[from] hyper::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign;
+
+    #[test]
+    fn test_sign() {
+        // Test cases 2 and 6 from RFC 4231.
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        assert_eq!(
+            sign(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
@@ -1,23 +1,38 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::mem;
 use std::pin::Pin;
 use std::sync::{Arc, Weak};
 use std::task::{Context, Poll};
 
-use chrono::Utc;
+use chrono::{DateTime, TimeZone, Utc};
 use dynamic_tournament_api::v3::id::{BracketId, EntrantId, EventId, SystemId, TournamentId};
-use dynamic_tournament_api::v3::tournaments::brackets::matches::Response;
+use dynamic_tournament_api::v3::tournaments::brackets::matches::{
+    ErrorResponse, MatchMetadata, MatchState, Response,
+};
+use dynamic_tournament_api::v3::tournaments::brackets::schedule::{
+    Schedule, ScheduleStatus, ScheduledMatch,
+};
+use dynamic_tournament_api::v3::tournaments::brackets::veto::MatchVeto;
+use dynamic_tournament_api::v3::tournaments::brackets::{
+    Bracket, MatchEstimate, Prediction, Progress, Scenarios,
+};
 use dynamic_tournament_api::v3::tournaments::log::{LogEvent, LogEventBody};
+use dynamic_tournament_core::progress::Estimator;
+use dynamic_tournament_core::schedule::{Scheduler, Slot};
+use dynamic_tournament_core::snapshot::Snapshot;
 use dynamic_tournament_core::{
     tournament::{Tournament, TournamentKind},
     EntrantScore, EntrantSpot, Matches, System,
 };
 use futures::{ready, Stream};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
 
-use crate::{store::Store, Error};
+use crate::store::{BracketState, Store};
+use crate::webhooks::Webhooks;
+use crate::Error;
 
 #[derive(Clone, Debug)]
 pub struct LiveBracket {
@@ -27,36 +42,63 @@ pub struct LiveBracket {
 }
 
 impl LiveBracket {
-    pub fn set_user_id(&mut self, id: u64) {
-        self.user_id = Some(id);
+    pub fn set_user_id(&mut self, id: Option<u64>) {
+        self.user_id = id;
     }
 
-    pub fn update(&self, index: u64, nodes: [EntrantScore<u64>; 2]) {
+    /// Updates the match with the given `index` using the `nodes`. The update is only applied if
+    /// the match is still at the given `revision`. Returns the new revision of the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorResponse::Conflict`] with the current state of the match if the match was
+    /// changed since `revision`.
+    pub fn update(
+        &self,
+        index: u64,
+        revision: u64,
+        nodes: [EntrantScore<u64>; 2],
+    ) -> Result<u64, ErrorResponse> {
         let mut bracket = self.inner.bracket.write();
+        let mut revisions = self.inner.revisions.write();
 
-        bracket.update_match(index.try_into().unwrap(), |m, res| {
-            let mut loser_index = None;
+        let Some(current) = revisions.get_mut(index as usize) else {
+            return Err(ErrorResponse::Proto);
+        };
 
-            for (i, (entrant, node)) in m.entrants.iter_mut().zip(nodes).enumerate() {
-                if let EntrantSpot::Entrant(entrant) = entrant {
-                    entrant.data = node;
-                }
+        if *current != revision {
+            return Err(ErrorResponse::Conflict(match_state(
+                &bracket,
+                index as usize,
+                *current,
+            )));
+        }
 
-                if node.winner {
-                    res.winner_default(entrant);
-                    loser_index = Some(match i {
-                        0 => 1,
-                        _ => 0,
-                    });
-                }
-            }
+        *current += 1;
+        let revision = *current;
 
-            if let Some(loser_index) = loser_index {
-                res.loser_default(&m.entrants[loser_index]);
-            }
-        });
+        let before = match_result(&bracket, index as usize);
+        let matches = bracket.matches().clone();
 
-        self.notify(BracketChange::UpdateMatch { index, nodes });
+        update_match(&mut bracket, index as usize, nodes);
+        bump_changed(&bracket, &matches, index as usize, &mut revisions);
+        drop(revisions);
+        self.invalidate();
+
+        // The last update of a match is the time it was concluded.
+        self.inner
+            .finished
+            .write()
+            .insert(index as usize, timestamp(Utc::now()));
+
+        // Ratings only change if the winner or the entrants changed.
+        let rerate = match_result(&bracket, index as usize) != before;
+
+        self.notify(BracketChange::UpdateMatch {
+            index,
+            revision,
+            nodes,
+        });
 
         let log_event = LogEvent {
             id: EventId(0),
@@ -78,17 +120,45 @@ impl LiveBracket {
             if let Err(err) = bracket.store().await {
                 log::error!("Failed to save bracket state: {}", err);
             }
+
+            if let Err(err) = bracket.reschedule(index as usize).await {
+                log::error!("Failed to update bracket schedule: {}", err);
+            }
+
+            if rerate {
+                if let Err(err) = bracket.rate(index as usize).await {
+                    log::error!("Failed to update ratings: {}", err);
+                }
+            }
         });
+
+        Ok(revision)
     }
 
     pub fn reset(&self, index: usize) {
         let mut bracket = self.inner.bracket.write();
+        let mut revisions = self.inner.revisions.write();
+
+        let Some(revision) = revisions.get_mut(index) else {
+            return;
+        };
+        *revision += 1;
+        let revision = *revision;
+
+        let before = match_result(&bracket, index);
+        let matches = bracket.matches().clone();
 
         bracket.update_match(index, |_, res| {
             res.reset_default();
         });
+        bump_changed(&bracket, &matches, index, &mut revisions);
+        drop(revisions);
+        self.invalidate();
+        self.inner.finished.write().remove(&index);
 
-        self.notify(BracketChange::ResetMatch { index });
+        let rerate = match_result(&bracket, index) != before;
+
+        self.notify(BracketChange::ResetMatch { index, revision });
 
         let log_event = LogEvent {
             id: EventId(0),
@@ -109,6 +179,16 @@ impl LiveBracket {
             if let Err(err) = bracket.store().await {
                 log::error!("Failed to save bracket state: {}", err);
             }
+
+            if let Err(err) = bracket.reschedule(index).await {
+                log::error!("Failed to update bracket schedule: {}", err);
+            }
+
+            if rerate {
+                if let Err(err) = bracket.rate(index).await {
+                    log::error!("Failed to update ratings: {}", err);
+                }
+            }
         });
     }
 
@@ -118,6 +198,11 @@ impl LiveBracket {
             self.inner.tx.receiver_count()
         );
 
+        // The history is locked while sending so that changes are always received in the
+        // order of their ids.
+        let mut history = self.inner.history.lock();
+        let event = history.push(event);
+
         // Note that this operation can never fail. We keep a receiver ourselves.
         let _ = self.inner.tx.send(event);
     }
@@ -127,7 +212,325 @@ impl LiveBracket {
         bracket.into_matches()
     }
 
-    pub fn receiver(&self) -> broadcast::Receiver<BracketChange> {
+    /// Returns the current state of all matches together with their revisions.
+    pub fn state(&self) -> (Matches<EntrantScore<u64>>, Vec<u64>) {
+        let bracket = self.inner.bracket.read();
+        let revisions = self.inner.revisions.read().clone();
+
+        (bracket.clone().into_matches(), revisions)
+    }
+
+    /// Returns the current state of all matches together with their revisions and the id of the
+    /// last change that is included in the state.
+    pub fn snapshot(&self) -> (u64, Matches<EntrantScore<u64>>, Vec<u64>) {
+        let bracket = self.inner.bracket.read();
+        let revisions = self.inner.revisions.read().clone();
+        let id = self.inner.history.lock().last_id();
+
+        (id, bracket.clone().into_matches(), revisions)
+    }
+
+    /// Returns all changes after the change with the given `id`. Returns `None` if the changes
+    /// are no longer available.
+    pub fn changes_since(&self, id: u64) -> Option<Vec<ChangeEvent>> {
+        self.inner.history.lock().since(id)
+    }
+
+    /// Returns the [`MatchState`] of the match with the given `index`.
+    pub fn match_state(&self, index: usize) -> Option<MatchState> {
+        let bracket = self.inner.bracket.read();
+        let revision = *self.inner.revisions.read().get(index)?;
+
+        Some(match_state(&bracket, index, revision))
+    }
+
+    /// Returns a copy of the current state of the bracket.
+    pub fn tournament(&self) -> Tournament<EntrantId, EntrantScore<u64>> {
+        self.inner.bracket.read().clone()
+    }
+
+    /// Returns a copy of the current state of the bracket together with its generation. The
+    /// generation changes whenever the state of the bracket changes.
+    pub fn tournament_with_generation(&self) -> (u64, Tournament<EntrantId, EntrantScore<u64>>) {
+        let bracket = self.inner.bracket.read();
+        let generation = self.inner.analysis.lock().generation;
+
+        (generation, bracket.clone())
+    }
+
+    /// Returns the cached [`Prediction`] of the current state of the bracket if it was computed
+    /// using the same `ratings`.
+    pub fn cached_prediction(&self, ratings: &[f64]) -> Option<Prediction> {
+        match &self.inner.analysis.lock().prediction {
+            Some((cached, prediction)) if cached == ratings => Some(prediction.clone()),
+            _ => None,
+        }
+    }
+
+    /// Caches a [`Prediction`] that was computed using `ratings` on the state with the given
+    /// `generation`. Does nothing if the state changed in the meantime.
+    pub fn cache_prediction(&self, generation: u64, ratings: Vec<f64>, prediction: Prediction) {
+        let mut analysis = self.inner.analysis.lock();
+
+        if analysis.generation == generation {
+            analysis.prediction = Some((ratings, prediction));
+        }
+    }
+
+    /// Returns the cached [`Scenarios`] of the current state of the bracket for the given `top`.
+    pub fn cached_scenarios(&self, top: usize) -> Option<Scenarios> {
+        self.inner.analysis.lock().scenarios.get(&top).cloned()
+    }
+
+    /// Caches [`Scenarios`] that were computed on the state with the given `generation`. Does
+    /// nothing if the state changed in the meantime.
+    pub fn cache_scenarios(&self, generation: u64, scenarios: Scenarios) {
+        let mut analysis = self.inner.analysis.lock();
+
+        if analysis.generation == generation {
+            analysis.scenarios.insert(scenarios.top, scenarios);
+        }
+    }
+
+    /// Discards all cached results. Must be called while holding the write lock of the bracket.
+    fn invalidate(&self) {
+        let mut analysis = self.inner.analysis.lock();
+        analysis.generation += 1;
+        analysis.prediction = None;
+        analysis.scenarios.clear();
+    }
+
+    /// Recreates all matches from `bracket` after its entrants or options were edited and stores
+    /// `bracket` together with the new state. Returns `false` without changing anything if any
+    /// match already has a result, since the result would be lost.
+    ///
+    /// Once both are stored the schedule is re-planned and the vetoes and metadata of all matches
+    /// are removed. All listeners are notified with [`BracketChange::Rebuild`].
+    pub async fn rebuild(&self, bracket: Bracket) -> Result<bool, Error> {
+        let tournament = build(bracket.clone(), None)?;
+
+        // Hold the lock until the vetoes are removed from the store so that no pending save
+        // writes a veto of the old matches afterwards.
+        let veto_guard = self.inner.veto_save.lock().await;
+        // No pending save may write the new state before the bracket is stored.
+        let state_guard = self.inner.state_save.lock().await;
+
+        let previous = {
+            let mut current = self.inner.bracket.write();
+
+            // Checked while holding the lock so that no result is added before the matches are
+            // replaced.
+            if has_results(&current) {
+                return Ok(false);
+            }
+
+            self.swap(&mut current, tournament, HashMap::new())
+        };
+
+        let state = Some(self.stored_state());
+
+        let tournament_id = self.inner.tournament_id;
+        let bracket_id = self.inner.bracket_id;
+
+        if let Err(err) = self
+            .inner
+            .store
+            .update_bracket_with_state(tournament_id, bracket_id, &bracket, &state)
+            .await
+        {
+            // Go back to the matches of the stored bracket.
+            let (tournament, finished) = previous;
+            self.swap(&mut self.inner.bracket.write(), tournament, finished);
+            self.notify(BracketChange::Rebuild);
+            return Err(err);
+        }
+        drop(state_guard);
+
+        self.inner.vetoes.write().clear();
+
+        self.inner
+            .store
+            .vetoes(tournament_id)
+            .clear(bracket_id)
+            .await?;
+        drop(veto_guard);
+
+        self.inner
+            .store
+            .match_metadata(tournament_id)
+            .clear(bracket_id)
+            .await?;
+
+        {
+            let _guard = self.lock_schedule().await;
+            let schedules = self.inner.store.schedules(tournament_id);
+
+            if let Some(mut schedule) = schedules.get(bracket_id).await? {
+                schedule.matches.clear();
+                self.plan(&mut schedule);
+                schedules.insert(bracket_id, &schedule).await?;
+            }
+        }
+
+        self.notify(BracketChange::Rebuild);
+        Ok(true)
+    }
+
+    /// Replaces the state in `bracket`, which must be the locked state of the bracket, with
+    /// `tournament` and the times at which its matches were concluded with `finished`. Returns
+    /// the previous state and times. The revisions of all matches are increased so that updates
+    /// of the previous matches are rejected.
+    #[allow(clippy::type_complexity)]
+    fn swap(
+        &self,
+        bracket: &mut Tournament<EntrantId, EntrantScore<u64>>,
+        tournament: Tournament<EntrantId, EntrantScore<u64>>,
+        finished: HashMap<usize, u64>,
+    ) -> (
+        Tournament<EntrantId, EntrantScore<u64>>,
+        HashMap<usize, u64>,
+    ) {
+        let mut revisions = self.inner.revisions.write();
+        revisions.resize(tournament.matches().len(), 0);
+        for revision in revisions.iter_mut() {
+            *revision += 1;
+        }
+
+        let finished = mem::replace(&mut *self.inner.finished.write(), finished);
+
+        self.invalidate();
+        (mem::replace(bracket, tournament), finished)
+    }
+
+    /// Replaces the state of the bracket with `tournament`, e.g. after a rollback. `tournament`
+    /// must be a state of the same matches.
+    ///
+    /// Every match that changed is handled like an update or reset of the match: its revision is
+    /// increased, listeners and webhooks are notified and the ratings and the schedule are
+    /// updated. Matches can also change without being updated directly, so all listeners are
+    /// notified with [`BracketChange::Rebuild`] afterwards.
+    pub async fn replace(
+        &self,
+        tournament: Tournament<EntrantId, EntrantScore<u64>>,
+    ) -> Result<(), Error> {
+        let state_guard = self.inner.state_save.lock().await;
+
+        // The indices of all changed matches and whether their result changed.
+        let mut changed = Vec::new();
+
+        let (previous, previous_finished) = {
+            let mut bracket = self.inner.bracket.write();
+            let mut revisions = self.inner.revisions.write();
+            let mut finished = self.inner.finished.write();
+
+            let previous_finished = finished.clone();
+            let now = timestamp(Utc::now());
+
+            for (index, (old, new)) in bracket
+                .matches()
+                .iter()
+                .zip(tournament.matches().iter())
+                .enumerate()
+            {
+                if old == new {
+                    continue;
+                }
+
+                if let Some(revision) = revisions.get_mut(index) {
+                    *revision += 1;
+                }
+
+                if new.is_concluded() {
+                    finished.insert(index, now);
+                } else {
+                    finished.remove(&index);
+                }
+
+                let rerate = match_result(&bracket, index) != match_result(&tournament, index);
+                changed.push((index, rerate));
+            }
+
+            self.invalidate();
+            (mem::replace(&mut *bracket, tournament), previous_finished)
+        };
+
+        let tournament_id = self.inner.tournament_id;
+        let bracket_id = self.inner.bracket_id;
+
+        let state = Some(self.stored_state());
+        if let Err(err) = self
+            .inner
+            .store
+            .update_bracket_state(tournament_id, bracket_id, &state)
+            .await
+        {
+            // Go back to the stored state. The revisions stay increased, which only rejects
+            // updates based on the state that was never stored.
+            let mut bracket = self.inner.bracket.write();
+            *bracket = previous;
+            *self.inner.finished.write() = previous_finished;
+            self.invalidate();
+            return Err(err);
+        }
+        drop(state_guard);
+
+        {
+            let bracket = self.inner.bracket.read();
+            let revisions = self.inner.revisions.read();
+            let author = self.user_id.unwrap_or(0);
+
+            for (index, _) in &changed {
+                let state = match_state(&bracket, *index, revisions[*index]);
+
+                let (change, body) = if state.nodes == [EntrantScore::default(); 2] {
+                    (
+                        BracketChange::ResetMatch {
+                            index: *index,
+                            revision: state.revision,
+                        },
+                        LogEventBody::ResetMatch {
+                            bracket_id,
+                            index: state.index,
+                        },
+                    )
+                } else {
+                    (
+                        BracketChange::UpdateMatch {
+                            index: state.index,
+                            revision: state.revision,
+                            nodes: state.nodes,
+                        },
+                        LogEventBody::UpdateMatch {
+                            bracket_id,
+                            index: state.index,
+                            nodes: state.nodes,
+                        },
+                    )
+                };
+
+                self.notify(change);
+                self.inner.webhooks.dispatch(tournament_id, author, &body);
+            }
+        }
+
+        self.notify(BracketChange::Rebuild);
+
+        for (index, rerate) in changed {
+            if rerate {
+                if let Err(err) = self.rate(index).await {
+                    log::error!("Failed to update ratings: {}", err);
+                }
+            }
+
+            if let Err(err) = self.reschedule(index).await {
+                log::error!("Failed to update bracket schedule: {}", err);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn receiver(&self) -> broadcast::Receiver<ChangeEvent> {
         self.inner.tx.subscribe()
     }
 
@@ -142,35 +545,667 @@ impl LiveBracket {
     }
 
     pub async fn store(&self) -> Result<(), Error> {
-        let matches = self.inner.bracket.read().clone().into_matches();
+        // Saves are serialized and always write the current state. A save that runs late can
+        // therefore never overwrite a newer state with an older one.
+        let _guard = self.inner.state_save.lock().await;
+        let state = self.stored_state();
 
         self.inner
             .store
             .update_bracket_state(
                 self.inner.tournament_id,
                 self.inner.bracket_id,
-                &Some(matches),
+                &Some(state),
             )
             .await?;
         Ok(())
     }
 
+    /// Returns the [`BracketState`] written to the store. It contains the revisions of all
+    /// matches, so that they never decrease after the bracket is loaded again.
+    fn stored_state(&self) -> BracketState {
+        let bracket = self.inner.bracket.read();
+        let revisions = self.inner.revisions.read().clone();
+
+        let mut finished: Vec<(usize, u64)> = self
+            .inner
+            .finished
+            .read()
+            .iter()
+            .map(|(index, time)| (*index, *time))
+            .collect();
+        finished.sort_unstable();
+
+        BracketState::Live {
+            snapshot: bracket.snapshot(),
+            revisions,
+            finished,
+        }
+    }
+
     pub async fn log(&self, event: LogEvent) -> Result<(), Error> {
+        self.inner
+            .webhooks
+            .dispatch(self.inner.tournament_id, event.author, &event.body);
+
         self.inner
             .store
             .event_log(self.inner.tournament_id)
             .insert(&event)
             .await
     }
+
+    /// Plans all matches in the [`Schedule`] using the current state of the bracket.
+    ///
+    /// Matches that are finished, manually overridden or currently in progress are not moved.
+    /// No other match is planned before the current time.
+    pub fn plan(&self, schedule: &mut Schedule) {
+        let now = Utc::now();
+        let bracket = self.inner.bracket.read();
+
+        let config = &schedule.config;
+        let mut scheduler = Scheduler::new(
+            timestamp(config.start.max(now)),
+            config.stations.len(),
+            config.match_duration,
+        )
+        .rest(config.rest);
+
+        for (index, slot) in schedule.matches.iter_mut().enumerate() {
+            let Some(slot) = slot else {
+                continue;
+            };
+
+            let Some(match_) = bracket.matches().get(index) else {
+                continue;
+            };
+
+            // The result of a finished match was reset.
+            if slot.status == ScheduleStatus::Finished && !match_.is_concluded() {
+                slot.status = ScheduleStatus::Planned;
+            }
+
+            let in_progress = slot.status == ScheduleStatus::Planned
+                && slot.start <= now
+                && match_.entrants.iter().all(EntrantSpot::is_entrant);
+
+            if slot.status != ScheduleStatus::Planned || in_progress {
+                scheduler = scheduler.fix(
+                    index,
+                    Slot {
+                        start: timestamp(slot.start),
+                        end: timestamp(slot.end),
+                        station: slot.station,
+                    },
+                );
+            }
+        }
+
+        let slots = scheduler.schedule(&*bracket);
+
+        schedule.matches = (0..slots.len())
+            .map(|index| {
+                let status = match schedule.matches.get(index) {
+                    Some(Some(slot)) => slot.status,
+                    _ => ScheduleStatus::Planned,
+                };
+
+                slots.get(index).map(|slot| ScheduledMatch {
+                    start: datetime(slot.start),
+                    end: datetime(slot.end),
+                    station: slot.station,
+                    status,
+                })
+            })
+            .collect();
+    }
+
+    /// Estimates the progress of the bracket. Every match is expected to take `match_duration`
+    /// seconds until durations could be observed.
+    pub fn progress(&self, match_duration: u64) -> Progress {
+        let bracket = self.inner.bracket.read();
+
+        let mut estimator = Estimator::new(timestamp(Utc::now()), match_duration);
+        for (index, time) in self.inner.finished.read().iter() {
+            estimator = estimator.finished(*index, *time);
+        }
+
+        let progress = estimator.estimate(&*bracket);
+
+        Progress {
+            completed: progress.completed(),
+            total: progress.total(),
+            match_duration: progress.match_duration(),
+            end: progress.end().map(datetime),
+            critical_path: progress.critical_path().to_vec(),
+            matches: progress
+                .estimates()
+                .map(|estimate| {
+                    estimate.map(|estimate| MatchEstimate {
+                        start: datetime(estimate.start),
+                        end: datetime(estimate.end),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Updates the stored [`Schedule`] after the match with the given `index` changed. If the
+    /// match was concluded it is marked as finished at the current time. All remaining matches
+    /// are re-planned.
+    ///
+    /// Does nothing if the bracket has no schedule.
+    pub async fn reschedule(&self, index: usize) -> Result<(), Error> {
+        let _guard = self.lock_schedule().await;
+        let schedules = self.inner.store.schedules(self.inner.tournament_id);
+
+        let Some(mut schedule) = schedules.get(self.inner.bracket_id).await? else {
+            return Ok(());
+        };
+
+        let is_concluded = is_concluded(&self.inner.bracket.read(), index);
+
+        if let Some(Some(slot)) = schedule.matches.get_mut(index) {
+            if is_concluded && slot.status != ScheduleStatus::Finished {
+                let now = Utc::now();
+
+                slot.start = slot.start.min(now);
+                slot.end = now;
+                slot.status = ScheduleStatus::Finished;
+            }
+        }
+
+        self.plan(&mut schedule);
+
+        schedules.insert(self.inner.bracket_id, &schedule).await
+    }
+
+    /// Locks the [`Schedule`] of the bracket. All changes to the stored schedule must be made
+    /// while holding the returned guard.
+    pub async fn lock_schedule(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.inner.schedule_save.lock().await
+    }
+
+    /// Returns the [`MatchVeto`]s of all matches, ordered by the match index.
+    pub fn vetoes(&self) -> Vec<MatchVeto> {
+        let mut vetoes: Vec<MatchVeto> = self.inner.vetoes.read().values().cloned().collect();
+        vetoes.sort_by_key(|veto| veto.index);
+        vetoes
+    }
+
+    /// Returns the [`MatchVeto`] of the match with the given `index`.
+    pub fn veto(&self, index: usize) -> Option<MatchVeto> {
+        self.inner.vetoes.read().get(&index).cloned()
+    }
+
+    /// Replaces the [`MatchVeto`] of the match with the given `index`. A `None` value removes the
+    /// veto.
+    pub async fn set_veto(&self, index: usize, veto: Option<MatchVeto>) -> Result<(), Error> {
+        let _guard = self.inner.veto_save.lock().await;
+        let vetoes = self.inner.store.vetoes(self.inner.tournament_id);
+
+        match veto {
+            Some(veto) => {
+                vetoes.insert(self.inner.bracket_id, &veto).await?;
+                self.inner.vetoes.write().insert(index, veto);
+            }
+            None => {
+                vetoes.delete(self.inner.bracket_id, index).await?;
+                self.inner.vetoes.write().remove(&index);
+            }
+        }
+
+        self.notify(BracketChange::Veto { index });
+        Ok(())
+    }
+
+    /// Plays the next step of the veto of the match with the given `index` using the map at index
+    /// `map`. The step is played by the entrant with the given `key`, which must be the entrant
+    /// whose turn it is. If `key` is `None` the step is played for the entrant whose turn it is,
+    /// which requires the caller to be authorized.
+    pub fn play_veto(
+        &self,
+        index: usize,
+        map: usize,
+        key: Option<&str>,
+    ) -> Result<(), ErrorResponse> {
+        let mut vetoes = self.inner.vetoes.write();
+        let veto = vetoes.get_mut(&index).ok_or(ErrorResponse::InvalidVeto)?;
+
+        let (_, expected) = veto.veto.next().ok_or(ErrorResponse::InvalidVeto)?;
+
+        let team = match (key, &veto.keys) {
+            (None, _) => expected,
+            (Some(key), Some(keys)) => keys
+                .iter()
+                .position(|k| !k.is_empty() && k == key)
+                .ok_or(ErrorResponse::Unauthorized)?,
+            (Some(_), None) => return Err(ErrorResponse::Unauthorized),
+        };
+
+        if team != expected {
+            return Err(ErrorResponse::Unauthorized);
+        }
+
+        if let Err(err) = veto.veto.apply(team, map) {
+            log::debug!("Failed to play veto step: {}", err);
+            return Err(ErrorResponse::InvalidVeto);
+        }
+
+        let finished = veto.veto.is_finished();
+        let maps: Vec<String> = veto.veto.maps().into_iter().map(String::from).collect();
+        drop(vetoes);
+
+        self.notify(BracketChange::Veto { index });
+
+        let log_event = if finished {
+            Some(LogEvent {
+                id: EventId(0),
+                date: Utc::now(),
+                author: self.user_id.unwrap_or(0),
+                body: LogEventBody::Veto {
+                    bracket_id: self.inner.bracket_id,
+                    index: index as u64,
+                    maps,
+                },
+            })
+        } else {
+            None
+        };
+
+        let bracket = self.clone();
+        tokio::task::spawn(async move {
+            // Saves are serialized and always write the current state. A task that runs late
+            // can therefore never overwrite a newer state with an older one.
+            let guard = bracket.inner.veto_save.lock().await;
+            if let Some(veto) = bracket.veto(index) {
+                if let Err(err) = bracket
+                    .inner
+                    .store
+                    .vetoes(bracket.inner.tournament_id)
+                    .insert(bracket.inner.bracket_id, &veto)
+                    .await
+                {
+                    log::error!("Failed to save veto: {}", err);
+                }
+            }
+            drop(guard);
+
+            if let Some(log_event) = log_event {
+                if let Err(err) = bracket.log(log_event).await {
+                    log::error!("Failed to log event: {}", err);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Replaces the [`MatchMetadata`] of the match with the given `index`. A `None` value removes
+    /// the metadata.
+    pub async fn set_metadata(
+        &self,
+        index: usize,
+        metadata: Option<MatchMetadata>,
+    ) -> Result<(), Error> {
+        let client = self.inner.store.match_metadata(self.inner.tournament_id);
+
+        let metadata = match metadata {
+            Some(metadata) => {
+                client.insert(self.inner.bracket_id, &metadata).await?;
+                metadata
+            }
+            None => {
+                client.delete(self.inner.bracket_id, index).await?;
+                MatchMetadata {
+                    index,
+                    ..Default::default()
+                }
+            }
+        };
+
+        self.notify(BracketChange::UpdateMetadata(metadata));
+        Ok(())
+    }
+
+    /// Updates the ratings after the result of the match with the given `index` changed. The
+    /// current result of the match is used, so the ratings are correct even if multiple updates
+    /// are rated out of order. See [`rate_match`] for details.
+    ///
+    /// [`rate_match`]: crate::ratings::rate_match
+    pub async fn rate(&self, index: usize) -> Result<(), Error> {
+        let result = match_result(&self.inner.bracket.read(), index);
+
+        crate::ratings::rate_match(
+            &self.inner.store,
+            self.inner.tournament_id,
+            self.inner.bracket_id,
+            index,
+            result,
+        )
+        .await
+    }
+}
+
+/// Loads the bracket with the given id from the `store` together with the revisions of all
+/// matches and the times at which matches were concluded.
+#[allow(clippy::type_complexity)]
+async fn load(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+) -> Result<
+    (
+        Tournament<EntrantId, EntrantScore<u64>>,
+        Vec<u64>,
+        HashMap<usize, u64>,
+    ),
+    Error,
+> {
+    let bracket = store.get_bracket(tournament_id, bracket_id).await?.unwrap();
+
+    let state = store.get_bracket_state(tournament_id, bracket_id).await?;
+
+    let stored = match &state {
+        Some(BracketState::Live {
+            revisions,
+            finished,
+            ..
+        }) => Some((revisions.clone(), finished.iter().copied().collect())),
+        _ => None,
+    };
+
+    let tournament = build(bracket, state)?;
+
+    let (mut revisions, finished) = match stored {
+        Some(stored) => stored,
+        None => restore(store, tournament_id, bracket_id, &tournament).await?,
+    };
+    revisions.resize(tournament.matches().len(), 0);
+
+    Ok((tournament, revisions, finished))
+}
+
+/// Stores `tournament` as the state of the bracket with the given id whose stored state cannot be
+/// loaded, e.g. when rolling it back. The revisions are restored from the event log and increased.
+///
+/// The previous state is unknown, so only the ratings of matches that are no longer concluded are
+/// reverted.
+pub async fn recover(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    tournament: Tournament<EntrantId, EntrantScore<u64>>,
+) -> Result<(), Error> {
+    let (mut revisions, finished) = restore(store, tournament_id, bracket_id, &tournament).await?;
+    for revision in revisions.iter_mut() {
+        *revision += 1;
+    }
+
+    let mut finished: Vec<(usize, u64)> = finished.into_iter().collect();
+    finished.sort_unstable();
+
+    let state = BracketState::Live {
+        snapshot: tournament.snapshot(),
+        revisions,
+        finished,
+    };
+    store
+        .update_bracket_state(tournament_id, bracket_id, &Some(state))
+        .await?;
+
+    for index in 0..tournament.matches().len() {
+        if match_result(&tournament, index).is_none() {
+            crate::ratings::rate_match(store, tournament_id, bracket_id, index, None).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if changing the bracket from `before` to `after` recreates all matches.
+pub fn requires_rebuild(before: &Bracket, after: &Bracket) -> bool {
+    before.entrants != after.entrants || before.options != after.options
+}
+
+/// Returns `true` if any match of the `bracket` has a score or a winner.
+fn has_results(bracket: &Tournament<EntrantId, EntrantScore<u64>>) -> bool {
+    bracket.matches().iter().any(|m| {
+        m.entrants.iter().any(|spot| match spot {
+            EntrantSpot::Entrant(entrant) => entrant.data != EntrantScore::default(),
+            _ => false,
+        })
+    })
+}
+
+/// Creates the bracket from its stored `state`. A `None` state creates the initial state of the
+/// bracket.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the `state` is not a valid state of the bracket.
+///
+/// [`Error`]: dynamic_tournament_core::Error
+pub fn build(
+    bracket: Bracket,
+    state: Option<BracketState>,
+) -> dynamic_tournament_core::Result<Tournament<EntrantId, EntrantScore<u64>>> {
+    let kind = match bracket.system {
+        SystemId(1) => TournamentKind::SingleElimination,
+        SystemId(2) => TournamentKind::DoubleElimination,
+        SystemId(3) => TournamentKind::RoundRobin,
+        SystemId(4) => TournamentKind::Swiss,
+        SystemId(5) => TournamentKind::Pools,
+        _ => unreachable!(),
+    };
+
+    let snapshot = match state {
+        Some(BracketState::Live { snapshot, .. }) | Some(BracketState::Snapshot(snapshot)) => {
+            snapshot
+        }
+        // Older brackets only store the matches. Loading them as a legacy snapshot also creates
+        // the initial state if none was stored yet.
+        Some(BracketState::Legacy(matches)) => Snapshot::legacy(
+            kind,
+            bracket.options,
+            bracket.entrants.into(),
+            Some(matches),
+        ),
+        None => Snapshot::legacy(kind, bracket.options, bracket.entrants.into(), None),
+    };
+
+    Tournament::from_snapshot(snapshot)
+}
+
+/// Updates the match with the given `index` in the `bracket` using the `nodes`.
+pub fn update_match(
+    bracket: &mut Tournament<EntrantId, EntrantScore<u64>>,
+    index: usize,
+    nodes: [EntrantScore<u64>; 2],
+) {
+    bracket.update_match(index, |m, res| {
+        let mut loser_index = None;
+
+        for (i, (entrant, node)) in m.entrants.iter_mut().zip(nodes).enumerate() {
+            if let EntrantSpot::Entrant(entrant) = entrant {
+                entrant.data = node;
+            }
+
+            if node.winner {
+                res.winner_default(entrant);
+                loser_index = Some(match i {
+                    0 => 1,
+                    _ => 0,
+                });
+            }
+        }
+
+        if let Some(loser_index) = loser_index {
+            res.loser_default(&m.entrants[loser_index]);
+        }
+    });
+}
+
+/// Increases the revisions of all matches other than `index` that changed compared to
+/// `before`. Updating or resetting a match also changes the matches its entrants move into, and
+/// updates based on the old state of those matches must be rejected.
+fn bump_changed(
+    bracket: &Tournament<EntrantId, EntrantScore<u64>>,
+    before: &Matches<EntrantScore<u64>>,
+    index: usize,
+    revisions: &mut [u64],
+) {
+    for (i, (old, new)) in before.iter().zip(bracket.matches().iter()).enumerate() {
+        if i != index && old != new {
+            if let Some(revision) = revisions.get_mut(i) {
+                *revision += 1;
+            }
+        }
+    }
+}
+
+/// Restores the revisions of all matches of the loaded `tournament` and the times at which
+/// matches were concluded from the event log. Used for brackets whose stored state contains no
+/// revisions.
+///
+/// Updating a match also increases the revisions of the matches its entrants move into, which is
+/// not recorded in the log. Every change of the bracket therefore increases the revisions of all
+/// matches, so that a revision is never lower than before the bracket was unloaded.
+async fn restore(
+    store: &Store,
+    tournament_id: TournamentId,
+    bracket_id: BracketId,
+    tournament: &Tournament<EntrantId, EntrantScore<u64>>,
+) -> Result<(Vec<u64>, HashMap<usize, u64>), Error> {
+    let mut changes = 0;
+    // The last update of a match is the time it was concluded.
+    let mut finished = HashMap::new();
+
+    for event in store.event_log(tournament_id).list().await? {
+        match event.body {
+            LogEventBody::UpdateMatch {
+                bracket_id: id,
+                index,
+                ..
+            } if id == bracket_id => {
+                finished.insert(index as usize, timestamp(event.date));
+            }
+            LogEventBody::ResetMatch { bracket_id: id, .. } if id == bracket_id => (),
+            LogEventBody::Rollback { bracket_id: id, .. } if id == bracket_id => (),
+            // All matches were recreated.
+            LogEventBody::UpdateBracket { id, before, after }
+                if id == bracket_id && requires_rebuild(&before, &after) =>
+            {
+                finished.clear();
+            }
+            _ => continue,
+        }
+
+        changes += 1;
+    }
+
+    // Resets and rollbacks may have reset concluded matches.
+    finished.retain(|index, _| is_concluded(tournament, *index));
+
+    Ok((vec![changes; tournament.matches().len()], finished))
+}
+
+/// Returns `true` if the match with the given `index` in the `bracket` is concluded.
+fn is_concluded(bracket: &Tournament<EntrantId, EntrantScore<u64>>, index: usize) -> bool {
+    bracket
+        .matches()
+        .get(index)
+        .map(|match_| match_.is_concluded())
+        .unwrap_or(false)
+}
+
+/// Returns the [`MatchState`] of the match with the given `index` in the `bracket`.
+fn match_state(
+    bracket: &Tournament<EntrantId, EntrantScore<u64>>,
+    index: usize,
+    revision: u64,
+) -> MatchState {
+    let nodes = bracket.matches()[index].entrants.map(|spot| match spot {
+        EntrantSpot::Entrant(node) => node.data,
+        _ => EntrantScore::default(),
+    });
+
+    MatchState {
+        index: index as u64,
+        revision,
+        nodes,
+    }
+}
+
+/// Returns the entrants of the match with the given `index` and the position of the winner.
+/// Returns `None` if the match is not concluded.
+fn match_result(
+    bracket: &Tournament<EntrantId, EntrantScore<u64>>,
+    index: usize,
+) -> Option<([EntrantId; 2], usize)> {
+    let match_ = bracket.matches().get(index)?;
+    if !match_.is_concluded() {
+        return None;
+    }
+
+    match &match_.entrants {
+        [EntrantSpot::Entrant(a), EntrantSpot::Entrant(b)] => Some((
+            [bracket.entrants()[a.index], bracket.entrants()[b.index]],
+            if a.data.winner { 0 } else { 1 },
+        )),
+        _ => None,
+    }
+}
+
+/// Converts a [`DateTime`] into seconds since the unix epoch, clamping dates before the epoch.
+fn timestamp(date: DateTime<Utc>) -> u64 {
+    date.timestamp().max(0) as u64
+}
+
+/// Converts seconds since the unix epoch into a [`DateTime`], saturating at the latest
+/// representable date.
+fn datetime(timestamp: u64) -> DateTime<Utc> {
+    i64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// Results of expensive computations on the state of a bracket.
+#[derive(Debug, Default)]
+struct AnalysisCache {
+    /// Increased whenever the state of the bracket changes.
+    generation: u64,
+    /// The last prediction together with the ratings it was computed with.
+    prediction: Option<(Vec<f64>, Prediction)>,
+    /// The scenarios for every number of advancing entrants.
+    scenarios: HashMap<usize, Scenarios>,
 }
 
 #[derive(Debug)]
 pub struct LiveBracketInner {
     store: Store,
+    webhooks: Webhooks,
     tournament_id: TournamentId,
     bracket_id: BracketId,
     bracket: RwLock<Tournament<EntrantId, EntrantScore<u64>>>,
-    tx: broadcast::Sender<BracketChange>,
+    /// The revisions of all matches. Always locked after `bracket`.
+    revisions: RwLock<Vec<u64>>,
+    /// The times at which matches were concluded in seconds since the unix epoch. Always locked
+    /// after `bracket`.
+    finished: RwLock<HashMap<usize, u64>>,
+    vetoes: RwLock<HashMap<usize, MatchVeto>>,
+    /// Held while vetoes are written to the store.
+    veto_save: tokio::sync::Mutex<()>,
+    /// Held while the state of the bracket is written to the store.
+    state_save: tokio::sync::Mutex<()>,
+    /// Held while the schedule is read, modified and written back to the store.
+    schedule_save: tokio::sync::Mutex<()>,
+    /// The most recent changes. Always locked after `bracket` and `revisions`.
+    history: Mutex<ChangeHistory>,
+    /// Always locked after `bracket`.
+    analysis: Mutex<AnalysisCache>,
+    tx: broadcast::Sender<ChangeEvent>,
 
     #[allow(clippy::type_complexity)]
     live_brackets: Arc<RwLock<HashMap<(TournamentId, BracketId), Weak<LiveBracketInner>>>>,
@@ -186,15 +1221,17 @@ impl Drop for LiveBracketInner {
 #[derive(Clone, Debug)]
 pub struct LiveBrackets {
     store: Store,
+    webhooks: Webhooks,
     #[allow(clippy::type_complexity)]
     inner: Arc<RwLock<HashMap<(TournamentId, BracketId), Weak<LiveBracketInner>>>>,
 }
 
 impl LiveBrackets {
     #[inline]
-    pub fn new(store: Store) -> Self {
+    pub fn new(store: Store, webhooks: Webhooks) -> Self {
         Self {
             store,
+            webhooks,
             inner: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -233,45 +1270,36 @@ impl LiveBrackets {
 
         log::debug!("LiveBracket not found in map, fetching from store");
 
-        let bracket = self
-            .store
-            .get_bracket(tournament_id, bracket_id)
-            .await?
-            .unwrap();
+        let (tournament, revisions, finished) =
+            load(&self.store, tournament_id, bracket_id).await?;
 
-        let state = self
+        let vetoes = self
             .store
-            .get_bracket_state(tournament_id, bracket_id)
-            .await?;
-
-        let kind = match bracket.system {
-            SystemId(1) => TournamentKind::SingleElimination,
-            SystemId(2) => TournamentKind::DoubleElimination,
-            SystemId(3) => TournamentKind::RoundRobin,
-            SystemId(4) => TournamentKind::Swiss,
-            _ => unreachable!(),
-        };
-
-        let tournament = match state {
-            Some(matches) => {
-                Tournament::resume(kind, bracket.entrants.into(), matches, bracket.options).unwrap()
-            }
-            None => {
-                let mut tournament = Tournament::new(kind, bracket.options);
-                tournament.extend(bracket.entrants);
-                tournament
-            }
-        };
+            .vetoes(tournament_id)
+            .list(bracket_id)
+            .await?
+            .into_iter()
+            .map(|veto| (veto.index, veto))
+            .collect();
 
-        let (tx, _) = broadcast::channel(32);
+        let (tx, _) = broadcast::channel(HISTORY_LEN);
 
         let bracket = LiveBracket {
             user_id: None,
             inner: Arc::new(LiveBracketInner {
                 store: self.store.clone(),
+                webhooks: self.webhooks.clone(),
                 tournament_id,
                 bracket_id,
                 bracket: RwLock::new(tournament),
+                revisions: RwLock::new(revisions),
+                finished: RwLock::new(finished),
+                vetoes: RwLock::new(vetoes),
+                veto_save: tokio::sync::Mutex::new(()),
+                state_save: tokio::sync::Mutex::new(()),
+                schedule_save: tokio::sync::Mutex::new(()),
+                history: Mutex::new(ChangeHistory::new()),
+                analysis: Mutex::new(AnalysisCache::default()),
                 tx,
 
                 live_brackets: self.inner.clone(),
@@ -287,19 +1315,19 @@ impl LiveBrackets {
     }
 }
 
-/// A [`Stream`] over all upcoming [`BracketChange`] events.
+/// A [`Stream`] over all upcoming [`ChangeEvent`]s.
 #[derive(Debug)]
 pub struct EventStream<'a> {
     _bracket: &'a LiveBracket,
-    state: BroadcastStream<BracketChange>,
+    state: BroadcastStream<ChangeEvent>,
     // Since `BroadcastStream::poll_next` doesn't register for future values we need to
     // manually poll after the poll is complete. If the value returns immediately we store
     // them in the buffer.
-    buf: Option<Result<BracketChange, ChangeError>>,
+    buf: Option<Result<ChangeEvent, ChangeError>>,
 }
 
 impl<'a> Stream for EventStream<'a> {
-    type Item = Result<BracketChange, ChangeError>;
+    type Item = Result<ChangeEvent, ChangeError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         log::trace!("EventStream.poll_next");
@@ -351,24 +1379,321 @@ pub enum ChangeError {
     Lagged,
 }
 
+/// The number of changes that are kept in the [`ChangeHistory`].
+const HISTORY_LEN: usize = 32;
+
+/// A [`BracketChange`] together with its id. The ids of the changes of a bracket are strictly
+/// increasing.
+#[derive(Clone, Debug)]
+pub struct ChangeEvent {
+    pub id: u64,
+    pub change: BracketChange,
+}
+
+/// The most recent [`ChangeEvent`]s of a bracket.
+#[derive(Debug)]
+struct ChangeHistory {
+    /// The id of the last change.
+    last_id: u64,
+    events: VecDeque<ChangeEvent>,
+}
+
+impl ChangeHistory {
+    fn new() -> Self {
+        // Ids start at the current time in microseconds. This keeps ids unique after the bracket
+        // was unloaded and loaded again.
+        let last_id = Utc::now().timestamp_micros() as u64;
+
+        Self {
+            last_id,
+            events: VecDeque::with_capacity(HISTORY_LEN),
+        }
+    }
+
+    fn last_id(&self) -> u64 {
+        self.last_id
+    }
+
+    fn push(&mut self, change: BracketChange) -> ChangeEvent {
+        self.last_id += 1;
+
+        if self.events.len() == HISTORY_LEN {
+            self.events.pop_front();
+        }
+
+        let event = ChangeEvent {
+            id: self.last_id,
+            change,
+        };
+        self.events.push_back(event.clone());
+        event
+    }
+
+    fn since(&self, id: u64) -> Option<Vec<ChangeEvent>> {
+        let first = self
+            .events
+            .front()
+            .map(|event| event.id)
+            .unwrap_or(self.last_id + 1);
+
+        if id + 1 < first || id > self.last_id {
+            return None;
+        }
+
+        Some(
+            self.events
+                .iter()
+                .filter(|event| event.id > id)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum BracketChange {
     UpdateMatch {
         index: u64,
+        revision: u64,
         nodes: [EntrantScore<u64>; 2],
     },
     ResetMatch {
         index: usize,
+        revision: u64,
+    },
+    Veto {
+        index: usize,
     },
+    UpdateMetadata(MatchMetadata),
+    Rebuild,
 }
 
 impl From<BracketChange> for Response {
     fn from(this: BracketChange) -> Self {
         match this {
-            BracketChange::UpdateMatch { index, nodes } => Response::UpdateMatch { index, nodes },
-            BracketChange::ResetMatch { index } => Response::ResetMatch {
+            BracketChange::UpdateMatch {
+                index,
+                revision,
+                nodes,
+            } => Response::UpdateMatch {
+                index,
+                revision,
+                nodes,
+            },
+            BracketChange::ResetMatch { index, revision } => Response::ResetMatch {
+                index: index as u64,
+                revision,
+            },
+            BracketChange::Veto { index } => Response::Veto {
                 index: index as u64,
             },
+            BracketChange::UpdateMetadata(metadata) => Response::UpdateMetadata(metadata),
+            BracketChange::Rebuild => Response::Rebuild,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use dynamic_tournament_api::v3::id::{BracketId, RoleId, SystemId, TournamentId};
+    use dynamic_tournament_api::v3::tournaments::brackets::Bracket;
+    use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant, Player};
+    use dynamic_tournament_api::v3::tournaments::ratings::RatingSystem;
+    use dynamic_tournament_api::v3::tournaments::{EntrantKind, Tournament};
+    use dynamic_tournament_core::prediction::DEFAULT_RATING;
+    use dynamic_tournament_core::EntrantScore;
+
+    use super::{build, update_match, BracketChange, ChangeHistory, LiveBrackets, HISTORY_LEN};
+    use crate::store::Store;
+    use crate::webhooks::Webhooks;
+
+    /// Creates a single elimination bracket with four players in a new tournament.
+    async fn bracket(store: &Store) -> (TournamentId, BracketId) {
+        let tournament_id = store
+            .insert_tournament(&Tournament {
+                id: TournamentId(0),
+                name: String::from("test"),
+                description: String::new(),
+                date: Utc::now(),
+                kind: EntrantKind::Player,
+            })
+            .await
+            .unwrap();
+
+        let mut entrants = Vec::new();
+        for index in 0..4 {
+            let player = Player {
+                name: format!("player{}", index),
+                role: RoleId(0),
+                rating: None,
+            };
+
+            let id = store
+                .insert_entrant(tournament_id, Entrant::player(player))
+                .await
+                .unwrap();
+            entrants.push(id);
+        }
+
+        let bracket = Bracket {
+            id: BracketId(0),
+            name: String::from("bracket"),
+            system: SystemId(1),
+            options: Default::default(),
+            entrants,
+            seeding: Default::default(),
+        };
+        let bracket_id = store.insert_bracket(tournament_id, &bracket).await.unwrap();
+
+        (tournament_id, bracket_id)
+    }
+
+    fn winner() -> [EntrantScore<u64>; 2] {
+        [
+            EntrantScore {
+                score: 2,
+                winner: true,
+            },
+            EntrantScore {
+                score: 1,
+                winner: false,
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_restore_revisions() {
+        let store = Store::memory();
+        let webhooks = Webhooks::new(store.clone());
+        let (tournament_id, bracket_id) = bracket(&store).await;
+
+        let live_brackets = LiveBrackets::new(store.clone(), webhooks.clone());
+        let bracket = live_brackets.get(tournament_id, bracket_id).await.unwrap();
+
+        // Both winners move into the final, which changes its revision twice.
+        bracket.update(0, 0, winner()).unwrap();
+        bracket.update(1, 0, winner()).unwrap();
+        bracket.reset(1);
+
+        let (_, revisions) = bracket.state();
+        assert_eq!(revisions, [1, 2, 3]);
+
+        // The state is saved in the background, make sure the latest state was written.
+        bracket.store().await.unwrap();
+
+        // Loads the bracket from the store again.
+        let restored = LiveBrackets::new(store.clone(), webhooks)
+            .get(tournament_id, bracket_id)
+            .await
+            .unwrap();
+
+        let (matches, restored_revisions) = restored.state();
+        assert_eq!(restored_revisions, revisions);
+        assert_eq!(matches, bracket.matches());
+        assert_eq!(restored.progress(60).completed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_replace_ratings() {
+        let store = Store::memory();
+        let (tournament_id, bracket_id) = bracket(&store).await;
+
+        store
+            .ratings(tournament_id)
+            .set_system(&RatingSystem::Elo { k: 32.0 })
+            .await
+            .unwrap();
+
+        let live_brackets = LiveBrackets::new(store.clone(), Webhooks::new(store.clone()));
+        let live_bracket = live_brackets.get(tournament_id, bracket_id).await.unwrap();
+
+        let data = store
+            .get_bracket(tournament_id, bracket_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let ratings = || async {
+            let mut ratings = Vec::new();
+            for id in data.entrants.iter() {
+                let entrant = store
+                    .get_entrant(tournament_id, *id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                match entrant.inner {
+                    // Players without a rating start with the default rating.
+                    EntrantVariant::Player(player) => {
+                        ratings.push(player.rating.map(|r| r as f64).unwrap_or(DEFAULT_RATING))
+                    }
+                    EntrantVariant::Team(_) => unreachable!(),
+                }
+            }
+            ratings
+        };
+
+        let initial = ratings().await;
+
+        let mut tournament = build(data.clone(), None).unwrap();
+        update_match(&mut tournament, 0, winner());
+        live_bracket.replace(tournament).await.unwrap();
+
+        // The winner also moves into the final.
+        let (_, revisions) = live_bracket.state();
+        assert_eq!(revisions, [1, 0, 1]);
+        assert_eq!(live_bracket.progress(60).completed, 1);
+        assert_eq!(
+            store.ratings(tournament_id).history().await.unwrap().len(),
+            2
+        );
+        assert_ne!(ratings().await, initial);
+
+        // Roll back to the initial state.
+        live_bracket
+            .replace(build(data.clone(), None).unwrap())
+            .await
+            .unwrap();
+
+        let (_, revisions) = live_bracket.state();
+        assert_eq!(revisions, [2, 0, 2]);
+        assert_eq!(live_bracket.progress(60).completed, 0);
+        assert!(store
+            .ratings(tournament_id)
+            .history()
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(ratings().await, initial);
+    }
+
+    #[test]
+    fn test_change_history() {
+        let mut history = ChangeHistory::new();
+        let start = history.last_id();
+
+        assert_eq!(history.since(start).unwrap().len(), 0);
+        assert!(history.since(start + 1).is_none());
+
+        for _ in 0..HISTORY_LEN + 2 {
+            history.push(BracketChange::Rebuild);
         }
+
+        let last_id = history.last_id();
+        assert_eq!(last_id, start + HISTORY_LEN as u64 + 2);
+
+        // The first two changes were removed from the history.
+        assert!(history.since(start).is_none());
+        assert!(history.since(start + 1).is_none());
+
+        let events = history.since(start + 2).unwrap();
+        assert_eq!(events.len(), HISTORY_LEN);
+        assert_eq!(events[0].id, start + 3);
+
+        let events = history.since(last_id - 1).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, last_id);
+
+        assert_eq!(history.since(last_id).unwrap().len(), 0);
     }
 }
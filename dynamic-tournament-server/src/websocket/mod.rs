@@ -5,7 +5,7 @@ use std::mem;
 
 use crate::State;
 
-use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::auth::{Claims, Flags};
 use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
 use dynamic_tournament_api::v3::tournaments::brackets::matches::{
     Decode, ErrorResponse, Request, Response,
@@ -112,8 +112,9 @@ where
     #[pin]
     changed: EventStream<'static>,
 
-    /// Id of the connected user. This is `None` if the user didn't authenticate yet.
-    client_user: Option<u64>,
+    /// Claims of the token the connected user authorized with. This is `None` if the user
+    /// didn't authenticate yet.
+    client_user: Option<Claims>,
 }
 
 impl<S> Connection<S>
@@ -271,7 +272,7 @@ where
         let this = self.as_mut().project();
         if let Poll::Ready(res) = this.changed.poll_next(cx) {
             let buf = match res {
-                Some(Ok(event)) => Response::from(event).to_bytes(),
+                Some(Ok(event)) => Response::from(event.change).to_bytes(),
                 // Lagged
                 Some(Err(_)) => Response::Error(ErrorResponse::Lagged).to_bytes(),
                 None => unreachable!(),
@@ -353,8 +354,8 @@ where
                 // The token is valid but we still need to verify the flags.
                 Ok(token) => {
                     if token.claims().flags.intersects(Flags::EDIT_SCORES) {
-                        self.client_user = Some(token.claims().sub);
-                        self.bracket.set_user_id(token.claims().sub);
+                        self.bracket.set_user_id(Some(token.claims().sub));
+                        self.client_user = Some(token.into_claims());
                         None
                     } else {
                         Some(Response::Error(ErrorResponse::Unauthorized))
@@ -366,25 +367,61 @@ where
                 }
             },
             Request::SyncState => {
-                let matches = self.bracket.matches();
-                Some(Response::SyncState(matches))
+                let (matches, revisions) = self.bracket.state();
+                Some(Response::SyncState(matches, revisions))
             }
-            Request::UpdateMatch { index, nodes } => {
-                if self.client_user.is_some() {
-                    self.bracket.update(index, nodes);
-                    None
-                } else {
-                    Some(Response::Error(ErrorResponse::Unauthorized))
+            Request::UpdateMatch {
+                index,
+                revision,
+                nodes,
+            } => {
+                if !self.is_authorized() {
+                    return Some(Response::Error(ErrorResponse::Unauthorized));
+                }
+
+                match self.bracket.update(index, revision, nodes) {
+                    Ok(_) => None,
+                    Err(err) => Some(Response::Error(err)),
                 }
             }
             Request::ResetMatch { index } => {
-                if self.client_user.is_some() {
+                if self.is_authorized() {
                     self.bracket.reset(index as usize);
                     None
                 } else {
                     Some(Response::Error(ErrorResponse::Unauthorized))
                 }
             }
+            Request::Veto { index, map, key } => {
+                // Entrants play using their key, authorized users can play for the entrant whose
+                // turn it is using an empty key.
+                let key = (!key.is_empty()).then_some(key);
+                if key.is_none() && !self.is_authorized() {
+                    return Some(Response::Error(ErrorResponse::Unauthorized));
+                }
+
+                match self
+                    .bracket
+                    .play_veto(index as usize, map as usize, key.as_deref())
+                {
+                    Ok(()) => None,
+                    Err(err) => Some(Response::Error(err)),
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the connected user is authorized. The authorization is dropped once the
+    /// tokens of the user are revoked.
+    fn is_authorized(&mut self) -> bool {
+        match &self.client_user {
+            Some(claims) if self.global_state.auth.is_revoked(claims) => {
+                self.client_user = None;
+                self.bracket.set_user_id(None);
+                false
+            }
+            Some(_) => true,
+            None => false,
         }
     }
 
@@ -1,3 +1,4 @@
+use dynamic_tournament_core::sides::Side;
 use dynamic_tournament_core::{EntrantScore, EntrantSpot};
 use yew::html::{Component, Context, Html};
 use yew::{html, Properties};
@@ -48,10 +49,16 @@ where
             None => String::from("display: hidden;"),
         };
 
+        let title = match ctx.props().side {
+            Some(Side::Home) => "Home",
+            Some(Side::Away) => "Away",
+            None => "",
+        };
+
         html! {
             <div class={classes}>
                 <div class="dt-bracket-team-name dt-flex">
-                    <div class="dt-bracket-team-color" { style }></div>
+                    <div class="dt-bracket-team-color" { style } { title }></div>
                     <span>{ text }</span>
                 </div>
                 <div class="dt-bracket-team-score">{ score }</div>
@@ -65,6 +72,8 @@ pub struct Props<T> {
     pub entrant: EntrantSpot<T>,
     pub node: EntrantSpot<EntrantScore<u64>>,
     pub color: Option<&'static str>,
+    #[prop_or_default]
+    pub side: Option<Side>,
 }
 
 impl<T> PartialEq for Props<T> {
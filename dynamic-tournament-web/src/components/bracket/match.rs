@@ -1,4 +1,6 @@
+use chrono::{DateTime, Utc};
 use dynamic_tournament_core::render::Position;
+use dynamic_tournament_core::sides::Side;
 use yew::prelude::*;
 
 use dynamic_tournament_api::auth::Flags;
@@ -7,7 +9,7 @@ use dynamic_tournament_core::{EntrantScore, EntrantSpot};
 use super::BracketEntrant;
 use crate::components::button::Button;
 use crate::components::icons::{FaPen, FaRotateLeft, FaSize};
-use crate::components::Protected;
+use crate::components::{Protected, Time};
 
 use std::fmt::Display;
 use std::marker::PhantomData;
@@ -54,13 +56,20 @@ where
             .zip(ctx.props().nodes)
             .enumerate()
             .map(|(index, (entrant, node))| {
-                let color = match index {
-                    0 => Some(COLOR_RED),
-                    _ => Some(COLOR_BLUE),
+                let side = ctx.props().sides.map(|sides| sides[index]);
+
+                // The colour follows the side if one is assigned.
+                let color = match side {
+                    Some(Side::Home) => Some(COLOR_RED),
+                    Some(Side::Away) => Some(COLOR_BLUE),
+                    None => match index {
+                        0 => Some(COLOR_RED),
+                        _ => Some(COLOR_BLUE),
+                    },
                 };
 
                 html! {
-                    <BracketEntrant<T> entrant={entrant.clone()} {node} {color} />
+                    <BracketEntrant<T> entrant={entrant.clone()} {node} {color} {side} />
                 }
             })
             .collect();
@@ -101,6 +110,25 @@ where
 
         let number = ctx.props().number;
 
+        let maps = if ctx.props().maps.is_empty() {
+            html! {}
+        } else {
+            html! {
+                <div class="dt-bracket-match-maps">
+                    { ctx.props().maps.join(", ") }
+                </div>
+            }
+        };
+
+        let estimate = match ctx.props().estimate {
+            Some(datetime) => html! {
+                <div class="dt-bracket-match-estimate">
+                    { "ETA " }<Time {datetime} />
+                </div>
+            },
+            None => html! {},
+        };
+
         html! {
             <div class="dt-bracket-match">
                 <span>{ number }</span>
@@ -110,6 +138,8 @@ where
                     </div>
                     {action_button}
                 </div>
+                {maps}
+                {estimate}
             </div>
         }
     }
@@ -119,6 +149,14 @@ where
 pub struct Props<T> {
     pub entrants: [EntrantSpot<T>; 2],
     pub nodes: [EntrantSpot<EntrantScore<u64>>; 2],
+    /// The sides of both entrants, if assigned.
+    pub sides: Option<[Side; 2]>,
+    /// The maps picked in the veto of the match.
+    #[prop_or_default]
+    pub maps: Vec<String>,
+    /// The estimated start time of the match.
+    #[prop_or_default]
+    pub estimate: Option<DateTime<Utc>>,
     pub on_action: Callback<Action>,
     pub number: usize,
     pub position: Position,
@@ -5,13 +5,19 @@ mod r#match;
 mod renderer;
 mod standings;
 
+use std::collections::HashMap;
+
 use dynamic_tournament_api::v3::tournaments::brackets::matches::{
     ErrorResponse, Request, Response,
 };
+use dynamic_tournament_api::v3::tournaments::brackets::veto::MatchVeto;
+use dynamic_tournament_api::v3::tournaments::brackets::Progress;
 use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant};
 use dynamic_tournament_core::options::TournamentOptionValues;
 use dynamic_tournament_core::tournament::TournamentKind;
-use dynamic_tournament_core::{EntrantScore, EntrantSpot, SingleElimination, System};
+use dynamic_tournament_core::{
+    DoubleElimination, EntrantScore, EntrantSpot, Matches, Pools, SingleElimination, System,
+};
 use entrant::BracketEntrant;
 use r#match::{Action, BracketMatch};
 
@@ -28,6 +34,7 @@ use dynamic_tournament_api::v3::tournaments::Tournament as ApiTournament;
 use crate::components::confirmation::Confirmation;
 use crate::components::popup::Popup;
 use crate::components::update_bracket::BracketUpdate;
+use crate::components::Time;
 use crate::services::errorlog::ErrorLog;
 use crate::services::Message as WebSocketMessage;
 use crate::services::{EventBus, WebSocketService};
@@ -45,8 +52,102 @@ use super::providers::{ClientProvider, Provider};
 pub struct Bracket {
     _producer: Box<dyn Bridge<EventBus>>,
     popup: Option<PopupState>,
+    /// The bracket the state was created from. This is refetched after the bracket was edited.
+    bracket: Rc<ApiBracket>,
     state: Option<Tournament<String, EntrantScore<u64>>>,
+    /// The revisions of all matches as last received from the server.
+    revisions: Vec<u64>,
     panel: Panel,
+    /// The maps of all matches with a veto.
+    vetoes: HashMap<usize, Vec<String>>,
+    progress: Option<Progress>,
+    /// `true` while the progress is being fetched.
+    fetching_progress: bool,
+    /// `true` if the bracket changed while the progress was being fetched.
+    progress_outdated: bool,
+}
+
+impl Bracket {
+    fn fetch_vetoes(ctx: &Context<Self>) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament.id;
+        let bracket_id = ctx.props().bracket.id;
+        ctx.link().send_future_batch(async move {
+            match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .veto(bracket_id)
+                .list()
+                .await
+            {
+                Ok(vetoes) => vec![Message::UpdateVetoes(vetoes)],
+                Err(err) => {
+                    ErrorLog::error(err.to_string());
+                    vec![]
+                }
+            }
+        });
+    }
+
+    /// Fetches the bracket after its entrants or options were edited. The state is synchronized
+    /// again once the new bracket was received.
+    fn fetch_bracket(ctx: &Context<Self>) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament.id;
+        let bracket_id = ctx.props().bracket.id;
+        ctx.link().send_future_batch(async move {
+            match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .get(bracket_id)
+                .await
+            {
+                Ok(bracket) => vec![Message::UpdateBracket(bracket)],
+                Err(err) => {
+                    ErrorLog::error(err.to_string());
+                    vec![]
+                }
+            }
+        });
+    }
+
+    /// Fetches the progress of the bracket. If the progress is already being fetched, another
+    /// fetch is started once it completes. This limits the number of requests when many matches
+    /// are updated at once.
+    fn request_progress(&mut self, ctx: &Context<Self>) {
+        if self.fetching_progress {
+            self.progress_outdated = true;
+        } else {
+            self.fetching_progress = true;
+            Self::fetch_progress(ctx);
+        }
+    }
+
+    fn fetch_progress(ctx: &Context<Self>) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament.id;
+        let bracket_id = ctx.props().bracket.id;
+        ctx.link().send_future_batch(async move {
+            match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .progress(bracket_id)
+                .await
+            {
+                Ok(progress) => vec![Message::UpdateProgress(Some(progress))],
+                Err(err) => {
+                    ErrorLog::error(err.to_string());
+                    vec![Message::UpdateProgress(None)]
+                }
+            }
+        });
+    }
 }
 
 impl Component for Bracket {
@@ -64,11 +165,20 @@ impl Component for Bracket {
             }
         });
 
+        Self::fetch_vetoes(ctx);
+        Self::fetch_progress(ctx);
+
         Self {
+            bracket: ctx.props().bracket.clone(),
             state: None,
+            revisions: Vec::new(),
             _producer: EventBus::bridge(ctx.link().callback(Message::HandleResponse)),
             popup: None,
             panel: Panel::default(),
+            vetoes: HashMap::new(),
+            progress: None,
+            fetching_progress: true,
+            progress_outdated: false,
         }
     }
 
@@ -80,7 +190,13 @@ impl Component for Bracket {
             return true;
         }
 
+        self.bracket = ctx.props().bracket.clone();
         self.state = None;
+        self.revisions.clear();
+        self.vetoes.clear();
+        self.progress = None;
+        Self::fetch_vetoes(ctx);
+        self.request_progress(ctx);
         true
     }
 
@@ -109,37 +225,38 @@ impl Component for Bracket {
                                 vec![]
                             });
                         }
+                        // The match was changed by another client before our update was
+                        // applied. Show the current state instead.
+                        if let ErrorResponse::Conflict(state) = err {
+                            ErrorLog::error(
+                                "The match was updated by someone else. Please check the result and try again.",
+                            );
+
+                            if let Some(bracket) = &mut self.state {
+                                update_match(bracket, state.index as usize, state.nodes);
+                            }
+
+                            if let Some(revision) = self.revisions.get_mut(state.index as usize) {
+                                *revision = state.revision;
+                            }
+
+                            return true;
+                        }
                         // We don't handle any other errors.
 
                         false
                     }
 
-                    Response::UpdateMatch { index, nodes } => {
+                    Response::UpdateMatch {
+                        index,
+                        revision,
+                        nodes,
+                    } => {
                         match &mut self.state {
                             Some(bracket) => {
-                                bracket.update_match(index.try_into().unwrap(), |m, res| {
-                                    let mut loser_index = None;
-
-                                    for (i, (entrant, node)) in
-                                        m.entrants.iter_mut().zip(nodes).enumerate()
-                                    {
-                                        if let EntrantSpot::Entrant(entrant) = entrant {
-                                            entrant.data = node;
-                                        }
-
-                                        if node.winner {
-                                            res.winner_default(entrant);
-                                            loser_index = Some(match i {
-                                                0 => 1,
-                                                _ => 0,
-                                            });
-                                        }
-                                    }
-
-                                    if let Some(loser_index) = loser_index {
-                                        res.loser_default(&m.entrants[loser_index]);
-                                    }
-                                });
+                                let matches = bracket.matches().clone();
+                                update_match(bracket, index.try_into().unwrap(), nodes);
+                                bump_changed(bracket, &matches, &mut self.revisions);
                             }
                             // We have no data to update the bracket yet.
                             None => {
@@ -147,46 +264,72 @@ impl Component for Bracket {
                             }
                         }
 
+                        if let Some(rev) = self.revisions.get_mut(index as usize) {
+                            *rev = revision;
+                        }
+
+                        self.request_progress(ctx);
+
                         true
                     }
-                    Response::ResetMatch { index } => {
+                    Response::ResetMatch { index, revision } => {
                         match &mut self.state {
                             Some(state) => {
+                                let matches = state.matches().clone();
                                 state.update_match(index as usize, |_, res| {
                                     res.reset_default();
                                 });
+                                bump_changed(state, &matches, &mut self.revisions);
                             }
                             None => {
                                 log::warn!("Received a ResetMatch frame before initializing the state, ignoring");
                             }
                         }
 
+                        if let Some(rev) = self.revisions.get_mut(index as usize) {
+                            *rev = revision;
+                        }
+
+                        self.request_progress(ctx);
+
                         true
                     }
-                    Response::SyncState(matches) => {
-                        let system_kind = match ctx.props().bracket.system {
+                    Response::SyncState(matches, revisions) => {
+                        self.revisions = revisions;
+
+                        let system_kind = match self.bracket.system {
                             SystemId(1) => TournamentKind::SingleElimination,
                             SystemId(2) => TournamentKind::DoubleElimination,
                             SystemId(3) => TournamentKind::RoundRobin,
                             SystemId(4) => TournamentKind::Swiss,
+                            SystemId(5) => TournamentKind::Pools,
                             _ => unimplemented!(),
                         };
 
                         let options = match system_kind {
-                            TournamentKind::SingleElimination => ctx
-                                .props()
+                            TournamentKind::SingleElimination => self
                                 .bracket
                                 .options
                                 .clone()
                                 .merge(SingleElimination::<u8, EntrantScore<u8>>::options())
                                 .unwrap(),
-                            TournamentKind::DoubleElimination => TournamentOptionValues::default(),
+                            TournamentKind::DoubleElimination => self
+                                .bracket
+                                .options
+                                .clone()
+                                .merge(DoubleElimination::<u8, EntrantScore<u8>>::options())
+                                .unwrap(),
                             TournamentKind::RoundRobin => TournamentOptionValues::default(),
                             TournamentKind::Swiss => TournamentOptionValues::default(),
+                            TournamentKind::Pools => self
+                                .bracket
+                                .options
+                                .clone()
+                                .merge(Pools::<u8, EntrantScore<u8>>::options())
+                                .unwrap(),
                         };
 
-                        let entrants = ctx
-                            .props()
+                        let entrants = self
                             .bracket
                             .entrants
                             .iter()
@@ -217,9 +360,62 @@ impl Component for Bracket {
 
                         true
                     }
+                    Response::Veto { .. } => {
+                        Self::fetch_vetoes(ctx);
+                        false
+                    }
+                    // The entrants or options of the bracket were edited and all matches were
+                    // recreated. The current state is no longer valid.
+                    Response::Rebuild => {
+                        self.state = None;
+                        self.vetoes.clear();
+                        Self::fetch_bracket(ctx);
+                        true
+                    }
                     _ => false,
                 }
             }
+            Message::UpdateVetoes(vetoes) => {
+                self.vetoes = vetoes
+                    .into_iter()
+                    .map(|veto| {
+                        let maps = veto.veto.maps().into_iter().map(String::from).collect();
+                        (veto.index, maps)
+                    })
+                    .collect();
+
+                true
+            }
+            Message::UpdateBracket(bracket) => {
+                self.bracket = Rc::new(bracket);
+
+                if let Some(websocket) = &ctx.props().websocket {
+                    let mut websocket = websocket.clone();
+                    ctx.link().send_future_batch(async move {
+                        let _ = websocket.send(Request::SyncState).await;
+                        vec![]
+                    });
+                }
+
+                Self::fetch_vetoes(ctx);
+                self.request_progress(ctx);
+                false
+            }
+            Message::UpdateProgress(progress) => {
+                self.fetching_progress = false;
+                if self.progress_outdated {
+                    self.progress_outdated = false;
+                    self.request_progress(ctx);
+                }
+
+                match progress {
+                    Some(progress) => {
+                        self.progress = Some(progress);
+                        true
+                    }
+                    None => false,
+                }
+            }
             Message::Action { index, action } => {
                 log::debug!("Called action {:?} on {}", action, index);
 
@@ -242,11 +438,13 @@ impl Component for Bracket {
             Message::UpdateMatch { index, nodes } => {
                 if let Some(websocket) = &ctx.props().websocket {
                     let mut websocket = websocket.clone();
+                    let revision = self.revisions.get(index).copied().unwrap_or_default();
 
                     ctx.link().send_future_batch(async move {
                         let _ = websocket
                             .send(Request::UpdateMatch {
                                 index: index.try_into().unwrap(),
+                                revision,
                                 nodes,
                             })
                             .await;
@@ -334,10 +532,31 @@ impl Component for Bracket {
 
             match ctx.props().panel {
                 Panel::Matches => {
-                    let bracket = HtmlRenderer::new(bracket, ctx).into_output();
+                    let bracket =
+                        HtmlRenderer::new(bracket, &self.vetoes, self.progress.as_ref(), ctx)
+                            .into_output();
+
+                    let progress = match &self.progress {
+                        Some(progress) => {
+                            let end = progress.end.map(|datetime| {
+                                html! {
+                                    <span>{ "Estimated end: " }<Time {datetime} /></span>
+                                }
+                            });
+
+                            html! {
+                                <div class="dt-bracket-progress">
+                                    <span>{ format!("{:.0}% complete", progress.percentage()) }</span>
+                                    { end.unwrap_or_else(|| html! {}) }
+                                </div>
+                            }
+                        }
+                        None => html! {},
+                    };
 
                     html! {
                         <>
+                            { progress }
                             { bracket }
                             { popup }
                         </>
@@ -345,9 +564,15 @@ impl Component for Bracket {
                 }
                 Panel::Standings => {
                     let tournament = Rc::new(self.state.clone().unwrap());
+                    let tournament_id = ctx.props().tournament.id;
+                    let bracket_id = ctx.props().bracket.id;
+                    let scenarios = matches!(
+                        tournament.kind(),
+                        TournamentKind::RoundRobin | TournamentKind::Swiss
+                    );
 
                     html! {
-                        <Standings<Tournament<String, EntrantScore<u64>>> {tournament} />
+                        <Standings<Tournament<String, EntrantScore<u64>>> {tournament} {tournament_id} {bracket_id} {scenarios} />
                     }
                 }
             }
@@ -357,6 +582,54 @@ impl Component for Bracket {
     }
 }
 
+/// Applies the `nodes` to the match with the given `index`.
+fn update_match(
+    bracket: &mut Tournament<String, EntrantScore<u64>>,
+    index: usize,
+    nodes: [EntrantScore<u64>; 2],
+) {
+    bracket.update_match(index, |m, res| {
+        let mut loser_index = None;
+
+        for (i, (entrant, node)) in m.entrants.iter_mut().zip(nodes).enumerate() {
+            if let EntrantSpot::Entrant(entrant) = entrant {
+                entrant.data = node;
+            }
+
+            if node.winner {
+                res.winner_default(entrant);
+                loser_index = Some(match i {
+                    0 => 1,
+                    _ => 0,
+                });
+            }
+        }
+
+        if let Some(loser_index) = loser_index {
+            res.loser_default(&m.entrants[loser_index]);
+        }
+    });
+}
+
+/// Increases the revisions of all matches that changed compared to `before`, the same way the
+/// server does when a match is updated or reset. The revision of the match that was updated is
+/// overwritten with the revision sent by the server afterwards.
+fn bump_changed(
+    bracket: &Tournament<String, EntrantScore<u64>>,
+    before: &Matches<EntrantScore<u64>>,
+    revisions: &mut [u64],
+) {
+    for ((old, new), revision) in before
+        .iter()
+        .zip(bracket.matches().iter())
+        .zip(revisions.iter_mut())
+    {
+        if old != new {
+            *revision += 1;
+        }
+    }
+}
+
 pub enum Message {
     /// Authorize using the current credentials. This may be sent be multiple times.
     Authorize,
@@ -371,6 +644,11 @@ pub enum Message {
         nodes: [EntrantScore<u64>; 2],
     },
     ResetMatch(usize),
+    UpdateVetoes(Vec<MatchVeto>),
+    /// The bracket was fetched again after it was edited.
+    UpdateBracket(ApiBracket),
+    /// The fetched progress. `None` if fetching the progress failed.
+    UpdateProgress(Option<Progress>),
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -1,6 +1,8 @@
 //! HTML renderer
+use std::collections::HashMap;
 use std::fmt::Display;
 
+use dynamic_tournament_api::v3::tournaments::brackets::Progress;
 use dynamic_tournament_core::render::{self, Column, Element, Position, Renderer, Row};
 use dynamic_tournament_core::{EntrantScore, Match, Node, System};
 use yew::{html, Context, Html};
@@ -15,6 +17,8 @@ where
     output: Html,
     ctx: &'a Context<Bracket>,
     tournament: &'a T,
+    vetoes: &'a HashMap<usize, Vec<String>>,
+    progress: Option<&'a Progress>,
 }
 
 impl<'a, T, E> HtmlRenderer<'a, T, E>
@@ -22,11 +26,18 @@ where
     T: System<Entrant = E, NodeData = EntrantScore<u64>>,
     E: Clone + Display + 'static,
 {
-    pub fn new(tournament: &'a T, ctx: &'a Context<Bracket>) -> Self {
+    pub fn new(
+        tournament: &'a T,
+        vetoes: &'a HashMap<usize, Vec<String>>,
+        progress: Option<&'a Progress>,
+        ctx: &'a Context<Bracket>,
+    ) -> Self {
         Self {
             output: html! {},
             ctx,
             tournament,
+            vetoes,
+            progress,
         }
     }
 
@@ -113,15 +124,22 @@ where
         let nodes = match_.map(|spot| spot.map(|node| node.data));
 
         let position = m.position.unwrap_or(Position::SpaceAround);
+        let sides = m.sides();
 
         let index = m.index();
+        let maps = self.vetoes.get(&index).cloned().unwrap_or_default();
+        let estimate = self
+            .progress
+            .and_then(|progress| progress.matches.get(index).copied().flatten())
+            .map(|estimate| estimate.start);
+
         let on_action = self
             .ctx
             .link()
             .callback(move |action| Message::Action { index, action });
 
         html! {
-            <BracketMatch<E> {entrants} {nodes} {on_action} number={round_index + 1} {position} />
+            <BracketMatch<E> {entrants} {nodes} {sides} {maps} {estimate} {on_action} number={round_index + 1} {position} />
         }
     }
 }
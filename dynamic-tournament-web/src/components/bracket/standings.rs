@@ -2,32 +2,169 @@ use std::cmp::Ordering;
 use std::fmt::Display;
 use std::marker::PhantomData;
 
-use dynamic_tournament_core::{EntrantScore, System};
+use dynamic_tournament_api::v3::id::{BracketId, TournamentId};
+use dynamic_tournament_api::v3::tournaments::brackets::{Prediction, Scenarios};
+use dynamic_tournament_core::scenarios::Status;
+use dynamic_tournament_core::{EntrantScore, Matches, System};
 use yew::{html, Component, Context, Html, Properties};
 
+use crate::components::providers::{ClientProvider, Provider};
+use crate::components::{Button, ParseInput};
+use crate::services::errorlog::ErrorLog;
 use crate::utils::Rc;
 
 pub struct Standings<S>
 where
     S: System + 'static,
 {
+    /// Whether the odds column is shown.
+    show_odds: bool,
+    prediction: Option<Prediction>,
+    /// Whether the scenarios column is shown.
+    show_scenarios: bool,
+    /// The number of positions that advance.
+    top: usize,
+    scenarios: Option<Scenarios>,
+    /// The matches the prediction and scenarios were requested for.
+    matches: Matches<EntrantScore<u64>>,
     _marker: PhantomData<S>,
 }
 
+impl<S, E> Standings<S>
+where
+    S: System<Entrant = E, NodeData = EntrantScore<u64>> + 'static,
+    E: Display,
+{
+    fn fetch_prediction(ctx: &Context<Self>) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament_id;
+        let bracket_id = ctx.props().bracket_id;
+        ctx.link().send_future_batch(async move {
+            match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .prediction(bracket_id)
+                .await
+            {
+                Ok(prediction) => vec![Message::UpdatePrediction(prediction)],
+                Err(err) => {
+                    ErrorLog::error(err.to_string());
+                    vec![]
+                }
+            }
+        });
+    }
+
+    fn fetch_scenarios(&self, ctx: &Context<Self>) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament_id;
+        let bracket_id = ctx.props().bracket_id;
+        let top = self.top;
+        ctx.link().send_future_batch(async move {
+            match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .scenarios(bracket_id, top)
+                .await
+            {
+                Ok(scenarios) => vec![Message::UpdateScenarios(scenarios)],
+                Err(err) => {
+                    ErrorLog::error(err.to_string());
+                    vec![]
+                }
+            }
+        });
+    }
+}
+
 impl<S, E> Component for Standings<S>
 where
     S: System<Entrant = E, NodeData = EntrantScore<u64>> + 'static,
     E: Display,
 {
-    type Message = ();
+    type Message = Message;
     type Properties = Props<S>;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         Self {
+            show_odds: false,
+            prediction: None,
+            show_scenarios: false,
+            // By default the better half of all entrants advances.
+            top: (ctx.props().tournament.entrants().len() / 2).max(1),
+            scenarios: None,
+            matches: ctx.props().tournament.matches().clone(),
             _marker: PhantomData,
         }
     }
 
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Message::ToggleOdds => {
+                self.show_odds = !self.show_odds;
+
+                if self.show_odds && self.prediction.is_none() {
+                    Self::fetch_prediction(ctx);
+                }
+
+                true
+            }
+            Message::UpdatePrediction(prediction) => {
+                self.prediction = Some(prediction);
+                true
+            }
+            Message::ToggleScenarios => {
+                self.show_scenarios = !self.show_scenarios;
+
+                if self.show_scenarios && self.scenarios.is_none() {
+                    self.fetch_scenarios(ctx);
+                }
+
+                true
+            }
+            Message::UpdateTop(top) => {
+                self.top = top;
+                self.scenarios = None;
+                self.fetch_scenarios(ctx);
+
+                true
+            }
+            Message::UpdateScenarios(scenarios) => {
+                // Drop responses for a previous number of positions.
+                if scenarios.top == self.top {
+                    self.scenarios = Some(scenarios);
+                }
+
+                true
+            }
+        }
+    }
+
+    // The prediction and scenarios are outdated once any match in the bracket changes.
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let matches = ctx.props().tournament.matches();
+
+        if self.matches != *matches {
+            self.matches = matches.clone();
+            self.prediction = None;
+            self.scenarios = None;
+
+            if self.show_odds {
+                Self::fetch_prediction(ctx);
+            }
+
+            if self.show_scenarios {
+                self.fetch_scenarios(ctx);
+            }
+        }
+
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> yew::Html {
         let standings = ctx.props().tournament.standings();
 
@@ -49,6 +186,87 @@ where
                     })
                     .collect();
 
+                let odds = if self.show_odds {
+                    let odds = self
+                        .prediction
+                        .as_ref()
+                        .and_then(|prediction| prediction.entrants.get(entry.index))
+                        .map(|prediction| format!("{:.1}%", prediction.win() * 100.0))
+                        .unwrap_or_default();
+
+                    html! {
+                        <td>
+                            { odds }
+                        </td>
+                    }
+                } else {
+                    html! {}
+                };
+
+                let scenarios = if self.show_scenarios {
+                    let badge = self
+                        .scenarios
+                        .as_ref()
+                        .and_then(|scenarios| {
+                            Some((scenarios, scenarios.entrants.get(entry.index)?))
+                        })
+                        .map(|(scenarios, entrant)| {
+                            let (text, class) = match entrant.status {
+                                Status::Clinched => ("Clinched", "dt-badge dt-badge-clinched"),
+                                Status::Eliminated => {
+                                    ("Eliminated", "dt-badge dt-badge-eliminated")
+                                }
+                                Status::Alive => ("Alive", "dt-badge dt-badge-alive"),
+                            };
+
+                            // List the results the entrant depends on.
+                            let needs = entrant
+                                .requirements
+                                .iter()
+                                .map(|requirement| {
+                                    let winner = scenarios
+                                        .entrants
+                                        .iter()
+                                        .position(|e| e.entrant == requirement.winner)
+                                        .and_then(|index| {
+                                            ctx.props().tournament.entrants().get(index)
+                                        })
+                                        .map(|name| name.to_string())
+                                        .unwrap_or_default();
+
+                                    format!("{} wins {}", winner, requirement.code)
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ");
+
+                            let needs = if needs.is_empty() {
+                                html! {}
+                            } else {
+                                html! {
+                                    <span class="dt-badge-requirements">
+                                        { format!("Needs: {}", needs) }
+                                    </span>
+                                }
+                            };
+
+                            html! {
+                                <>
+                                    <span class={class}>{ text }</span>
+                                    { needs }
+                                </>
+                            }
+                        })
+                        .unwrap_or_default();
+
+                    html! {
+                        <td>
+                            { badge }
+                        </td>
+                    }
+                } else {
+                    html! {}
+                };
+
                 html! {
                     <tr>
                         <td>
@@ -58,6 +276,8 @@ where
                             { name }
                         </td>
                         { values }
+                        { odds }
+                        { scenarios }
                     </tr>
                 }
             })
@@ -74,19 +294,78 @@ where
             })
             .collect();
 
+        let odds = if self.show_odds {
+            html! {
+                <th>
+                    { "Odds" }
+                </th>
+            }
+        } else {
+            html! {}
+        };
+
+        let scenarios = if self.show_scenarios {
+            html! {
+                <th>
+                    { "Scenarios" }
+                </th>
+            }
+        } else {
+            html! {}
+        };
+
+        let on_toggle_odds = ctx.link().callback(|_| Message::ToggleOdds);
+
+        let scenarios_controls = if ctx.props().scenarios {
+            let on_toggle_scenarios = ctx.link().callback(|_| Message::ToggleScenarios);
+
+            let top = if self.show_scenarios {
+                let onchange = ctx.link().callback(Message::UpdateTop);
+                let value = self.top.to_string();
+
+                html! {
+                    <label>
+                        { "Top" }
+                        <ParseInput<usize> kind="number" {value} {onchange} />
+                    </label>
+                }
+            } else {
+                html! {}
+            };
+
+            html! {
+                <>
+                    <Button onclick={on_toggle_scenarios} title="Toggle Scenarios">
+                        <span>{ "Scenarios" }</span>
+                    </Button>
+                    { top }
+                </>
+            }
+        } else {
+            html! {}
+        };
+
         html! {
-            <table class="dt-table dt-table-striped">
-                <tr>
-                    <th>
-                        { "Position" }
-                    </th>
-                    <th>
-                        { "Name" }
-                    </th>
-                    { keys }
-                </tr>
-                { scores }
-            </table>
+            <div>
+                <Button onclick={on_toggle_odds} title="Toggle Odds">
+                    <span>{ "Odds" }</span>
+                </Button>
+                { scenarios_controls }
+                <table class="dt-table dt-table-striped">
+                    <tr>
+                        <th>
+                            { "Position" }
+                        </th>
+                        <th>
+                            { "Name" }
+                        </th>
+                        { keys }
+                        { odds }
+                        { scenarios }
+                    </tr>
+                    { scores }
+                </table>
+            </div>
         }
     }
 }
@@ -97,6 +376,11 @@ where
     S: System<NodeData = EntrantScore<u64>>,
 {
     pub tournament: Rc<S>,
+    pub tournament_id: TournamentId,
+    pub bracket_id: BracketId,
+    /// Whether the clinch and elimination scenarios are available for the bracket.
+    #[prop_or_default]
+    pub scenarios: bool,
 }
 
 impl<S> PartialEq for Props<S>
@@ -106,9 +390,20 @@ where
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         self.tournament == other.tournament
+            && self.tournament_id == other.tournament_id
+            && self.bracket_id == other.bracket_id
+            && self.scenarios == other.scenarios
     }
 }
 
+pub enum Message {
+    ToggleOdds,
+    UpdatePrediction(Prediction),
+    ToggleScenarios,
+    UpdateTop(usize),
+    UpdateScenarios(Scenarios),
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Score {
     wins: usize,
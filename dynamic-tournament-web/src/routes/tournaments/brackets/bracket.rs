@@ -1,12 +1,15 @@
+use dynamic_tournament_api::v3::tournaments::brackets::matches::Response;
 use dynamic_tournament_api::v3::tournaments::brackets::BracketOverview;
 use dynamic_tournament_api::v3::tournaments::entrants::Entrant;
 use dynamic_tournament_api::v3::tournaments::Tournament;
 use dynamic_tournament_api::v3::{id::BracketId, tournaments::brackets::Bracket as ApiBracket};
 use yew::{html, Component, Context, Html, Properties};
+use yew_agent::{Bridge, Bridged};
 
 use crate::components::bracket::LiveBracket;
 use crate::components::providers::{ClientProvider, Provider};
 use crate::components::BracketList;
+use crate::services::{EventBus, Message as WebSocketMessage};
 use crate::utils::router::RouterContextExt;
 use crate::utils::{FetchData, Rc};
 
@@ -17,11 +20,34 @@ pub struct Props {
 }
 
 pub struct Bracket {
+    _producer: Box<dyn Bridge<EventBus>>,
     brackets: FetchData<Rc<Vec<BracketOverview>>>,
     bracket: FetchData<Rc<ApiBracket>>,
     entrants: FetchData<Rc<Vec<Entrant>>>,
 }
 
+impl Bracket {
+    fn fetch_bracket(ctx: &Context<Self>, id: BracketId) {
+        let client = ClientProvider::get(ctx);
+
+        let tournament_id = ctx.props().tournament.id;
+        ctx.link().send_future(async move {
+            let msg = match client
+                .v3()
+                .tournaments()
+                .brackets(tournament_id)
+                .get(id)
+                .await
+            {
+                Ok(b) => FetchData::from(Rc::new(b)),
+                Err(err) => FetchData::from_err(err),
+            };
+
+            Message::UpdateBracket(msg)
+        });
+    }
+}
+
 impl Component for Bracket {
     type Message = Message;
     type Properties = Props;
@@ -50,23 +76,7 @@ impl Component for Bracket {
             });
         }
 
-        {
-            let client = client.clone();
-            ctx.link().send_future(async move {
-                let msg = match client
-                    .v3()
-                    .tournaments()
-                    .brackets(tournament_id)
-                    .get(id)
-                    .await
-                {
-                    Ok(b) => FetchData::from(Rc::new(b)),
-                    Err(err) => FetchData::from_err(err),
-                };
-
-                Message::UpdateBracket(msg)
-            });
-        }
+        Self::fetch_bracket(ctx, id);
 
         ctx.link().send_future(async move {
             let msg = match client
@@ -84,6 +94,7 @@ impl Component for Bracket {
         });
 
         Self {
+            _producer: EventBus::bridge(ctx.link().callback(Message::Ws)),
             brackets: FetchData::new(),
             entrants: FetchData::new(),
             bracket: FetchData::new(),
@@ -95,30 +106,25 @@ impl Component for Bracket {
             Message::UpdateBrackets(bracket) => self.brackets = bracket,
             Message::UpdateEntrants(entrants) => self.entrants = entrants,
             Message::UpdateBracket(bracket) => self.bracket = bracket,
-            Message::OnClick(id) => {
-                let tournament_id = ctx.props().tournament.id;
+            // The bracket was edited, fetch the new entrants and options.
+            Message::Ws(WebSocketMessage::Response(Response::Rebuild)) => {
+                let id = if self.bracket.has_value() {
+                    self.bracket.as_ref().unwrap().id
+                } else {
+                    ctx.props().bracket_id
+                };
 
+                Self::fetch_bracket(ctx, id);
+                return false;
+            }
+            Message::Ws(_) => return false,
+            Message::OnClick(id) => {
                 // Don't update when requesting the same bracket.
                 if self.bracket.has_value() && self.bracket.as_ref().unwrap().id == id {
                     return false;
                 }
 
-                let client = ClientProvider::get(ctx);
-
-                ctx.link().send_future(async move {
-                    let msg = match client
-                        .v3()
-                        .tournaments()
-                        .brackets(tournament_id)
-                        .get(id)
-                        .await
-                    {
-                        Ok(b) => FetchData::from(Rc::new(b)),
-                        Err(err) => FetchData::from_err(err),
-                    };
-
-                    Message::UpdateBracket(msg)
-                });
+                Self::fetch_bracket(ctx, id);
 
                 ctx.router().update(|path| {
                     path.last_mut().unwrap().replace(id.to_string());
@@ -159,4 +165,5 @@ pub enum Message {
     UpdateBracket(FetchData<Rc<ApiBracket>>),
     UpdateEntrants(FetchData<Rc<Vec<Entrant>>>),
     OnClick(BracketId),
+    Ws(WebSocketMessage),
 }
@@ -1,4 +1,5 @@
 use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant};
 use dynamic_tournament_api::v3::tournaments::log::{LogEvent, LogEventBody};
 use dynamic_tournament_api::v3::tournaments::Tournament;
 use yew::{html, Component, Html, Properties};
@@ -69,7 +70,7 @@ impl Component for LogInner {
             let events = events
                 .iter()
                 .map(|event| {
-                    let body = match event.body {
+                    let body = match &event.body {
                         LogEventBody::UpdateMatch {
                             bracket_id,
                             index,
@@ -83,6 +84,133 @@ impl Component for LogInner {
                         LogEventBody::ResetMatch { bracket_id, index } => {
                             format!("Reset match {} (Bracket {})", index, bracket_id)
                         }
+                        LogEventBody::ShuffleBracket { bracket_id, seed } => {
+                            format!(
+                                "Shuffled entrants with seed {} (Bracket {})",
+                                seed, bracket_id
+                            )
+                        }
+                        LogEventBody::Veto {
+                            bracket_id,
+                            index,
+                            maps,
+                        } => {
+                            format!(
+                                "Finished veto of match {} with {} (Bracket {})",
+                                index,
+                                maps.join(", "),
+                                bracket_id
+                            )
+                        }
+                        LogEventBody::Rollback {
+                            bracket_id,
+                            event_id,
+                        } => {
+                            format!("Rolled back to event {} (Bracket {})", event_id, bracket_id)
+                        }
+                        LogEventBody::CreateTournament { tournament, .. } => {
+                            format!("Created tournament {}", tournament.name)
+                        }
+                        LogEventBody::UpdateTournament { after, .. } => {
+                            format!("Updated tournament {}", after.name)
+                        }
+                        LogEventBody::DeleteTournament { tournament, .. } => {
+                            format!("Deleted tournament {}", tournament.name)
+                        }
+                        LogEventBody::CreateEntrant { entrant, .. } => {
+                            format!("Created entrant {}", entrant_name(entrant))
+                        }
+                        LogEventBody::UpdateEntrant { after, .. } => {
+                            format!("Updated entrant {}", entrant_name(after))
+                        }
+                        LogEventBody::DeleteEntrant { entrant, .. } => {
+                            format!("Deleted entrant {}", entrant_name(entrant))
+                        }
+                        LogEventBody::CreateRole { role, .. } => {
+                            format!("Created role {}", role.name)
+                        }
+                        LogEventBody::DeleteRole { role, .. } => {
+                            format!("Deleted role {}", role.name)
+                        }
+                        LogEventBody::CreateBracket { id, bracket } => {
+                            format!("Created bracket {} (Bracket {})", bracket.name, id)
+                        }
+                        LogEventBody::UpdateBracket { id, after, .. } => {
+                            format!("Updated bracket {} (Bracket {})", after.name, id)
+                        }
+                        LogEventBody::DeleteBracket { id, bracket } => {
+                            format!("Deleted bracket {} (Bracket {})", bracket.name, id)
+                        }
+                        LogEventBody::UpdateSchedule {
+                            bracket_id,
+                            before,
+                            after,
+                        } => {
+                            format!(
+                                "{} schedule (Bracket {})",
+                                change(before, after),
+                                bracket_id
+                            )
+                        }
+                        LogEventBody::UpdateVetoConfig {
+                            bracket_id,
+                            before,
+                            after,
+                        } => {
+                            format!(
+                                "{} veto config (Bracket {})",
+                                change(before, after),
+                                bracket_id
+                            )
+                        }
+                        LogEventBody::UpdateMatchVeto {
+                            bracket_id,
+                            index,
+                            before,
+                            after,
+                        } => {
+                            format!(
+                                "{} veto of match {} (Bracket {})",
+                                change(before, after),
+                                index,
+                                bracket_id
+                            )
+                        }
+                        LogEventBody::UpdateMatchMetadata {
+                            bracket_id,
+                            index,
+                            before,
+                            after,
+                        } => {
+                            format!(
+                                "{} metadata of match {} (Bracket {})",
+                                change(before, after),
+                                index,
+                                bracket_id
+                            )
+                        }
+                        LogEventBody::UpdateRatingSystem { before, after } => {
+                            format!("{} rating system", change(before, after))
+                        }
+                        LogEventBody::CreateWebhook { id, webhook } => {
+                            format!("Created webhook {} (Webhook {})", webhook.url, id)
+                        }
+                        LogEventBody::DeleteWebhook { id, webhook } => {
+                            format!("Deleted webhook {} (Webhook {})", webhook.url, id)
+                        }
+                        LogEventBody::CreateUser { username, .. } => {
+                            format!("Created user {}", username)
+                        }
+                        LogEventBody::UpdateUser { before, after, .. } => {
+                            if before == after {
+                                format!("Updated user {}", after)
+                            } else {
+                                format!("Renamed user {} to {}", before, after)
+                            }
+                        }
+                        LogEventBody::DeleteUser { username, .. } => {
+                            format!("Deleted user {}", username)
+                        }
                     };
 
                     html! {
@@ -103,3 +231,19 @@ impl Component for LogInner {
         })
     }
 }
+
+fn entrant_name(entrant: &Entrant) -> &str {
+    match &entrant.inner {
+        EntrantVariant::Player(player) => &player.name,
+        EntrantVariant::Team(team) => &team.name,
+    }
+}
+
+/// Returns the verb describing a change from `before` to `after`.
+fn change<T>(before: &Option<T>, after: &Option<T>) -> &'static str {
+    match (before, after) {
+        (None, Some(_)) => "Created",
+        (Some(_), None) => "Deleted",
+        _ => "Updated",
+    }
+}
@@ -0,0 +1,161 @@
+use dynamic_tournament_api::auth::Flags;
+use dynamic_tournament_api::v3::id::EntrantId;
+use dynamic_tournament_api::v3::tournaments::brackets::BracketOverview;
+use dynamic_tournament_api::v3::tournaments::entrants::{Entrant, EntrantVariant};
+use dynamic_tournament_api::v3::tournaments::matches::UpcomingMatches;
+use dynamic_tournament_api::v3::tournaments::Tournament;
+use yew::{html, Component, Html, Properties};
+
+use crate::components::protected::ForbiddenAction;
+use crate::components::providers::{ClientProvider, Provider};
+use crate::components::{Protected, Time};
+use crate::utils::{FetchData, Rc};
+
+#[derive(Clone, Debug, PartialEq, Eq, Properties)]
+pub struct Props {
+    pub tournament: Rc<Tournament>,
+}
+
+/// An overview of all matches that can be called next.
+pub struct Matches;
+
+impl Component for Matches {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        html! {
+            <Protected flags={Flags::EDIT_SCORES} action={ForbiddenAction::NotFound}>
+                <MatchesInner tournament={ctx.props().tournament.clone()} />
+            </Protected>
+        }
+    }
+}
+
+type Data = (Vec<UpcomingMatches>, Vec<BracketOverview>, Vec<Entrant>);
+
+#[derive(Debug)]
+struct MatchesInner {
+    data: FetchData<Data>,
+}
+
+impl Component for MatchesInner {
+    type Message = FetchData<Data>;
+    type Properties = Props;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        let client = ClientProvider::get(ctx);
+
+        let id = ctx.props().tournament.id;
+        ctx.link().send_future(async move {
+            let client = client.v3();
+            let tournaments = client.tournaments();
+
+            let data = async {
+                let upcoming = tournaments.matches(id).next().await?;
+                let brackets = tournaments.brackets(id).list().await?;
+                let entrants = tournaments.entrants(id).list().await?;
+
+                Ok::<_, dynamic_tournament_api::Error>((upcoming, brackets, entrants))
+            };
+
+            match data.await {
+                Ok(val) => FetchData::from(val),
+                Err(err) => FetchData::from_err(err),
+            }
+        });
+
+        Self {
+            data: FetchData::new(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        self.data = msg;
+        true
+    }
+
+    fn view(&self, _ctx: &yew::Context<Self>) -> yew::Html {
+        self.data.render(|(upcoming, brackets, entrants)| {
+            let brackets: Html = upcoming
+                .iter()
+                .map(|bracket| {
+                    let name = brackets
+                        .iter()
+                        .find(|b| b.id == bracket.bracket_id)
+                        .map(|b| b.name.clone())
+                        .unwrap_or_else(|| bracket.bracket_id.to_string());
+
+                    let matches: Html = bracket
+                        .playable
+                        .iter()
+                        .map(|m| {
+                            let [a, b] = m.entrants.map(|id| entrant_name(entrants, id));
+
+                            let (scheduled, stream) = match &m.metadata {
+                                Some(metadata) => (
+                                    metadata.scheduled.map(|datetime| {
+                                        html! { <Time {datetime} /> }
+                                    }),
+                                    metadata.stream.clone().map(|stream| {
+                                        html! { <a href={stream.clone()}>{ stream }</a> }
+                                    }),
+                                ),
+                                None => (None, None),
+                            };
+
+                            html! {
+                                <tr>
+                                    <td>{ m.code.clone() }</td>
+                                    <td>{ a }</td>
+                                    <td>{ b }</td>
+                                    <td>{ scheduled.unwrap_or_else(|| html! {}) }</td>
+                                    <td>{ stream.unwrap_or_else(|| html! {}) }</td>
+                                </tr>
+                            }
+                        })
+                        .collect();
+
+                    html! {
+                        <div>
+                            <h3>{ name }</h3>
+                            <span>
+                                { format!("{} playable, {} waiting", bracket.playable.len(), bracket.blocked.len()) }
+                            </span>
+                            <table class="dt-table dt-table-striped">
+                                <tr>
+                                    <th>{ "Match" }</th>
+                                    <th>{ "Entrant" }</th>
+                                    <th>{ "Entrant" }</th>
+                                    <th>{ "Scheduled" }</th>
+                                    <th>{ "Stream" }</th>
+                                </tr>
+                                { matches }
+                            </table>
+                        </div>
+                    }
+                })
+                .collect();
+
+            html! {
+                <div>
+                    { brackets }
+                </div>
+            }
+        })
+    }
+}
+
+fn entrant_name(entrants: &[Entrant], id: EntrantId) -> String {
+    match entrants.iter().find(|entrant| entrant.id == id) {
+        Some(entrant) => match &entrant.inner {
+            EntrantVariant::Player(player) => player.name.clone(),
+            EntrantVariant::Team(team) => team.name.clone(),
+        },
+        None => String::from("Unknown"),
+    }
+}
@@ -2,6 +2,7 @@ mod admin;
 mod brackets;
 mod entrants;
 mod log;
+mod matches;
 mod navbar;
 mod overview;
 mod tournament;
@@ -32,6 +32,7 @@ impl Component for Navbar {
             (Route::Brackets, "Brackets", None),
             (Route::Entrants, "Entrants", None),
             (Route::Log, "Log", Some(Flags::ADMIN)),
+            (Route::Matches, "Next Matches", Some(Flags::EDIT_SCORES)),
             (Route::Admin, "Admin", Some(Flags::ADMIN)),
         ];
 
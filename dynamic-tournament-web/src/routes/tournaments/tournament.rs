@@ -7,6 +7,7 @@ use crate::utils::router::{Link, PathBuf, Routable, Switch};
 use crate::utils::{FetchData, Rc};
 
 use super::log::Log;
+use super::matches::Matches;
 use super::navbar::Navbar;
 use super::{Admin, Brackets, Entrants, Overview};
 use crate::components::icons::FaAngleLeft;
@@ -63,6 +64,7 @@ pub enum Route {
     Brackets,
     Entrants,
     Log,
+    Matches,
     Admin,
 }
 
@@ -73,6 +75,7 @@ impl Routable for Route {
             Some("brackets") => Some(Self::Brackets),
             Some("entrants") => Some(Self::Entrants),
             Some("log") => Some(Self::Log),
+            Some("matches") => Some(Self::Matches),
             Some("admin") => Some(Self::Admin),
             Some(_) => None,
         }
@@ -84,6 +87,7 @@ impl Routable for Route {
             Self::Brackets => String::from("/brackets"),
             Self::Entrants => String::from("/entrants"),
             Self::Log => String::from("/log"),
+            Self::Matches => String::from("/matches"),
             Self::Admin => String::from("/admin"),
         }
     }
@@ -107,6 +111,9 @@ fn switch(tournament: Rc<ApiTournament>) -> impl Fn(&Route) -> Html {
                 Route::Log => html! {
                     <Log {tournament} />
                 },
+                Route::Matches => html! {
+                    <Matches {tournament} />
+                },
                 Route::Admin => html! {
                     <Admin {tournament} />
                 },